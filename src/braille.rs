@@ -5,10 +5,11 @@ use crate::errors::*;
 use crate::pretty_print::mml_to_string;
 use crate::prefs::PreferenceManager;
 use std::cell::Ref;
-use regex::{Captures, Regex, RegexSet};
+use regex::{Captures, Regex};
 use phf::{phf_map, phf_set};
-use crate::speech::{BRAILLE_RULES, SpeechRulesWithContext};
+use crate::speech::{BRAILLE_RULES, SpeechRules, SpeechRulesWithContext};
 use std::ops::Range;
+use std::collections::{HashMap, HashSet};
 
 static UEB_PREFIXES: phf::Set<char> = phf_set! {
     '⠼', '⠈', '⠘', '⠸', '⠐', '⠨', '⠰', '⠠',
@@ -17,36 +18,76 @@ static UEB_PREFIXES: phf::Set<char> = phf_set! {
 
 /// braille the MathML
 /// If 'nav_node_id' is not an empty string, then the element with that id will have dots 7 & 8 turned on as per the pref
+///
+/// If the `BrailleSpatialLayout` pref is true and the math is an `mstack`, `mlongdiv`, or matrix,
+/// the braille is laid out spatially with each row on its own line (separated by '\n').
 pub fn braille_mathml(mathml: Element, nav_node_id: &str) -> Result<String> {
+    return Ok( braille_mathml_with_nav_range(mathml, nav_node_id)?.0 );
+}
+
+/// Same as [`braille_mathml`], but also returns the range of braille chars (not bytes) for the node with id `nav_node_id`.
+/// The range includes any indicators (e.g., capitalization) that are part of the node's braille.
+/// The range is empty if `nav_node_id` is empty or isn't found.
+///
+/// The range is computed even when `BrailleNavHighlight` is "Off" so that apps can draw their own cursor.
+pub fn braille_mathml_with_nav_range(mathml: Element, nav_node_id: &str) -> Result<(String, Range<usize>)> {
     crate::speech::SpeechRules::update()?;
     return BRAILLE_RULES.with(|rules| {
         rules.borrow_mut().read_files()?;
         let rules = rules.borrow();
-        let new_package = Package::new();
-        let mut rules_with_context = SpeechRulesWithContext::new(&rules, new_package.as_document(), nav_node_id);
-        let braille_string = rules_with_context.match_pattern::<String>(mathml)
-                        .chain_err(|| "Pattern match/replacement failure!")?;
-        let braille_string = braille_string.replace(' ', "");
-        let pref_manager = rules_with_context.get_rules().pref_manager.borrow();
+        let pref_manager = rules.pref_manager.borrow();
         let highlight_style = pref_manager.pref_to_string("BrailleNavHighlight");
         let braille_code = pref_manager.pref_to_string("BrailleCode");
-        let braille = match braille_code.as_str() {
-            "Nemeth" => nemeth_cleanup(braille_string),
-            "UEB" => ueb_cleanup(pref_manager, braille_string),
-            "Vietnam" => vietnam_cleanup(pref_manager, braille_string),   // FIX: probably needs some specialized cleanup
-            "CMU" => cmu_cleanup(pref_manager, braille_string),   // FIX: probably needs some specialized cleanup
-            _ => braille_string,    // probably needs cleanup if someone has another code, but this will have to get added by hand
+        let use_spatial_layout = pref_manager.pref_to_string("BrailleSpatialLayout") == "true" &&
+                                 (braille_code == "Nemeth" || braille_code == "UEB");
+        drop(pref_manager);
+
+        let spatial_element = if use_spatial_layout {get_spatial_element(mathml)} else {None};
+        let braille_with_style = |highlight_style: Option<&str>| -> Result<String> {
+            let nav_node = NavNode{ id: nav_node_id, highlight_style };
+            return match spatial_element {
+                Some(element) => spatial_braille(&rules, element, &nav_node, &braille_code),
+                None => braille_element(&rules, mathml, &nav_node, &braille_code),
+            };
         };
 
-        return Ok(
-            if highlight_style != "Off" {
-                highlight_braille_chars(braille, &braille_code, highlight_style == "All")
-            } else {
-             braille
-            }
-        );
+        let braille = braille_with_style(None)?;
+        if nav_node_id.is_empty() {
+            return Ok( (braille, 0..0) );
+        }
+        if highlight_style == "EndPoints" || highlight_style == "All" {
+            let braille = highlight_braille_lines(braille, &braille_code, highlight_style == "All");
+            let nav_range = highlighted_range(&braille);
+            return Ok( (braille, nav_range) );
+        }
+
+        // the nav node isn't (fully) marked in the user's braille -- braille it again with the endpoints marked to find it
+        let marked_braille = highlight_braille_lines(braille_with_style(Some("EndPoints"))?, &braille_code, false);
+        let nav_range = highlighted_range(&marked_braille);
+        let braille = if highlight_style == "Off" {braille} else {highlight_braille_lines(braille, &braille_code, false)};
+        return Ok( (braille, nav_range) );
     });
 
+    // highlighting is done line by line so that it doesn't spill over into other lines of a spatial layout
+    fn highlight_braille_lines(braille: String, braille_code: &str, fill_range: bool) -> String {
+        return braille.split('\n')
+                .map(|line| highlight_braille_chars(line.to_string(), braille_code, fill_range))
+                .collect::<Vec<String>>()
+                .join("\n");
+    }
+
+    /// the range of chars from the first to the last highlighted char (empty if nothing is highlighted)
+    fn highlighted_range(braille: &str) -> Range<usize> {
+        let mut highlighted_chars = braille.chars()
+                .enumerate()
+                .filter(|(_, ch)| is_highlighted(*ch))
+                .map(|(i, _)| i);
+        return match highlighted_chars.next() {
+            None => 0..0,
+            Some(start) => start..highlighted_chars.last().unwrap_or(start)+1,
+        };
+    }
+
     // highlight with dots 7 & 8 based on the highlight style
     // both the start and stop points will be extended to deal with indicators such as capitalization
     // if 'fill_range' is true, the interior will be highlighted
@@ -93,6 +134,7 @@ pub fn braille_mathml(mathml: Element, nav_node_id: &str) -> Result<String> {
             let i_byte_start = start_index - 3 * match braille_code {
                 "Nemeth" => i_start_nemeth(indicators, first_ch),
                 "UEB" => i_start_ueb(indicators),
+                "LaTeX" => 0,       // capitals, etc., are part of the (8-dot) char
                 _ => {
                     error!("highlight_first_indicator: Unknown braille code '{}'", braille);
                     0
@@ -188,9 +230,282 @@ pub fn braille_mathml(mathml: Element, nav_node_id: &str) -> Result<String> {
     }
 }
 
+/// The navigation node to mark in the braille
+struct NavNode<'a> {
+    id: &'a str,
+    /// overrides the `BrailleNavHighlight` pref if not `None`
+    highlight_style: Option<&'a str>,
+}
+
+/// Variables set by the `math` rule in the braille rule files (all are initialized to the empty string)
+static MATH_RULE_VARIABLES: &[&str] = &["RowStart", "RowEnd", "NewScriptContext", "NewUnderContext", "NewOverContext", "NewRadicalContext"];
+
+/// Braille `element` (linearly) and do the code-specific cleanup
+fn braille_element(rules: &SpeechRules, element: Element, nav_node: &NavNode, braille_code: &str) -> Result<String> {
+    let new_package = Package::new();
+    let mut rules_with_context = SpeechRulesWithContext::new(rules, new_package.as_document(), nav_node.id);
+    rules_with_context.braille_nav_highlight = nav_node.highlight_style.map(|style| style.to_string());
+    if name(&element) != "math" {
+        // the 'math' rule sets variables that the rules for its descendants assume are set
+        for var_name in MATH_RULE_VARIABLES {
+            rules_with_context.get_context().set_variable(*var_name, Value::String(String::default()));
+        }
+    }
+    let braille_string = rules_with_context.match_pattern::<String>(element)
+                    .chain_err(|| "Pattern match/replacement failure!")?;
+    let braille_string = braille_string.replace(' ', "");
+    let pref_manager = rules.pref_manager.borrow();
+    return Ok( match braille_code {
+        "Nemeth" => nemeth_cleanup(braille_string),
+        "UEB" => ueb_cleanup(pref_manager, braille_string),
+        "Vietnam" => vietnam_cleanup(pref_manager, braille_string),   // FIX: probably needs some specialized cleanup
+        "CMU" => cmu_cleanup(pref_manager, braille_string),   // FIX: probably needs some specialized cleanup
+        "LaTeX" => latex_cleanup(pref_manager, braille_string),
+        _ => braille_string,    // probably needs cleanup if someone has another code, but this will have to get added by hand
+    } );
+}
+
+/// Returns the element to lay out spatially if the math is just an `mstack`, `mlongdiv`, `mtable`, or bracketed `mtable`.
+/// Spatial layouts only make sense for the whole expression, so nothing is returned for these elements when they are embedded.
+fn get_spatial_element(mathml: Element) -> Option<Element> {
+    let mut element = mathml;
+    while name(&element) == "math" || (name(&element) == "mrow" && element.children().len() == 1) {
+        let children = element.children();
+        if children.len() != 1 {
+            return None;
+        }
+        element = as_element(children[0]);
+    }
+    match name(&element) {
+        "mstack" | "mlongdiv" | "mtable" => return Some(element),
+        "mrow" => {
+            let children = element.children();
+            if children.len() == 3 && name(&as_element(children[1])) == "mtable" &&
+               name(&as_element(children[0])) == "mo" && name(&as_element(children[2])) == "mo" {
+                return Some(element);
+            }
+            return None;
+        },
+        _ => return None,
+    }
+}
+
+/// A row in a spatial layout of `mstack` and `mlongdiv`.
+/// The rows are aligned on their right edge; `position` moves the row that many columns to the left (MathML's `position` attr).
+enum SpatialRow {
+    /// `left` is put at the start of the line and must be at least `gap` cells away from `right` (e.g., an operator)
+    Text { left: String, gap: usize, right: String, position: isize },
+    /// A separator line -- a length of 0 means the line is as wide as the layout
+    Line { length: usize, position: isize },
+}
+
+const SPATIAL_LINE_CHAR: char = '⠒';     // dots 2-5 are used for separation lines in both Nemeth and UEB
+const SPATIAL_SPACE: char = '⠀';
+
+/// Lay out `element` (found by `get_spatial_element`) with each row on its own line
+fn spatial_braille(rules: &SpeechRules, element: Element, nav_node: &NavNode, braille_code: &str) -> Result<String> {
+    let lines = match name(&element) {
+        "mstack" => {
+            let rows = spatial_stack_rows(rules, element.children().into_iter().map(as_element), 0, nav_node, braille_code)?;
+            layout_spatial_rows(&rows)
+        },
+        "mlongdiv" => spatial_long_division(rules, element, nav_node, braille_code)?,
+        "mtable" => spatial_table(rules, element, None, nav_node, braille_code)?,
+        _ => {
+            let children = element.children();
+            spatial_table(rules, as_element(children[1]), Some((as_element(children[0]), as_element(children[2]))), nav_node, braille_code)?
+        },
+    };
+    return Ok( lines.join("\n") );
+}
+
+/// Braille a digit row/cell of an `mstack` or `mlongdiv`.
+/// In Nemeth, the numeric indicator is not used in spatial arrangements for computation.
+fn braille_spatial_cell(rules: &SpeechRules, element: Element, nav_node: &NavNode, braille_code: &str) -> Result<String> {
+    let braille = braille_element(rules, element, nav_node, braille_code)?;
+    let braille = braille.trim_matches(SPATIAL_SPACE);
+    if braille_code == "Nemeth" {
+        if let Some(without_indicator) = braille.strip_prefix('⠼') {
+            return Ok(without_indicator.to_string());
+        }
+    }
+    return Ok(braille.to_string());
+}
+
+/// Convert the children of an `mstack` (or the rows of an `mlongdiv`) to `SpatialRow`s.
+/// `position` is the accumulated shift from `msgroup`s that contain the children.
+fn spatial_stack_rows<'a>(rules: &SpeechRules, children: impl Iterator<Item=Element<'a>>, position: isize,
+                          nav_node: &NavNode, braille_code: &str) -> Result<Vec<SpatialRow>> {
+    let mut rows = vec![];
+    for child in children {
+        let child_position = position + get_int_attr(child, "position", 0);
+        match name(&child) {
+            "msline" => rows.push(SpatialRow::Line {
+                length: get_int_attr(child, "length", 0).max(0) as usize,
+                position: child_position,
+            }),
+            "msgroup" => {
+                let shift = get_int_attr(child, "shift", 0);
+                for (i, grandchild) in child.children().into_iter().map(as_element).enumerate() {
+                    rows.append(&mut spatial_stack_rows(rules, std::iter::once(grandchild), child_position + (i as isize)*shift,
+                                                        nav_node, braille_code)?);
+                }
+            },
+            "mscarries" => {
+                let mut carries = String::default();
+                for carry in child.children().into_iter().map(as_element) {
+                    let carry = if name(&carry) == "mscarry" && carry.children().len() == 1 {as_element(carry.children()[0])} else {carry};
+                    if name(&carry) == "none" || name(&carry) == "mscarry" {
+                        carries.push(SPATIAL_SPACE);
+                    } else {
+                        carries += &braille_spatial_cell(rules, carry, nav_node, braille_code)?;
+                    }
+                }
+                rows.push(SpatialRow::Text { left: String::default(), gap: 0, right: carries, position: child_position });
+            },
+            "msrow" => {
+                let (left, right) = braille_spatial_row(rules, child, nav_node, braille_code)?;
+                rows.push(SpatialRow::Text { left, gap: 1, right, position: child_position });
+            },
+            _ => {
+                let right = braille_spatial_cell(rules, child, nav_node, braille_code)?;
+                rows.push(SpatialRow::Text { left: String::default(), gap: 0, right, position: child_position });
+            },
+        }
+    }
+    return Ok(rows);
+
+    fn get_int_attr(element: Element, attr_name: &str, default: isize) -> isize {
+        return element.attribute_value(attr_name)
+                .and_then(|value| value.trim().parse::<isize>().ok())
+                .unwrap_or(default);
+    }
+}
+
+/// Braille an `msrow`, returning the leading operator (if any) and the cells.
+/// Canonicalization splits numbers into a cell per digit, so the numeric indicator is only used at the start of a run of digits.
+fn braille_spatial_row(rules: &SpeechRules, row: Element, nav_node: &NavNode, braille_code: &str) -> Result<(String, String)> {
+    let row_children = row.children().into_iter().map(as_element).collect::<Vec<Element>>();
+    let (left, cells) = match row_children.first() {
+        Some(first) if name(first) == "mo" => (braille_spatial_cell(rules, *first, nav_node, braille_code)?, &row_children[1..]),
+        _ => (String::default(), &row_children[..]),
+    };
+    let mut right = String::default();
+    let mut is_in_number = false;
+    for cell in cells {
+        if name(cell) == "none" {
+            right.push(SPATIAL_SPACE);
+        } else {
+            let braille = braille_spatial_cell(rules, *cell, nav_node, braille_code)?;
+            right += if is_in_number {braille.strip_prefix('⠼').unwrap_or(&braille)} else {&braille};
+        }
+        is_in_number = name(cell) == "mn";
+    }
+    return Ok( (left, right) );
+}
+
+/// Align the rows on their right edges (shifted by their `position`)
+fn layout_spatial_rows(rows: &[SpatialRow]) -> Vec<String> {
+    let right_edge = rows.iter()
+        .map(|row| match row {
+            SpatialRow::Text { left, gap, right, position } => {
+                let left_len = left.chars().count();
+                (if left_len == 0 {0} else {left_len + gap}) as isize + right.chars().count() as isize + position
+            },
+            SpatialRow::Line { length, position } => *length as isize + position,
+        })
+        .max()
+        .unwrap_or(0)
+        .max(0);
+    return rows.iter()
+        .map(|row| match row {
+            SpatialRow::Text { left, gap: _, right, position } => {
+                let n_pad = right_edge - position - (left.chars().count() + right.chars().count()) as isize;
+                left.clone() + &SPATIAL_SPACE.to_string().repeat(n_pad.max(0) as usize) + right
+            },
+            SpatialRow::Line { length, position } => {
+                let length = if *length == 0 {right_edge} else {*length as isize};
+                let n_pad = right_edge - position - length;
+                SPATIAL_SPACE.to_string().repeat(n_pad.max(0) as usize) + &SPATIAL_LINE_CHAR.to_string().repeat(length.max(0) as usize)
+            },
+        })
+        .collect();
+}
+
+/// Long division is laid out with the result on top, a line over the dividend,
+/// the divisor and the division bracket to the left of the dividend, and then the steps underneath.
+/// All the `longdivstyle`s use this layout (the one used in the US).
+fn spatial_long_division(rules: &SpeechRules, element: Element, nav_node: &NavNode, braille_code: &str) -> Result<Vec<String>> {
+    let children = element.children().into_iter().map(as_element).collect::<Vec<Element>>();
+    if children.len() < 3 {
+        bail!("mlongdiv has {} children -- it needs at least a divisor, result, and dividend", children.len());
+    }
+    // print uses what looks like a close paren for the division bracket
+    let division_bracket = if braille_code == "Nemeth" {"⠾"} else {"⠐⠜"};
+    let divisor = braille_spatial_cell(rules, children[0], nav_node, braille_code)?;
+    // canonicalization makes the result and dividend be `msrow`s
+    let (_, result) = braille_spatial_row(rules, children[1], nav_node, braille_code)?;
+    let (_, dividend) = braille_spatial_row(rules, children[2], nav_node, braille_code)?;
+    let mut rows = vec![
+        SpatialRow::Text { left: String::default(), gap: 0, right: result, position: 0 },
+        SpatialRow::Line { length: dividend.chars().count(), position: 0 },
+        SpatialRow::Text { left: divisor + division_bracket, gap: 0, right: dividend, position: 0 },
+    ];
+    rows.append(&mut spatial_stack_rows(rules, children[3..].iter().copied(), 0, nav_node, braille_code)?);
+    return Ok( layout_spatial_rows(&rows) );
+}
+
+/// Lay out a table with each row on a line and the columns aligned on the left.
+/// If `brackets` are given (e.g., a matrix), each line starts/ends with the (enlarged in Nemeth) bracket.
+fn spatial_table(rules: &SpeechRules, table: Element, brackets: Option<(Element, Element)>, nav_node: &NavNode, braille_code: &str) -> Result<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = vec![];
+    for row in table.children().into_iter().map(as_element) {
+        let mut cells = vec![];
+        let row_children = row.children().into_iter().map(as_element).collect::<Vec<Element>>();
+        // put the label at the end so it doesn't interfere with the column alignment
+        let (label, row_children) = if name(&row) == "mlabeledtr" && !row_children.is_empty() {
+            (Some(row_children[0]), &row_children[1..])
+        } else {
+            (None, &row_children[..])
+        };
+        for cell in row_children.iter().chain(label.iter()) {
+            let braille = braille_element(rules, *cell, nav_node, braille_code)?;
+            cells.push(braille.trim_matches(SPATIAL_SPACE).to_string());
+        }
+        rows.push(cells);
+    }
+
+    let n_columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let column_widths = (0..n_columns)
+        .map(|i| rows.iter().filter_map(|row| row.get(i)).map(|cell| cell.chars().count()).max().unwrap_or(0))
+        .collect::<Vec<usize>>();
+    let (open, close) = match brackets {
+        None => (String::default(), String::default()),
+        Some((open, close)) => {
+            let enlarged = if braille_code == "Nemeth" && rows.len() > 1 {"⠠"} else {""};
+            (enlarged.to_string() + braille_element(rules, open, nav_node, braille_code)?.trim_matches(SPATIAL_SPACE),
+             enlarged.to_string() + braille_element(rules, close, nav_node, braille_code)?.trim_matches(SPATIAL_SPACE))
+        },
+    };
+
+    return Ok( rows.iter()
+        .map(|row| {
+            let cells = column_widths.iter().enumerate()
+                .map(|(i, &width)| {
+                    let cell = row.get(i).map(|cell| cell.as_str()).unwrap_or("");
+                    cell.to_string() + &SPATIAL_SPACE.to_string().repeat(width - cell.chars().count())
+                })
+                .collect::<Vec<String>>()
+                .join(&SPATIAL_SPACE.to_string());
+            let line = open.clone() + &cells + &close;
+            if close.is_empty() {line.trim_end_matches(SPATIAL_SPACE).to_string()} else {line}
+        })
+        .collect() );
+}
+
 fn is_highlighted(ch: char) -> bool {
     let ch_as_u32 = ch as u32;
-    return (0x28C0..0x28FF).contains(&ch_as_u32);
+    return (0x28C0..=0x28FF).contains(&ch_as_u32);
 }
 
 fn highlight(ch: char) -> char {
@@ -199,7 +514,7 @@ fn highlight(ch: char) -> char {
 
 fn unhighlight(ch: char) -> char {
     let ch_as_u32 = ch as u32;
-    if (0x28C0..0x28FF).contains(&ch_as_u32) {
+    if (0x28C0..=0x28FF).contains(&ch_as_u32) {
         return unsafe{char::from_u32_unchecked(ch_as_u32 & 0x283F)};  
     } else {
         return ch;
@@ -481,22 +796,151 @@ static LETTER_NUMBERS: phf::Set<char> = phf_set! {
     '⠁', '⠃', '⠉', '⠙', '⠑', '⠋', '⠛', '⠓', '⠊', '⠚',
 };
 
-static SHORT_FORMS: phf::Set<&str> = phf_set! {
-    "L⠁L⠃", "L⠁L⠃L⠧", "L⠁L⠉", "L⠁L⠉L⠗", "L⠁L⠋",
-    "L⠁L⠋L⠝", "L⠁L⠋L⠺", "L⠁L⠛", "L⠁L⠛L⠌", "L⠁L⠇",
-     "L⠁L⠇L⠍", "L⠁L⠇L⠗", "L⠁L⠇L⠞", "L⠁L⠇L⠹", "L⠁L⠇L⠺",
-     "L⠃L⠇", "L⠃L⠗L⠇", "L⠉L⠙", "L⠙L⠉L⠇", "L⠙L⠉L⠇L⠛",
-     "L⠙L⠉L⠧", "L⠙L⠉L⠧L⠛", "L⠑L⠊", "L⠋L⠗", "L⠋L⠌", "L⠛L⠙",
-     "L⠛L⠗L⠞", "L⠓L⠍", "L⠓L⠍L⠋", "L⠓L⠻L⠋", "L⠊L⠍L⠍", "L⠇L⠇", "L⠇L⠗",
-     "L⠍L⠽L⠋", "L⠍L⠡", "L⠍L⠌", "L⠝L⠑L⠉", "L⠝L⠑L⠊", "L⠏L⠙",
-     "L⠏L⠻L⠉L⠧", "L⠏L⠻L⠉L⠧L⠛", "L⠏L⠻L⠓", "L⠟L⠅", "L⠗L⠉L⠧",
-     "L⠗L⠉L⠧L⠛", "L⠗L⠚L⠉", "L⠗L⠚L⠉L⠛", "L⠎L⠙", "L⠎L⠡", "L⠞L⠙",
-     "L⠞L⠛L⠗", "L⠞L⠍", "L⠞L⠝", "L⠭L⠋", "L⠭L⠎", "L⠽L⠗", "L⠽L⠗L⠋",
-     "L⠽L⠗L⠧L⠎", "L⠮L⠍L⠧L⠎", "L⠡L⠝", "L⠩L⠙", "L⠹L⠽L⠋", "L⠳L⠗L⠧L⠎",
-     "L⠺L⠙", "L⠆L⠉", "L⠆L⠋", "L⠆L⠓", "L⠆L⠇", "L⠆L⠝", "L⠆L⠎", "L⠆L⠞",
-     "L⠆L⠽", "L⠒L⠉L⠧", "L⠒L⠉L⠧L⠛", "L⠐L⠕L⠋"
+// UEB short forms (RUEB 10.9)
+// These are only used when the word stands alone, but a letter sequence that matches one needs a grade 1 indicator
+static SHORT_FORMS: phf::Map<&str, &str> = phf_map! {
+    "about" => "⠁⠃", "above" => "⠁⠃⠧", "according" => "⠁⠉", "across" => "⠁⠉⠗", "after" => "⠁⠋",
+    "afternoon" => "⠁⠋⠝", "afterward" => "⠁⠋⠺", "again" => "⠁⠛", "against" => "⠁⠛⠌", "also" => "⠁⠇",
+    "almost" => "⠁⠇⠍", "already" => "⠁⠇⠗", "altogether" => "⠁⠇⠞", "although" => "⠁⠇⠹", "always" => "⠁⠇⠺",
+    "blind" => "⠃⠇", "braille" => "⠃⠗⠇", "could" => "⠉⠙", "declare" => "⠙⠉⠇", "declaring" => "⠙⠉⠇⠛",
+    "deceive" => "⠙⠉⠧", "deceiving" => "⠙⠉⠧⠛", "either" => "⠑⠊", "friend" => "⠋⠗", "first" => "⠋⠌", "good" => "⠛⠙",
+    "great" => "⠛⠗⠞", "him" => "⠓⠍", "himself" => "⠓⠍⠋", "herself" => "⠓⠻⠋", "immediate" => "⠊⠍⠍", "little" => "⠇⠇",
+    "letter" => "⠇⠗", "myself" => "⠍⠽⠋", "much" => "⠍⠡", "must" => "⠍⠌", "necessary" => "⠝⠑⠉", "neither" => "⠝⠑⠊",
+    "paid" => "⠏⠙", "perceive" => "⠏⠻⠉⠧", "perceiving" => "⠏⠻⠉⠧⠛", "perhaps" => "⠏⠻⠓", "quick" => "⠟⠅",
+    "receive" => "⠗⠉⠧", "receiving" => "⠗⠉⠧⠛", "rejoice" => "⠗⠚⠉", "rejoicing" => "⠗⠚⠉⠛", "said" => "⠎⠙",
+    "such" => "⠎⠡", "today" => "⠞⠙", "together" => "⠞⠛⠗", "tomorrow" => "⠞⠍", "tonight" => "⠞⠝",
+    "itself" => "⠭⠋", "its" => "⠭⠎", "your" => "⠽⠗", "yourself" => "⠽⠗⠋", "yourselves" => "⠽⠗⠧⠎",
+    "themselves" => "⠮⠍⠧⠎", "children" => "⠡⠝", "should" => "⠩⠙", "thyself" => "⠹⠽⠋", "ourselves" => "⠳⠗⠧⠎",
+    "would" => "⠺⠙", "because" => "⠆⠉", "before" => "⠆⠋", "behind" => "⠆⠓", "below" => "⠆⠇", "beneath" => "⠆⠝",
+    "beside" => "⠆⠎", "between" => "⠆⠞", "beyond" => "⠆⠽", "conceive" => "⠒⠉⠧", "conceiving" => "⠒⠉⠧⠛",
+    "oneself" => "⠐⠕⠋",
+};
+
+// Alphabetic wordsigns, strong wordsigns, and lower wordsigns (RUEB 10.1, 10.2, 10.5)
+// These are only used when the word stands alone
+static WORDSIGNS: phf::Map<&str, &str> = phf_map! {
+    "but" => "⠃", "can" => "⠉", "do" => "⠙", "every" => "⠑", "from" => "⠋", "go" => "⠛", "have" => "⠓",
+    "just" => "⠚", "knowledge" => "⠅", "like" => "⠇", "more" => "⠍", "not" => "⠝", "people" => "⠏",
+    "quite" => "⠟", "rather" => "⠗", "so" => "⠎", "that" => "⠞", "us" => "⠥", "very" => "⠧", "will" => "⠺",
+    "it" => "⠭", "you" => "⠽", "as" => "⠵",
+    "child" => "⠡", "shall" => "⠩", "this" => "⠹", "which" => "⠱", "out" => "⠳", "still" => "⠌",
+    "be" => "⠆", "enough" => "⠢", "were" => "⠶", "his" => "⠦", "in" => "⠔", "was" => "⠴",
+};
+
+/// Where in a word a UEB groupsign can be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContractionPosition {
+    Anywhere,
+    Beginning,      // "be", "con", "dis" (RUEB 10.6.5)
+    Middle,         // "ea", "bb", "ff", "gg" (RUEB 10.6.8)
+    NotBeginning,   // "ing" and the final-letter groupsigns (RUEB 10.8)
+}
+
+struct Groupsign {
+    letters: &'static str,
+    braille: &'static str,
+    position: ContractionPosition,
+}
+
+// The contractions that can be used as part of a word (RUEB 10.3 - 10.8)
+// If more than one matches at a position, the longest one is used; for equal lengths, the first one in this table is used
+static GROUPSIGNS: &[Groupsign] = &[
+    // strong contractions
+    Groupsign{ letters: "and", braille: "⠯", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "for", braille: "⠿", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "of", braille: "⠷", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "the", braille: "⠮", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "with", braille: "⠾", position: ContractionPosition::Anywhere },
+    // strong groupsigns
+    Groupsign{ letters: "ch", braille: "⠡", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "gh", braille: "⠣", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "sh", braille: "⠩", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "th", braille: "⠹", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "wh", braille: "⠱", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "ed", braille: "⠫", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "er", braille: "⠻", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "ou", braille: "⠳", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "ow", braille: "⠪", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "st", braille: "⠌", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "ar", braille: "⠜", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "ing", braille: "⠬", position: ContractionPosition::NotBeginning },
+    // lower groupsigns
+    Groupsign{ letters: "be", braille: "⠆", position: ContractionPosition::Beginning },
+    Groupsign{ letters: "con", braille: "⠒", position: ContractionPosition::Beginning },
+    Groupsign{ letters: "dis", braille: "⠲", position: ContractionPosition::Beginning },
+    Groupsign{ letters: "ea", braille: "⠂", position: ContractionPosition::Middle },
+    Groupsign{ letters: "bb", braille: "⠆", position: ContractionPosition::Middle },
+    // "cc" (⠒) is left out: RUEB doesn't use it in "arccosine" and similar function names where it bridges "arc" and "cos"
+    Groupsign{ letters: "ff", braille: "⠖", position: ContractionPosition::Middle },
+    Groupsign{ letters: "gg", braille: "⠶", position: ContractionPosition::Middle },
+    Groupsign{ letters: "en", braille: "⠢", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "in", braille: "⠔", position: ContractionPosition::Anywhere },
+    // initial-letter contractions
+    Groupsign{ letters: "day", braille: "⠐⠙", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "ever", braille: "⠐⠑", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "father", braille: "⠐⠋", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "here", braille: "⠐⠓", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "know", braille: "⠐⠅", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "lord", braille: "⠐⠇", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "mother", braille: "⠐⠍", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "name", braille: "⠐⠝", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "one", braille: "⠐⠕", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "part", braille: "⠐⠏", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "question", braille: "⠐⠟", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "right", braille: "⠐⠗", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "some", braille: "⠐⠎", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "time", braille: "⠐⠞", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "under", braille: "⠐⠥", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "work", braille: "⠐⠺", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "young", braille: "⠐⠽", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "there", braille: "⠐⠮", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "character", braille: "⠐⠡", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "through", braille: "⠐⠹", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "where", braille: "⠐⠱", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "ought", braille: "⠐⠳", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "upon", braille: "⠘⠥", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "word", braille: "⠘⠺", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "these", braille: "⠘⠮", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "those", braille: "⠘⠹", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "whose", braille: "⠘⠱", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "cannot", braille: "⠸⠉", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "had", braille: "⠸⠓", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "many", braille: "⠸⠍", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "spirit", braille: "⠸⠎", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "world", braille: "⠸⠺", position: ContractionPosition::Anywhere },
+    Groupsign{ letters: "their", braille: "⠸⠮", position: ContractionPosition::Anywhere },
+    // final-letter groupsigns
+    // note: "ble" and "com" were lower groupsigns in EBAE, but they are not part of UEB (⠼ is the numeric indicator)
+    Groupsign{ letters: "ation", braille: "⠠⠝", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ally", braille: "⠠⠽", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ound", braille: "⠨⠙", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ance", braille: "⠨⠑", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "sion", braille: "⠨⠝", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "less", braille: "⠨⠎", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ount", braille: "⠨⠞", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ence", braille: "⠰⠑", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ong", braille: "⠰⠛", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ful", braille: "⠰⠇", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "tion", braille: "⠰⠝", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ness", braille: "⠰⠎", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ment", braille: "⠰⠞", position: ContractionPosition::NotBeginning },
+    Groupsign{ letters: "ity", braille: "⠰⠽", position: ContractionPosition::NotBeginning },
+];
+
+static LETTER_TO_BRAILLE: phf::Map<char, &str> = phf_map! {
+    'a' => "⠁", 'b' => "⠃", 'c' => "⠉", 'd' => "⠙", 'e' => "⠑", 'f' => "⠋", 'g' => "⠛", 'h' => "⠓", 'i' => "⠊",
+    'j' => "⠚", 'k' => "⠅", 'l' => "⠇", 'm' => "⠍", 'n' => "⠝", 'o' => "⠕", 'p' => "⠏", 'q' => "⠟", 'r' => "⠗",
+    's' => "⠎", 't' => "⠞", 'u' => "⠥", 'v' => "⠧", 'w' => "⠺", 'x' => "⠭", 'y' => "⠽", 'z' => "⠵",
 };
 
+lazy_static! {
+    // the inverse of LETTER_TO_BRAILLE
+    static ref BRAILLE_TO_LETTER: HashMap<char, char> = LETTER_TO_BRAILLE.entries()
+            .map(|(&letter, braille)| (braille.chars().next().unwrap(), letter))
+            .collect();
+}
+
+
 static LETTER_PREFIXES: phf::Set<char> = phf_set! {
     'B', 'I', '𝔹', 'S', 'T', 'D', 'C', '𝐶', '𝑐',
 };
@@ -511,9 +955,29 @@ lazy_static! {
     static ref COLLAPSE_SPACES: Regex = Regex::new(r"⠀⠀+").unwrap();
 }
 
+/// Returns true if the letters (in "Lx" form) would be read as a short form.
+/// That is the case if they match a short form either as is or after the groupsigns in them are contracted.
 fn is_short_form(chars: &[char]) -> bool {
-    let chars_as_string = chars.iter().map(|ch| ch.to_string()).collect::<String>();
-    return SHORT_FORMS.contains(&chars_as_string);
+    lazy_static! {
+        static ref SHORT_FORM_LETTERS: HashSet<String> = SHORT_FORMS.values()
+                .map(|braille| braille.chars().map(|ch| format!("L{}", ch)).collect::<String>())
+                .collect();
+    }
+    let chars_as_string = chars.iter().map(|&ch| unhighlight(ch)).collect::<String>();
+    if SHORT_FORM_LETTERS.contains(&chars_as_string) {
+        return true;
+    }
+    let letters = chars.iter()
+            .filter_map(|&ch| BRAILLE_TO_LETTER.get(&unhighlight(ch)).copied())
+            .collect::<Vec<char>>();
+    if 2 * letters.len() != chars.len() {
+        return false;
+    }
+    let contracted = contract_word(&letters, &vec![true; letters.len()], false).iter()
+            .flat_map(|(braille, _)| braille.chars())
+            .map(|ch| format!("L{}", ch))
+            .collect::<String>();
+    return SHORT_FORM_LETTERS.contains(&contracted);
 }
 
 fn ueb_cleanup(pref_manager: Ref<PreferenceManager>, raw_braille: String) -> String {
@@ -1049,6 +1513,9 @@ fn remove_unneeded_mode_changes(raw_braille: &str, start_mode: UEB_Mode, start_d
                             // debug!("  is_alone -- pushing '1'");
                             result.push('1');
                             mode = UEB_Mode::Grade1;
+                            if start_g2_letter == Some(i) {
+                                start_g2_letter = None;     // letters after a grade 1 indicator are not contracted
+                            }
                         }
                         // debug!("  pushing {:?}", right_matched_chars);
                         right_matched_chars.iter().for_each(|&ch| result.push(ch));
@@ -1064,7 +1531,8 @@ fn remove_unneeded_mode_changes(raw_braille: &str, start_mode: UEB_Mode, start_d
                             let is_greek = chars[i+1] == 'G';
                             let (is_alone, right_matched_chars, n_letters) = stands_alone(&chars, if is_greek {i+2} else {i+1});
                             // GTM 1.2.1 says we only need to use G1 for single letters or sequences that are a shortform (e.g, "ab")
-                            if is_alone && (n_letters == 1 || is_short_form(&right_matched_chars[..2*n_letters])) {
+                            let is_grade1 = is_alone && (n_letters == 1 || is_short_form(&right_matched_chars[..2*n_letters]));
+                            if is_grade1 {
                                 // debug!("  is_alone -- pushing '1'");
                                 result.push('1');
                                 mode = UEB_Mode::Grade1;
@@ -1077,7 +1545,7 @@ fn remove_unneeded_mode_changes(raw_braille: &str, start_mode: UEB_Mode, start_d
                                 result.push('G');
                                 i += 1;
                             }
-                            start_g2_letter = Some(i);
+                            start_g2_letter = if is_grade1 {None} else {Some(i)};  // letters after a grade 1 indicator are not contracted
                             // debug!("  pushing 'C' + {:?}", right_matched_chars);
                             right_matched_chars.iter().for_each(|&ch| result.push(ch));
                             i += 1 + right_matched_chars.len();
@@ -1099,7 +1567,7 @@ fn remove_unneeded_mode_changes(raw_braille: &str, start_mode: UEB_Mode, start_d
                     _ => {
                         if let Some(start) = start_g2_letter {
                             if !cap_word_mode {
-                                result = handle_contractions(&chars[start..i], result);
+                                result = handle_contractions(&chars, start, i, result);
                             }
                             cap_word_mode = false;
                             start_g2_letter = None;     // not start of char sequence
@@ -1115,7 +1583,7 @@ fn remove_unneeded_mode_changes(raw_braille: &str, start_mode: UEB_Mode, start_d
                 }
                 if mode != UEB_Mode::Grade2 && !cap_word_mode {
                     if let Some(start) = start_g2_letter {
-                        result = handle_contractions(&chars[start..i], result);
+                        result = handle_contractions(&chars, start, i, result);
                         start_g2_letter = None;     // not start of char sequence
                     }
                 }
@@ -1124,7 +1592,7 @@ fn remove_unneeded_mode_changes(raw_braille: &str, start_mode: UEB_Mode, start_d
     }
     if mode == UEB_Mode::Grade2 {
         if let Some(start) = start_g2_letter {
-            result = handle_contractions(&chars[start..i], result);
+            result = handle_contractions(&chars, start, i, result);
         }
     }
 
@@ -1217,74 +1685,161 @@ fn stands_alone(chars: &[char], i: usize) -> (bool, &[char], usize) {
     }
 }
 
-/// Return a modified result if chars can be contracted.
-/// Otherwise, the original string is returned
-fn handle_contractions(chars: &[char], mut result: String) -> String {
-    struct Replacement {
-        pattern: &'static str,
-        replacement: &'static str
-    }
-
-    // It would be much better from an extensibility point of view to read the table in from a file
-    // FIX: this would be much easier to read/maintain if ASCII braille were used
-    // FIX:   (without the "L"s) and the CONTRACTIONS table built as a lazy static
-    static CONTRACTIONS: &[Replacement] = &[
-        Replacement{ pattern: "L⠁L⠝L⠙", replacement: "L⠯" },           // and
-        Replacement{ pattern: "L⠋L⠕L⠗", replacement: "L⠿" },           // for
-        Replacement{ pattern: "L⠕L⠋", replacement: "L⠷" },             // of
-        Replacement{ pattern: "L⠞L⠓L⠑", replacement: "L⠮" },           // the
-        Replacement{ pattern: "L⠺L⠊L⠞L⠓", replacement: "L⠾" },         // with
-        Replacement{ pattern: "L⠉L⠓", replacement: "L⠡" },              // ch
-        Replacement{ pattern: "L⠊L⠝", replacement: "L⠔" },              // in
-
-        // cc -- don't match if after/before a cap letter -- no/can't use negative pattern (?!...) in regex package
-        // figure this out -- also applies to ea, bb, ff, and gg (not that they matter)
-        // cc may be important for "arccos", but RUEB doesn't apply it to "arccosine", so maybe not
-        // Replacement{ pattern: "L⠉L⠉", replacement: "L⠒" },              // cc -- don't match if after/before a cap letter
-        
-        
-        Replacement{ pattern: "L⠎L⠓", replacement: "L⠩" },              // sh
-        Replacement{ pattern: "L⠁L⠗", replacement: "L⠜" },              // ar
-        Replacement{ pattern: "L⠑L⠗", replacement: "L⠻" },              // er
-        Replacement{ pattern: "(?P<s>L.)L⠍L⠑L⠝L⠞", replacement: "${s}L⠰L⠞" }, // ment
-        Replacement{ pattern: "(?P<s>L.)L⠞L⠊L⠕L⠝", replacement: "${s}L⠰L⠝" } ,// tion
-        Replacement{ pattern: "(?P<s>L.)L⠑L⠁(?P<e>L.)", replacement: "${s}L⠂${e}" },  // ea
-    ];
+/// Return a modified result if the letters in `chars[i_start..i_end]` can be contracted (UEB grade 2).
+/// `result` should end with those chars; otherwise, the original string is returned.
+///
+/// Indicators (caps, typeforms, ...) in front of the first letter of a contraction are kept,
+/// but a contraction is not used if a letter other than its first one has an indicator (e.g., a cap in the middle of it).
+/// Wordsigns and short forms are only used when the letters stand alone (UEB 2.6).
+fn handle_contractions(chars: &[char], i_start: usize, i_end: usize, mut result: String) -> String {
+    let word_chars = &chars[i_start..i_end];
+    let word_as_str = word_chars.iter().collect::<String>();
+    if !result.ends_with(&word_as_str) {
+        return result;
+    }
 
-    lazy_static! {
-        static ref CONTRACTION_PATTERNS: RegexSet = init_patterns(CONTRACTIONS);
-        static ref CONTRACTION_REGEX: Vec<Regex> = init_regex(CONTRACTIONS);
+    // pick apart the chars into the letters and the indicators in front of each of them
+    let mut prefixes: Vec<String> = Vec::with_capacity(word_chars.len()/2);
+    let mut letters: Vec<char> = Vec::with_capacity(word_chars.len()/2);
+    let mut highlighted: Vec<bool> = Vec::with_capacity(word_chars.len()/2);
+    let mut prefix = String::default();
+    let mut i_first_letter = None;
+    let mut i = 0;
+    while i < word_chars.len() {
+        if word_chars[i] == 'L' && i+1 < word_chars.len() {
+            let cell = word_chars[i+1];
+            if let Some(&letter) = BRAILLE_TO_LETTER.get(&unhighlight(cell)) {
+                if i_first_letter.is_none() {
+                    i_first_letter = Some(i_start + i);
+                }
+                prefixes.push(prefix);
+                prefix = String::default();
+                letters.push(letter);
+                highlighted.push(is_highlighted(cell));
+                i += 2;
+                continue;
+            }
+        }
+        prefix.push(word_chars[i]);
+        i += 1;
+    }
+    let suffix = prefix;
+    if letters.len() < 2 {
+        return result;
+    }
+
+    // a letter can be part of a contraction if there is nothing in front of it,
+    //   except for the first letter which can have cap and typeform indicators
+    let can_contract = prefixes.iter().enumerate()
+            .map(|(i, prefix)| if i == 0 {prefix.chars().all(|ch| "CBIw".contains(ch))} else {prefix.is_empty()})
+            .collect::<Vec<bool>>();
+    let is_standing_alone = match i_first_letter {
+        Some(i_letter) => {
+            let (is_alone, _, n_letters) = stands_alone(chars, i_letter);
+            is_alone && n_letters == letters.len()
+        },
+        None => false,
+    };
+
+    let pieces = contract_word(&letters, &can_contract, is_standing_alone);
+    if pieces.len() == letters.len() {
+        return result;      // nothing was contracted
     }
 
-    let mut chars_as_str = chars.iter().collect::<String>();
-    // debug!("  handle_contractions: examine '{}'", &chars_as_str);
-    let matches = CONTRACTION_PATTERNS.matches(&chars_as_str);
-    for i in matches.iter() {
-        let element = &CONTRACTIONS[i];
-        // debug!("  replacing '{}' with '{}' in '{}'", element.pattern, element.replacement, &chars_as_str);
-        result.truncate(result.len() - chars_as_str.len());
-        chars_as_str = CONTRACTION_REGEX[i].replace_all(&chars_as_str, element.replacement).to_string();
-        result.push_str(&chars_as_str);
-        // debug!("  result after replace '{}'", result);
+    result.truncate(result.len() - word_as_str.len());
+    let mut i_letter = 0;
+    for (braille, n_letters) in pieces {
+        result.push_str(&prefixes[i_letter]);
+        let is_highlighted = highlighted[i_letter..i_letter+n_letters].iter().any(|&is_highlighted| is_highlighted);
+        for ch in braille.chars() {
+            result.push('L');
+            result.push(if is_highlighted {highlight(ch)} else {ch});
+        }
+        i_letter += n_letters;
     }
+    result.push_str(&suffix);
     return result;
+}
 
+/// Contract a word given as (lower case) print letters.
+/// `can_contract[i]` is false if letter `i` can't be part of a contraction.
+///
+/// Returns the braille for each piece of the word along with the number of letters it covers.
+fn contract_word(letters: &[char], can_contract: &[bool], is_standing_alone: bool) -> Vec<(&'static str, usize)> {
+    let n_letters = letters.len();
+    if is_standing_alone && can_contract.iter().all(|&can| can) {
+        let word = letters.iter().collect::<String>();
+        if let Some(&braille) = WORDSIGNS.get(word.as_str()).or_else(|| SHORT_FORMS.get(word.as_str())) {
+            return vec![(braille, n_letters)];
+        }
+    }
 
-
-    fn init_patterns(contractions: &[Replacement]) -> RegexSet {
-        let mut vec = Vec::with_capacity(contractions.len());
-        for contraction in contractions {
-            vec.push(contraction.pattern);
+    let mut pieces = Vec::with_capacity(n_letters);
+    let mut i = 0;
+    while i < n_letters {
+        let mut best: Option<&Groupsign> = None;
+        for groupsign in GROUPSIGNS {
+            let len = groupsign.letters.len();
+            if i + len > n_letters || best.is_some_and(|best| best.letters.len() >= len) ||
+               !can_contract[i..i+len].iter().all(|&can| can) {
+                continue;
+            }
+            let is_allowed_here = match groupsign.position {
+                ContractionPosition::Anywhere => true,
+                ContractionPosition::Beginning => i == 0 && is_first_syllable(letters, len),
+                ContractionPosition::Middle => i > 0 && i + len < n_letters,
+                ContractionPosition::NotBeginning => i > 0,
+            };
+            if is_allowed_here && groupsign.letters.chars().zip(&letters[i..i+len]).all(|(ch, &letter)| ch == letter) {
+                best = Some(groupsign);
+            }
         }
-        return RegexSet::new(&vec).unwrap();
+        match best {
+            Some(groupsign) => {
+                pieces.push( (groupsign.braille, groupsign.letters.len()) );
+                i += groupsign.letters.len();
+            },
+            None => {
+                pieces.push( (letter_to_braille(letters[i]), 1) );
+                i += 1;
+            },
+        }
+    }
+
+    // a standing alone groupsign is read as a wordsign (e.g., "st" would be read as "still"), so don't use it in that case
+    if is_standing_alone && pieces.len() == 1 && WORDSIGNS.values().any(|&braille| braille == pieces[0].0) {
+        return letters.iter().map(|&letter| (letter_to_braille(letter), 1)).collect();
     }
+    return pieces;
 
-    fn init_regex(contractions: &[Replacement]) -> Vec<Regex> {
-        let mut vec = Vec::with_capacity(contractions.len());
-        for contraction in contractions {
-            vec.push(Regex::new(contraction.pattern).unwrap());
+    fn letter_to_braille(letter: char) -> &'static str {
+        return LETTER_TO_BRAILLE.get(&letter).unwrap();
+    }
+
+    /// RUEB 10.6.5: "be", "con", and "dis" are only used when they form the first syllable of a word.
+    /// Without a syllabification dictionary, that is approximated by checking that
+    /// * the rest of the word has a vowel other than a final (silent) "e" -- rules out "bed", "best", "cone", and "disc"
+    /// * the next letter doesn't belong to the groupsign's syllable:
+    ///   - after "be", it isn't a vowel that forms a vowel pair with the "e" or a doubled consonant -- rules out "beer", "bean", and "better"
+    ///   - after "con" and "dis", it is a consonant (otherwise that consonant starts the next syllable) -- rules out "conic" and "dish"
+    fn is_first_syllable(letters: &[char], len: usize) -> bool {
+        let rest = &letters[len..];
+        let rest_without_final_e = match rest.last() {
+            Some('e') => &rest[..rest.len()-1],
+            _ => rest,
+        };
+        if !rest_without_final_e.iter().any(|&letter| is_vowel(letter)) {
+            return false;
+        }
+        let next = rest[0];
+        if letters[len-1] == 'e' {
+            return !(next == 'a' || next == 'e' || next == 'u') && rest.get(1) != Some(&next);
         }
-        return vec;
+        return !is_vowel(next);
+    }
+
+    fn is_vowel(letter: char) -> bool {
+        return "aeiouy".contains(letter);
     }
 }
 
@@ -1464,6 +2019,62 @@ fn cmu_cleanup(_pref_manager: Ref<PreferenceManager>, raw_braille: String) -> St
     }
 }
 
+/// 8-dot (computer braille) tables for the printable ASCII chars (' '..='~') used by the LaTeX braille code.
+/// The nth char in the string is the braille for the ASCII char 0x20+n.
+static LATEX_BRAILLE_TABLES: phf::Map<&str, &str> = phf_map! {
+    // North American Braille Computer Code (NABCC) -- capital letters (and '@', '[', '\', ']', '^', '_') add dot 7
+    "NorthAmerican" => "⠀⠮⠐⠼⠫⠩⠯⠄⠷⠾⠡⠬⠠⠤⠨⠌⠴⠂⠆⠒⠲⠢⠖⠶⠦⠔⠱⠰⠣⠿⠜⠹⡈⡁⡃⡉⡙⡑⡋⡛⡓⡊⡚⡅⡇⡍⡝⡕⡏⡟⡗⡎⡞⡥⡧⡺⡭⡽⡵⡪⡳⡻⡘⡸⠈⠁⠃⠉⠙⠑⠋⠛⠓⠊⠚⠅⠇⠍⠝⠕⠏⠟⠗⠎⠞⠥⠧⠺⠭⠽⠵⠪⠳⠻⠘",
+    // 6-dot North American braille ASCII -- upper and lower case letters are the same
+    "BrailleASCII" => "⠀⠮⠐⠼⠫⠩⠯⠄⠷⠾⠡⠬⠠⠤⠨⠌⠴⠂⠆⠒⠲⠢⠖⠶⠦⠔⠱⠰⠣⠿⠜⠹⠈⠁⠃⠉⠙⠑⠋⠛⠓⠊⠚⠅⠇⠍⠝⠕⠏⠟⠗⠎⠞⠥⠧⠺⠭⠽⠵⠪⠳⠻⠘⠸⠈⠁⠃⠉⠙⠑⠋⠛⠓⠊⠚⠅⠇⠍⠝⠕⠏⠟⠗⠎⠞⠥⠧⠺⠭⠽⠵⠪⠳⠻⠘",
+};
+
+fn latex_cleanup(pref_manager: Ref<PreferenceManager>, raw_braille: String) -> String {
+    let table_name = pref_manager.pref_to_string("LaTeX_BrailleTable");
+    let result = latex_to_braille(&raw_braille, &table_name);
+    // a '𝘄' at the end wasn't needed (see latex_to_braille)
+    return result.trim_end_matches('𝘄').trim_matches('⠀').to_string();
+}
+
+/// Convert the (mostly ASCII) LaTeX generated by the LaTeX braille rules to braille using the 8-dot table `table_name`.
+/// Chars that are already braille (e.g., highlighted nav nodes) are left alone and ASCII spaces are removed (use "⠀" to keep a space).
+///
+/// '𝘄' follows a command name (e.g., "\alpha𝘄"). It becomes a space if the next char is a letter (so it isn't part of the name),
+/// otherwise it is removed. If it is the last char, it is left alone because this may only be part of the braille.
+pub fn latex_to_braille(latex: &str, table_name: &str) -> String {
+    let table = match LATEX_BRAILLE_TABLES.get(table_name) {
+        Some(table) => table,
+        None => {
+            error!("Unknown LaTeX braille table '{}' -- using 'NorthAmerican'", table_name);
+            LATEX_BRAILLE_TABLES.get("NorthAmerican").unwrap()
+        }
+    };
+    let table = table.chars().collect::<Vec<char>>();
+    let chars = latex.chars().collect::<Vec<char>>();
+    let mut result = String::with_capacity(3*chars.len());
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch == '𝘄' {
+            match chars.get(i+1) {
+                None => result.push(ch),
+                Some(&next_ch) => if next_ch.is_ascii_alphabetic() || is_letter_cell(&table, next_ch) {
+                    result.push(table[0]);
+                },
+            }
+        } else if ('!'..='~').contains(&ch) {
+            result.push(table[ch as usize - 0x20]);
+        } else if ch != ' ' {
+            result.push(ch);
+        }
+    }
+    return result;
+
+    /// true if `ch` is the braille for a letter (possibly highlighted)
+    fn is_letter_cell(table: &[char], ch: char) -> bool {
+        let ch = unhighlight(ch) as u32;
+        return ('A'..='Z').chain('a'..='z')
+                .any(|letter| table[letter as usize - 0x20] as u32 & 0x283F == ch);
+    }
+}
+
 /************** Braille xpath functionality ***************/
 use crate::canonicalize::{name, as_element, as_text};
 use crate::xpath_functions::{is_leaf, IsBracketed, validate_one_node};
@@ -1998,7 +2609,88 @@ mod tests {
         assert_eq!("⠼⠙⣰⣁⠉", braille);
         return Ok( () );
     }
-    
+
+    #[test]
+    fn nav_range() -> Result<()> {
+        let mathml_str = "<math display='block' id='id-0'>
+            <mrow id='id-1'>
+                <mn id='id-2'>4</mn>
+                <mo id='id-3'>&#x2062;</mo>
+                <mi id='id-4'>a</mi>
+                <mo id='id-5'>&#x2062;</mo>
+                <mi id='id-6'>c</mi>
+            </mrow>
+        </math>";
+        crate::interface::set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+        set_mathml(mathml_str.to_string()).unwrap();
+        set_preference("BrailleCode".to_string(), "UEB".to_string()).unwrap();
+        set_preference("BrailleNavHighlight".to_string(), "EndPoints".to_string()).unwrap();
+        assert_eq!(("⣼⣙⠰⠁⠉".to_string(), 0, 2), get_braille_with_nav_range("id-2".to_string())?);
+        assert_eq!(("⠼⠙⣰⣁⠉".to_string(), 2, 4), get_braille_with_nav_range("id-4".to_string())?);
+        assert_eq!(("⠼⠙⠰⠁⠉".to_string(), 0, 0), get_braille_with_nav_range("".to_string())?);
+
+        // the range is reported even when the braille isn't marked
+        set_preference("BrailleNavHighlight".to_string(), "Off".to_string()).unwrap();
+        assert_eq!(("⠼⠙⠰⠁⠉".to_string(), 2, 4), get_braille_with_nav_range("id-4".to_string())?);
+        assert_eq!(("⠼⠙⠰⠁⠉".to_string(), 0, 5), get_braille_with_nav_range("id-1".to_string())?);
+        set_preference("BrailleNavHighlight".to_string(), "FirstChar".to_string()).unwrap();
+        assert_eq!(("⠼⠙⣰⣁⠉".to_string(), 2, 4), get_braille_with_nav_range("id-4".to_string())?);
+        assert_eq!(("⠼⠙⠰⠁⠉".to_string(), 0, 0), get_braille_with_nav_range("id-99".to_string())?);
+        return Ok( () );
+    }
+
+    #[test]
+    fn nav_range_equals() -> Result<()> {
+        // NABCC '=' is ⠿ and becomes ⣿ when highlighted (the last char in the braille block)
+        let mathml_str = "<math id='id-0'>
+            <mrow id='id-1'><mi id='id-2'>x</mi><mo id='id-3'>=</mo><mn id='id-4'>2</mn></mrow>
+        </math>";
+        crate::interface::set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+        set_mathml(mathml_str.to_string()).unwrap();
+        set_preference("BrailleCode".to_string(), "LaTeX".to_string()).unwrap();
+        set_preference("BrailleNavHighlight".to_string(), "EndPoints".to_string()).unwrap();
+        assert_eq!(("⠭⣿⠆".to_string(), 1, 2), get_braille_with_nav_range("id-3".to_string())?);
+        set_preference("BrailleNavHighlight".to_string(), "Off".to_string()).unwrap();
+        assert_eq!(("⠭⠿⠆".to_string(), 1, 2), get_braille_with_nav_range("id-3".to_string())?);
+        return Ok( () );
+    }
+
+    #[test]
+    fn latex_highlight() -> Result<()> {
+        let mathml_str = "<math id='id-0'>
+            <mfrac id='id-1'>
+                <mrow id='id-2'><mi id='id-3'>x</mi><mo id='id-4'>+</mo><mi id='id-5'>α</mi></mrow>
+                <mn id='id-6'>2</mn>
+            </mfrac>
+        </math>";
+        crate::interface::set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+        set_mathml(mathml_str.to_string()).unwrap();
+        set_preference("BrailleCode".to_string(), "LaTeX".to_string()).unwrap();
+        set_preference("BrailleNavHighlight".to_string(), "EndPoints".to_string()).unwrap();
+        let braille = get_braille("id-2".to_string())?;
+        assert_eq!("⡳⠋⠗⠁⠉⠪⣭⠬⡳⠁⠇⠏⠓⣁⠻⠪⠆⠻", braille);
+        let braille = get_braille("id-5".to_string())?;
+        assert_eq!("⡳⠋⠗⠁⠉⠪⠭⠬⣳⠁⠇⠏⠓⣁⠻⠪⠆⠻", braille);
+        return Ok( () );
+    }
+
+    #[test]
+    fn latex_highlight_equals() -> Result<()> {
+        // NABCC '=' is ⠿, so highlighting it uses the last char (⣿) in the braille block
+        let mathml_str = "<math id='id-0'>
+            <mrow id='id-1'><mi id='id-2'>x</mi><mo id='id-3'>=</mo><mn id='id-4'>2</mn></mrow>
+        </math>";
+        crate::interface::set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+        set_mathml(mathml_str.to_string()).unwrap();
+        set_preference("BrailleCode".to_string(), "LaTeX".to_string()).unwrap();
+        set_preference("BrailleNavHighlight".to_string(), "EndPoints".to_string()).unwrap();
+        let braille = get_braille("id-3".to_string())?;
+        assert_eq!("⠭⣿⠆", braille);
+        assert!(is_highlighted('⣿'));
+        assert_eq!(unhighlight('⣿'), '⠿');
+        return Ok( () );
+    }
+
     #[test]
     #[allow(non_snake_case)]
     fn test_UEB_start_mode() -> Result<()> {
@@ -12,10 +12,14 @@ use sxd_document::QName;
 use phf::{phf_map, phf_set};
 use crate::xpath_functions::{IsBracketed, is_leaf};
 use std::ptr::eq as ptr_eq;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use crate::pretty_print::*;
 use regex::Regex;
 use std::fmt;
 use crate::chemistry::*;
+use crate::source_map::{SOURCE_REF_ATTR, merge_sources, split_sources};
+use crate::diagnostics::{DIAGNOSTIC_ATTR, DiagnosticKind, Severity, report};
 
 // FIX: DECIMAL_SEPARATOR should be set by env, or maybe language
 const DECIMAL_SEPARATOR: &str = ".";
@@ -26,6 +30,10 @@ const EMPTY_IN_2D: &str = "data-empty-in-2D";
 const SPACE_AFTER: &str = "data-space-after";
 // character to use instead of the text content for priority, etc.
 pub const CHEMICAL_BOND: &str ="data-chemical-bond";
+/// Marks a leaf that is a unit of measure (e.g., the "m" in "9.8 m/s²"). The value is the unit symbol (e.g., "m" or "°C").
+pub const UNIT_ATTR: &str = "data-unit";
+/// The column of a cell (a digit, `none`, `mscarry`, ...) in an `mstack` or `mlongdiv`, counting from 1 on the left.
+pub const STACK_COLUMN_ATTR: &str = "data-column";
 
 /// Used when mhchem is detected and we should favor postscripts rather than prescripts in constructing an mmultiscripts
 const MHCHEM_MMULTISCRIPTS_HACK: &str = "MHCHEM_SCRIPT_HACK";
@@ -33,12 +41,42 @@ const MHCHEM_MMULTISCRIPTS_HACK: &str = "MHCHEM_SCRIPT_HACK";
 // (perfect) hash of operators built from MathML's operator dictionary
 static OPERATORS: phf::Map<&str, OperatorInfo> = include!("operator-info.in");
 
+/// Operators from the `OperatorDictionary` definitions that are added to or override the built-in [`OPERATORS`].
+#[derive(Default)]
+struct OperatorOverrides {
+	definitions: HashMap<String, String>,		// the (merged) definitions -- the operators are only rebuilt when these change
+	operators: HashMap<String, &'static OperatorInfo>,
+	// the OperatorInfo needs to be 'static like the built-in ones, so they are leaked -- they are interned by value
+	// so that rebuilding (e.g., switching back and forth between subject areas) reuses them rather than leaking more
+	interned: HashMap<Vec<(OperatorTypes, usize)>, &'static OperatorInfo>,
+}
+
+thread_local!{
+	static OPERATOR_OVERRIDES: RefCell<OperatorOverrides> = RefCell::new( OperatorOverrides::default() );
+}
+
 
 // The set of fence operators that can being either a left or right fence (or infix). For example: "|".
 static AMBIGUOUS_OPERATORS: phf::Set<&str> = phf_set! {
 	"|", "∥", "\u{2016}"
 };
 
+// The quantifiers. They are followed by the bound variable(s), an optional separator, and the body they scope over.
+// For example: "∀x ∈ S, x > 0" or "∃y P(y)".
+static QUANTIFIERS: phf::Set<&str> = phf_set! {
+	"∀", "∃", "∄", "∃!"
+};
+
+// The separators that can come between the bound variable(s) of a quantifier and its body
+static QUANTIFIER_SEPARATORS: phf::Set<&str> = phf_set! {
+	",", ":", "."
+};
+
+// Operators that only occur in logical formulas -- when one of these is present, the logical connectives are lower priority than relations
+static LOGICAL_OPERATORS: phf::Set<&str> = phf_set! {
+	"¬", "⇒", "⇐", "⇔", "⟹", "⟸", "⟺", "⊢", "⊨", "⊩", "⊬", "⊭"
+};
+
 // static vars used when canonicalizing
 lazy_static!{
 	// lowest priority operator so it is never popped off the stack
@@ -71,6 +109,44 @@ lazy_static!{
 	static ref IMPLIED_PLUS_SLASH_HIGH_PRIORITY: OperatorInfo = OperatorInfo{	// (linear) mixed fraction 2 3/4
 		op_type: OperatorTypes::INFIX, priority: 881, next: &None
 	};
+	// QUANTIFIER_SCOPE -- the (possibly implied) separator between the bound variable(s) of a quantifier and its body
+	// It is lower priority than anything in the body and, unlike ",", it isn't n-ary so that chained quantifiers nest
+	static ref QUANTIFIER_SCOPE: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::INFIX, priority: 40, next: &None
+	};
+	// BOUND_VARIABLE_SEPARATOR -- the "," between bound variables that share a restriction (e.g., "∀x, y ∈ ℝ")
+	// It is higher priority than relations so that the restriction applies to all the variables
+	static ref BOUND_VARIABLE_SEPARATOR: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::INFIX, priority: 901, next: &None
+	};
+	// SET_BUILDER_SEPARATOR -- the "|" or ":" in {x | x > 0, x < 5} separates the element from all the conditions
+	static ref SET_BUILDER_SEPARATOR: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::INFIX, priority: 35, next: &None
+	};
+	// The logical connectives in a logical formula -- they are lower priority than relations (but higher than quantifiers)
+	//   so that "x > 0 ∧ x < 5" and "x = 1 ⇒ x² = 1" group around the relations. "⊢" separates the lists in a sequent.
+	static ref LOGICAL_NOT: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::PREFIX, priority: 238, next: &None
+	};
+	static ref LOGICAL_AND: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::INFIX, priority: 236, next: &None
+	};
+	static ref LOGICAL_OR: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::INFIX, priority: 234, next: &None
+	};
+	static ref LOGICAL_IMPLIES: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::INFIX, priority: 232, next: &None
+	};
+	static ref LOGICAL_IFF: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::INFIX, priority: 231, next: &None
+	};
+	static ref SEQUENT: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::INFIX, priority: 35, next: &None
+	};
+	// EVALUATED_AT -- a scripted vertical bar after an expression (e.g., x^2|_0^1); it applies to everything up to a relation
+	static ref EVALUATED_AT: OperatorInfo = OperatorInfo{
+		op_type: OperatorTypes::POSTFIX, priority: 265, next: &None
+	};
 
 	// Useful static defaults to have available if there is no character match
 	static ref DEFAULT_OPERATOR_INFO_PREFIX: &'static OperatorInfo = &OperatorInfo{
@@ -214,6 +290,9 @@ impl OperatorInfo {
 	}
 
 	fn is_nary(&self, previous_op: &OperatorInfo) -> bool {
+		if ptr_eq(self, &*QUANTIFIER_SCOPE) {
+			return false;		// "∀x ∃y P(x,y)" nests
+		}
 		return	ptr_eq(previous_op,self) ||
 				(previous_op.is_plus_or_minus() && self.is_plus_or_minus()) ||
 				(previous_op.is_times() && self.is_times());
@@ -328,6 +407,9 @@ pub fn set_mathml_name(element: Element, new_name: &str) {
 
 pub fn replace_children<'a>(mathml: Element<'a>, replacements: Vec<Element<'a>>) -> Element<'a> {
 	// replace the children of the parent (must exist since this only happens for leaves) with the new children
+	if replacements.len() > 1 {
+		split_sources(mathml, &replacements);
+	}
 	if replacements.len() == 1 {
 		// rather than replace the children, the children are already in place, so we can optimize a little
 		add_attrs(mathml, replacements[0].attributes());
@@ -438,6 +520,7 @@ impl CanonicalizeContext {
 		let pref_manager = pref_manager.borrow();
 		let block_separator_pref = pref_manager.pref_to_string("BlockSeparators");
 		let decimal_separator_pref = pref_manager.pref_to_string("DecimalSeparators");
+		update_operator_overrides(&pref_manager.subject_area());
 
 		let block_separator = Regex::new(&format!("[{}]", regex::escape(&block_separator_pref))).unwrap();
 		let decimal_separator = Regex::new(&format!("[{}]", regex::escape(&decimal_separator_pref))).unwrap();
@@ -722,6 +805,9 @@ impl CanonicalizeContext {
 					let mn = create_mathml_element(&doc, "mn");
 					mo.set_text("-");
 					mn.set_text(&text[first_char.len_utf8()..]);
+					split_sources(mathml, &[mo, mn]);
+					report(mathml, DiagnosticKind::LeafSplit, Severity::Warning,
+						   format!("'{}' in an 'mn' was split into a minus sign and the number '{}'", text, as_text(mn)));
 					set_mathml_name(mathml, "mrow");
 					mathml.set_attribute_value(CHANGED_ATTR, ADDED_ATTR_VALUE);
 					mathml.replace_children([mo,mn]);
@@ -751,10 +837,12 @@ impl CanonicalizeContext {
 					}
 					if name(&as_element(preceding_siblings[preceding_siblings.len()-1])) != "mo" &&
 					   name(&as_element(following_siblings[0])) != "mo" {
+						report_retag(mathml, "mo");
 						set_mathml_name(mathml, "mo");
 					}
 					return Some(mathml);
-				} else if OPERATORS.get(text).is_some() {
+				} else if lookup_operator(text).is_some() {
+					report_retag(mathml, "mo");
 					set_mathml_name(mathml, "mo");
 					return Some(mathml);
 				} else if let Some(result) = merge_arc_trig(mathml) {
@@ -795,7 +883,8 @@ impl CanonicalizeContext {
 					CanonicalizeContext::make_empty_element(mathml);
 				} else if let Some(dash) = canonicalize_dash(text) {
 					mathml.set_text(dash);
-				} else if OPERATORS.get(text).is_some() {
+				} else if lookup_operator(text).is_some() {
+					report_retag(mathml, "mo");
 					set_mathml_name(mathml, "mo");
 					return Some(mathml);
 				}
@@ -828,6 +917,9 @@ impl CanonicalizeContext {
 						} else {
 							return Some(mathml);
 						},
+						"∃" => if let Some(result) = merge_exists_unique(mathml) {
+							return Some(result);
+						},
 						_ => (),
 					}
 				}
@@ -838,6 +930,7 @@ impl CanonicalizeContext {
 					if ["…", "⋯", "∞"].contains(&text) ||
 					   definitions.borrow().get_hashset("FunctionNames").unwrap().contains(text) ||
 					   definitions.borrow().get_hashset("GeometryShapes").unwrap().contains(text) {
+						report_retag(mathml, "mi");
 						set_mathml_name(mathml, "mi");
 						return Some(mathml);
 					}
@@ -847,6 +940,7 @@ impl CanonicalizeContext {
 						return Some(mathml);
 					}
 					if CURRENCY_SYMBOLS.contains(text) {
+						report_retag(mathml, "mi");
 						set_mathml_name(mathml, "mi");
 						return Some(mathml);
 					}
@@ -1001,6 +1095,8 @@ impl CanonicalizeContext {
 					merge_number_blocks(self, mathml, &mut children);
 					merge_whitespace(&mut children);
 					handle_convert_to_mmultiscripts(&mut children);
+					group_differentials(mathml, &mut children);
+					mark_units(mathml, &children);
 
 				} else if element_name == "msub" || element_name == "msup" || 
 						  element_name == "msubsup" || element_name == "mmultiscripts"{
@@ -1043,6 +1139,9 @@ impl CanonicalizeContext {
 				if element_name == "mrow" || ELEMENTS_WITH_ONE_CHILD.contains(element_name) {
 					clean_chemistry_mrow(mathml);
 				}
+				if element_name == "mstack" || element_name == "mlongdiv" {
+					clean_stack(mathml);
+				}
 				self.assure_nary_tag_has_one_child(mathml);
 				if crate::xpath_functions::IsNode::is_2D(&mathml) {
 					CanonicalizeContext::mark_empty_content(mathml);
@@ -1153,7 +1252,7 @@ impl CanonicalizeContext {
 							"∷" | "::" => return Some(true),		// "::" might not be canonicalized yet
 							"∶" => return Some(false),
 							_ => {
-								if let Some(op) = OPERATORS.get(text) {
+								if let Some(op) = lookup_operator(text) {
 									if op.priority < *PROPORTIONAL_PRIORITY {
 										return None;		// no "∷"
 									}
@@ -1344,6 +1443,164 @@ impl CanonicalizeContext {
 			}
 		}
 
+		/// Put an `mstack` or `mlongdiv` into a form that is simple for speech, navigation, and braille:
+		/// * the rows (the children of `mstack`, and the children after the first three of `mlongdiv`) are all `msrow`, `mscarries`, or `msline`.
+		///   `msgroup`s are removed by adding their `shift`s to the `position` of the rows; other children are wrapped in an `msrow`.
+		/// * the result and dividend of an `mlongdiv` are `msrow`s (the divisor is left alone)
+		/// * an `mn` in an `msrow` is split into an `mn` for each character because each character is in its own column
+		/// * the children of `mscarries` are all `mscarry`s, which get the `mscarries`' `crossout` and `location` if they don't have their own
+		/// * the cells (the children of the rows, except for a leading operator) are given their column in STACK_COLUMN_ATTR.
+		///   As with braille, the rows are aligned on their right edges and `position` shifts a row to the left.
+		fn clean_stack(mathml: Element) {
+			let children = mathml.children().into_iter().map(as_element).collect::<Vec<Element>>();
+			let mut new_children = vec![];
+			let mut rows = vec![];
+			let rows_start = if name(&mathml) == "mlongdiv" {
+				new_children.push(children[0]);
+				for &child in &children[1..3] {
+					rows.push( clean_stack_row(child, 0) );
+				}
+				3
+			} else {
+				0
+			};
+			for &child in &children[rows_start..] {
+				flatten_stack_rows(child, 0, &mut rows);
+			}
+
+			// find the right edge so the columns can be numbered from the left
+			let cells = rows.iter().map(|&row| stack_row_cells(row)).collect::<Vec<Vec<Element>>>();
+			let right_edge = rows.iter().zip(&cells)
+					.map(|(&row, cells)| {
+						let width = if name(&row) == "msline" {get_int_attr(row, "length", 0).max(0)} else {cells.len() as isize};
+						width + get_int_attr(row, "position", 0)
+					})
+					.max()
+					.unwrap_or(0);
+			for (&row, cells) in rows.iter().zip(&cells) {
+				let first_column = right_edge - get_int_attr(row, "position", 0) - cells.len() as isize + 1;
+				for (i, cell) in cells.iter().enumerate() {
+					cell.set_attribute_value(STACK_COLUMN_ATTR, &(first_column + i as isize).to_string());
+				}
+			}
+			new_children.append(&mut rows);
+			mathml.replace_children(new_children);
+
+			/// Move the rows in 'child' (possibly nested `msgroup`s) to 'rows', folding 'position' into each row's `position`
+			fn flatten_stack_rows<'a>(child: Element<'a>, position: isize, rows: &mut Vec<Element<'a>>) {
+				let position = position + get_int_attr(child, "position", 0);
+				if name(&child) == "msgroup" {
+					let shift = get_int_attr(child, "shift", 0);
+					for (i, grandchild) in child.children().into_iter().map(as_element).enumerate() {
+						flatten_stack_rows(grandchild, position + (i as isize)*shift, rows);
+					}
+				} else {
+					rows.push( clean_stack_row(child, position) );
+				}
+			}
+
+			/// Returns 'child' as an `msrow`, `mscarries`, or `msline` with its `position` set to 'position'
+			fn clean_stack_row(child: Element, position: isize) -> Element {
+				let row = match name(&child) {
+					"msrow" | "mscarries" | "msline" => child,
+					_ => {
+						let msrow = create_mathml_element(&child.document(), "msrow");
+						msrow.set_attribute_value(CHANGED_ATTR, ADDED_ATTR_VALUE);
+						msrow.append_child(child);
+						msrow
+					},
+				};
+				if position == 0 {
+					row.remove_attribute("position");
+				} else {
+					row.set_attribute_value("position", &position.to_string());
+				}
+				match name(&row) {
+					"msrow" => {
+						let mut cells = vec![];
+						for cell in row.children().into_iter().map(as_element) {
+							if name(&cell) == "mn" && as_text(cell).chars().count() > 1 {
+								cells.append(&mut split_number(cell));
+							} else {
+								cells.push(cell);
+							}
+						}
+						row.replace_children(cells);
+					},
+					"mscarries" => {
+						let crossout = row.attribute_value("crossout");
+						let location = row.attribute_value("location");
+						let mut carries = vec![];
+						for carry in row.children().into_iter().map(as_element) {
+							let carry = if name(&carry) == "mscarry" {
+								carry
+							} else {
+								let mscarry = create_mathml_element(&carry.document(), "mscarry");
+								mscarry.set_attribute_value(CHANGED_ATTR, ADDED_ATTR_VALUE);
+								mscarry.append_child(carry);
+								mscarry
+							};
+							for (attr_name, value) in [("crossout", crossout), ("location", location)] {
+								if let Some(value) = value {
+									if carry.attribute(attr_name).is_none() {
+										carry.set_attribute_value(attr_name, value);
+									}
+								}
+							}
+							carries.push(carry);
+						}
+						row.replace_children(carries);
+					},
+					_ => (),
+				}
+				return row;
+			}
+
+			/// Split 'mn' into an `mn` for each (non-whitespace) character. The first one keeps the `id`.
+			fn split_number(mn: Element) -> Vec<Element> {
+				let doc = mn.document();
+				let digits = as_text(mn).chars()
+						.filter(|ch| !ch.is_whitespace())
+						.map(|ch| {
+							let digit = create_mathml_element(&doc, "mn");
+							digit.set_text(&ch.to_string());
+							digit
+						})
+						.collect::<Vec<Element>>();
+				split_sources(mn, &digits);
+				for (i, &digit) in digits.iter().enumerate() {
+					for attr in mn.attributes() {
+						let attr_name = attr.name().local_part();
+						if attr_name != SOURCE_REF_ATTR && attr_name != DIAGNOSTIC_ATTR && (i == 0 || attr_name != "id") {
+							digit.set_attribute_value(attr.name(), attr.value());
+						}
+					}
+				}
+				return digits;
+			}
+
+			/// The cells of a row (an `msrow` minus a leading operator or the `mscarry`s of an `mscarries`)
+			fn stack_row_cells(row: Element) -> Vec<Element> {
+				let mut cells = row.children().into_iter().map(as_element).collect::<Vec<Element>>();
+				match name(&row) {
+					"msrow" => {
+						if cells.first().is_some_and(|first| name(first) == "mo") {
+							cells.remove(0);
+						}
+						return cells;
+					},
+					"mscarries" => return cells,
+					_ => return vec![],
+				}
+			}
+
+			fn get_int_attr(element: Element, attr_name: &str, default: isize) -> isize {
+				return element.attribute_value(attr_name)
+						.and_then(|value| value.trim().parse::<isize>().ok())
+						.unwrap_or(default);
+			}
+		}
+
 		/// If arg is "arc" (with optional space), merge the following element in if a trig function (sibling is deleted)
 		fn merge_arc_trig(leaf: Element) -> Option<Element> {
 			assert!(is_leaf(leaf));
@@ -1368,8 +1625,11 @@ impl CanonicalizeContext {
 				let following_text = as_text(following_sibling);
 				if definitions.borrow().get_hashset("TrigFunctionNames").unwrap().contains(following_text) {
 					let new_text = "arc".to_string() + following_text;
+					report(leaf, DiagnosticKind::LeavesMerged, Severity::Warning,
+						   format!("'{}' '{}' was merged into '{}'", leaf_text, following_text, new_text));
 					set_mathml_name(leaf, "mi");
 					leaf.set_text(&new_text);
+					merge_sources(leaf, &[following_sibling]);
 					following_sibling.remove_from_parent();
 					return Some(leaf);
 				}
@@ -1382,6 +1642,7 @@ impl CanonicalizeContext {
 			assert!(is_leaf(leaf));
 			let leaf_text = as_text(leaf);
 			if leaf_text == "||" {
+				report(leaf, DiagnosticKind::LeavesMerged, Severity::Warning, "'||' was changed to '‖' (U+2016)".to_string());
 				leaf.set_text("‖");		// U+2016
 				return Some(leaf);
 			} else if leaf_text != "|" {
@@ -1418,7 +1679,28 @@ impl CanonicalizeContext {
 			}
 
 			// didn't find any
+			report(leaf, DiagnosticKind::LeavesMerged, Severity::Warning, "'|' '|' was merged into '‖' (U+2016)".to_string());
 			leaf.set_text("‖");		// U+2016
+			merge_sources(leaf, &[following_sibling]);
+			following_sibling.remove_from_parent();
+			return Some(leaf);
+		}
+
+		/// Convert "∃" "!" to "∃!" (there exists a unique) -- the "!" is not a factorial or a prefix operator
+		fn merge_exists_unique(leaf: Element) -> Option<Element> {
+			assert!(is_leaf(leaf));
+			let following_siblings = leaf.following_siblings();
+			if following_siblings.is_empty() {
+				return None;
+			}
+
+			let following_sibling = as_element(following_siblings[0]);
+			if name(&following_sibling) != "mo" || as_text(following_sibling) != "!" {
+				return None;
+			}
+			report(leaf, DiagnosticKind::LeavesMerged, Severity::Warning, "'∃' '!' was merged into '∃!'".to_string());
+			leaf.set_text("∃!");
+			merge_sources(leaf, &[following_sibling]);
 			following_sibling.remove_from_parent();
 			return Some(leaf);
 		}
@@ -1434,7 +1716,8 @@ impl CanonicalizeContext {
 				return;
 			}
 			let are_same = mathml.attributes().iter()
-							.zip( following_element.attributes() )
+							.filter(|attr| !is_bookkeeping_attr(attr))
+							.zip( following_element.attributes().iter().filter(|attr| !is_bookkeeping_attr(attr)) )
 							.all(|(first, second)| first.name()==second.name() && first.value()==second.value());
 			if are_same {
 				mathml.append_children(following_element.children());
@@ -1442,11 +1725,19 @@ impl CanonicalizeContext {
 			}
 		}
 
+		/// attrs used to keep track of where things came from and what happened to them (they don't affect canonicalization)
+		fn is_bookkeeping_attr(attr: &Attribute) -> bool {
+			let attr_name = attr.name().local_part();
+			return attr_name == SOURCE_REF_ATTR || attr_name == DIAGNOSTIC_ATTR;
+		}
+
 		fn convert_mfenced_to_mrow(mfenced: Element) -> Element {
 			// The '<'/'>' replacements are because WIRIS uses them out instead of the correct chars in its template
 			let open = mfenced.attribute_value("open").unwrap_or("(").replace('<', "⟨");
 			let close = mfenced.attribute_value("close").unwrap_or(")").replace('>', "⟩");
 			debug!("open={}, close={}", open, close);
+			report(mfenced, DiagnosticKind::MfencedConverted, Severity::Warning,
+				   format!("'mfenced' is deprecated; converted to an 'mrow' with fences '{}' and '{}'", open, close));
 			let mut separators= mfenced.attribute_value("separators").unwrap_or(",").chars();
 			set_mathml_name(mfenced, "mrow");
 			mfenced.remove_attribute("open");
@@ -1586,6 +1877,7 @@ impl CanonicalizeContext {
 								let new_text = "\u{A0}".to_string() + as_text(next_child);
 								next_child.set_text(&new_text);
 							}
+							merge_sources(next_child, &[child]);
 							children.remove(i);	
 							continue;	// try again with 'next' removed
 						}
@@ -1596,6 +1888,7 @@ impl CanonicalizeContext {
 						if name(&prev_child) == "mi" || name(&prev_child) == "mn" || name(&prev_child) == "mtext" {
 							let new_text = as_text(prev_child).to_string() + "\u{A0}";
 							prev_child.set_text(&new_text);
+							merge_sources(prev_child, &[child]);
 							children.remove(i);
 							continue;		// don't advance 'i'
 						}	
@@ -1605,6 +1898,7 @@ impl CanonicalizeContext {
 						if name(&next_child) == "mi" || name(&next_child) == "mn" {
 							let new_text = "\u{A0}".to_string() + as_text(next_child);
 							next_child.set_text(&new_text);
+							merge_sources(next_child, &[child]);
 							children.remove(i);
 							i += 1; 	// don't need to look at next child since we know what it is
 							continue;
@@ -1615,6 +1909,201 @@ impl CanonicalizeContext {
 			}
 		}
 
+		/// Group a differential (e.g., "d x" or "∂ x") into an mrow so it is treated as a single unit
+		/// (otherwise, the "d x" at the end of an integrand is just more implied multiplication).
+		/// "∂" and "ⅆ" are always differentials; "d" is only considered one when it follows an integral.
+		fn group_differentials<'a>(mrow: Element<'a>, children: &mut Vec<ChildOfElement<'a>>) {
+			if children.len() <= 2 {
+				return;		// nothing to gain by grouping (or already grouped)
+			}
+			let mut i = 0;
+			while i + 1 < children.len() {
+				let child = as_element(children[i]);
+				if is_differential_d(child) && is_differential_var(as_element(children[i+1])) &&
+				   (as_text(child) != "d" || is_after_integral(mrow, &children[..i])) {
+					let differential = create_mathml_element(&mrow.document(), "mrow");
+					differential.set_attribute_value(CHANGED_ATTR, ADDED_ATTR_VALUE);
+					differential.append_children(children.drain(i..i+2).collect::<Vec<ChildOfElement>>());
+					children.insert(i, ChildOfElement::Element(differential));
+				}
+				i += 1;
+			}
+
+			fn is_differential_d(leaf: Element) -> bool {
+				return (name(&leaf) == "mi" || name(&leaf) == "mo") && matches!(as_text(leaf), "d" | "ⅆ" | "∂");
+			}
+
+			fn is_differential_var(var: Element) -> bool {
+				let var = match name(&var) {
+					"msub" | "msup" => as_element(var.children()[0]),
+					_ => var,
+				};
+				return name(&var) == "mi" && as_text(var) != "d";
+			}
+
+			/// true if an integral comes before 'preceding_children' in 'mrow' or in an ancestor of 'mrow'
+			fn is_after_integral(mrow: Element, preceding_children: &[ChildOfElement]) -> bool {
+				if preceding_children.iter().any(|&child| is_integral(as_element(child))) {
+					return true;
+				}
+				let mut element = mrow;
+				while name(&element) != "math" {
+					if element.preceding_siblings().iter().any(|&child| child.element().is_some_and(is_integral)) {
+						return true;
+					}
+					element = match element.parent().and_then(|parent| parent.element()) {
+						Some(parent) => parent,
+						None => return false,
+					};
+				}
+				return false;
+			}
+
+			fn is_integral(element: Element) -> bool {
+				let op = get_possible_embellished_node(element);
+				return name(&op) == "mo" && matches!(as_text(op), "∫" | "∬" | "∭" | "⨌" | "∮" | "∯" | "∰" | "∱" | "∲" | "∳");
+			}
+		}
+
+		/// Mark units of measure (e.g., the "m" and "s" in "9.8 m/s²") with UNIT_ATTR (its value is the unit symbol).
+		/// Unit symbols are only recognized when they follow a number, but units explicitly marked
+		/// with `intent=":unit"` or `class="MathML-unit"` are always marked.
+		/// Note: "°" has already been made a pseudo-script of the number, so "25 °C" is `msup(25, °)` followed by "C".
+		/// Chemistry is left alone: "2 N₂ + 3 H₂" is not "2 newtons sub 2 plus 3 henries sub 2".
+		fn mark_units(mrow: Element, children: &[ChildOfElement]) {
+			if mrow.attribute(MAYBE_CHEMISTRY).is_some() || mrow.attribute(UNIT_ATTR).is_some() ||
+			   children.iter().any(|&child| is_likely_chemistry(as_element(child))) {
+				return;
+			}
+			let mut i = 0;
+			while i < children.len() {
+				let child = as_element(children[i]);
+				if is_explicit_unit(child) {
+					mark_unit(child);
+				}
+				i += 1;
+				if is_degrees(child) {
+					if let Some(&scale) = children.get(i) {
+						let scale = as_element(scale);
+						if (name(&scale) == "mi" || name(&scale) == "mtext") && matches!(as_text(scale).trim(), "C" | "F") {
+							scale.set_attribute_value(UNIT_ATTR, &format!("°{}", as_text(scale).trim()));
+							i += 1;
+						}
+					}
+					continue;
+				}
+				if name(&child) != "mn" {
+					continue;
+				}
+				// find the longest sequence of units (possibly separated by '/', '·', etc) after the number
+				let mut end = i;
+				while end < children.len() {
+					if is_unit_at(children, end) {
+						end += 1;
+					} else if end > i && end + 1 < children.len() &&
+							  is_unit_operator(as_element(children[end])) && is_unit_at(children, end+1) {
+						end += 2;
+					} else {
+						break;
+					}
+				}
+				// if an identifier follows (e.g., the "Cl" in "2 H Cl"), these probably aren't units
+				let is_followed_by_identifier = children.get(end).is_some_and(|&next| matches!(name(&as_element(next)), "mi" | "mtext"));
+				// a lone element symbol (e.g., the "C" in "6 C") is more likely chemistry than a unit (coulombs)
+				let is_lone_element_symbol = end == i + 1 && is_element_symbol(as_element(children[i]));
+				if !(is_followed_by_identifier || is_lone_element_symbol) {
+					children[i..end].iter().for_each(|&unit| mark_unit(as_element(unit)));
+				}
+				i = end;
+			}
+
+			/// true if children[i] is a unit and not a function call such as "min(x,y)" or "min{a,b}"
+			fn is_unit_at(children: &[ChildOfElement], i: usize) -> bool {
+				let element = as_element(children[i]);
+				if !is_unit(element) {
+					return false;
+				}
+				return !(is_leaf(element) && children.get(i+1).is_some_and(|&next| {
+					let next = as_element(next);
+					name(&next) == "mo" && is_fence(next)
+				}));
+			}
+
+			/// true if 'element' is a chemical formula such as "N₂" (units have superscripts, but not subscripts) or a reaction arrow
+			fn is_likely_chemistry(element: Element) -> bool {
+				return match name(&element) {
+					"msub" | "msubsup" | "mmultiscripts" => element.attribute(MAYBE_CHEMISTRY).is_some(),
+					"mo" => matches!(as_text(element), "→" | "⟶" | "⇌" | "⇄" | "⇋" | "⟷" | "⥂" | "⥄" | "⇀"),
+					_ => false,
+				}
+			}
+
+			/// true if 'element' is a (non-explicit) unit symbol that is also a one letter chemical element (e.g., "C", "K", "N")
+			fn is_element_symbol(element: Element) -> bool {
+				let element = if name(&element) == "msup" {as_element(element.children()[0])} else {element};
+				return (name(&element) == "mi" || name(&element) == "mtext") && !is_explicit_unit(element) &&
+					   as_text(element).trim().len() == 1 && is_chemical_element(element);
+			}
+
+			/// true if 'element' is a number with a degree sign (e.g., "25°")
+			fn is_degrees(element: Element) -> bool {
+				if name(&element) != "msup" {
+					return false;
+				}
+				let children = element.children();
+				let script = as_element(children[1]);
+				return name(&as_element(children[0])) == "mn" && name(&script) == "mo" && as_text(script) == "°";
+			}
+
+			fn is_explicit_unit(element: Element) -> bool {
+				return crate::infer_intent::has_intent_property(element, "unit") ||
+				       element.attribute_value("class").is_some_and(|class| class.split_whitespace().any(|c| c == "MathML-unit"));
+			}
+
+			/// true if 'element' is (or is built from) unit symbols (e.g., "m", "km²", "m/s")
+			fn is_unit(element: Element) -> bool {
+				return match name(&element) {
+					"mi" | "mtext" => {
+						if is_explicit_unit(element) {
+							return true;
+						}
+						// a single italic letter is almost certainly a variable
+						let text = as_text(element).trim();
+						(name(&element) == "mtext" || text.chars().count() > 1 || !text.chars().all(char::is_alphabetic) ||
+						 element.attribute_value("mathvariant") == Some("normal")) &&
+						crate::xpath_functions::UnitName::is_unit(text)
+					},
+					"msup" | "msub" => {
+						let base = as_element(element.children()[0]);
+						is_leaf(base) && is_unit(base)
+					},
+					"mfrac" => element.children().iter().all(|&child| is_unit(as_element(child))),
+					"mrow" => {
+						let children = element.children();
+						children.iter().any(|&child| is_unit(as_element(child))) &&
+						children.iter().all(|&child| is_unit(as_element(child)) || is_unit_operator(as_element(child)))
+					},
+					_ => false,
+				}
+			}
+
+			fn is_unit_operator(element: Element) -> bool {
+				return name(&element) == "mo" && matches!(as_text(element), "\u{2062}" | "/" | "⋅" | "·" | "∙");
+			}
+
+			fn mark_unit(element: Element) {
+				match name(&element) {
+					"mi" | "mtext" => {element.set_attribute_value(UNIT_ATTR, as_text(element).trim());},
+					"msup" | "msub" => mark_unit(as_element(element.children()[0])),
+					"mfrac" | "mrow" => element.children().iter()
+											.map(|&child| as_element(child))
+											.filter(|&child| !is_unit_operator(child))
+											.for_each(mark_unit),
+					_ => (),
+				}
+			}
+		}
+
 		/// look for potential numbers by looking for sequences with commas, spaces, and decimal points
 		fn merge_number_blocks(context: &CanonicalizeContext, parent_mrow: Element, children: &mut Vec<ChildOfElement>) {
 			// debug!("parent:\n{}", mml_to_string(&parent_mrow));
@@ -1750,6 +2239,8 @@ impl CanonicalizeContext {
 					new_leaf.set_text(&ch.to_string());
 					children.push(new_leaf);
 				}
+				split_sources(leaf, &children);
+				report(leaf, DiagnosticKind::LeafSplit, Severity::Info, format!("'{}' was split into separate letters", as_text(leaf)));
 				set_mathml_name(leaf, "mrow");
 				leaf.replace_children(children);
 				return leaf;
@@ -1888,8 +2379,11 @@ impl CanonicalizeContext {
 				mn_text.push_str(as_text(child));
 			}
 			let child = as_element(children[start]);
+			report(child, DiagnosticKind::NumberMerged, Severity::Warning,
+				   format!("{} elements were merged into the number '{}'", end-start, mn_text));
 			set_mathml_name(child, "mn");
 			child.set_text(&mn_text);
+			merge_sources(child, &children[start+1..end].iter().map(|&child| as_element(child)).collect::<Vec<Element>>());
 
 			children.drain(start+1..end);
 		}
@@ -1910,6 +2404,7 @@ impl CanonicalizeContext {
 						} else {
 							// merge chars
 							new_text.push_str(text);
+							merge_sources(first_child.unwrap(), &[child]);
 							child.remove_from_parent();
 						}
 					} else if new_text.len() > 1 {
@@ -1947,7 +2442,9 @@ impl CanonicalizeContext {
 						n_dots += 1;
 						if n_dots == 3 {
 							let first_child = as_element(children[i-2]);
+							report(first_child, DiagnosticKind::LeavesMerged, Severity::Warning, "'.' '.' '.' was merged into '…'".to_string());
 							first_child.set_text("…");
+							merge_sources(first_child, &[as_element(children[i-1]), child]);
 							as_element(children[i-1]).remove_from_parent();
 							child.remove_from_parent();
 							n_dots = 0;
@@ -2000,6 +2497,7 @@ impl CanonicalizeContext {
 				let child = as_element(child_as_element);
 				let text = as_text(child); 		// only in this function because it is an <mo>
 				new_text.push_str(text);
+				merge_sources(first_child, &[child]);
 				child.remove_from_parent();
 			}
 			first_child.set_text(&merge_prime_text(&new_text));
@@ -2727,7 +3225,7 @@ impl CanonicalizeContext {
 		let found_op_info = if mo_node.attribute_value(CHEMICAL_BOND).is_some() {
 			Some(&*IMPLIED_CHEMICAL_BOND)
 		} else {
-			OPERATORS.get(as_text(mo_node))
+			lookup_operator(as_text(mo_node))
 		};
 		if found_op_info.is_none() {
 			// no known operator -- return the unknown operator with the correct "fix" type
@@ -2826,7 +3324,7 @@ impl CanonicalizeContext {
 		// if in a prefix location, it is a left fence
 		// note:  if there is an operator on the top of the stack, it wants an operand (otherwise it would have been reduced)
 		let operator_str = as_text(mo_node);
-		let found_op_info = OPERATORS.get(operator_str);
+		let found_op_info = lookup_operator(operator_str);
 		if found_op_info.is_none() {
 			return original_op;
 		}
@@ -3026,6 +3524,13 @@ impl CanonicalizeContext {
 			if likely_names.contains(base_name) {
 				return FunctionNameCertainty::True;	// don't bother checking contents of parens, consider these as function names
 			}
+
+			// "P(A|B)" and "E[X|Y]" -- the "|" is "given" (see determine_vertical_bar_op(), which makes an unmatched "|" infix)
+			if (defs.get_hashset("ProbabilityFunctionNames").unwrap().contains(base_name) ||
+			    defs.get_hashset("ExpectedValueFunctionNames").unwrap().contains(base_name)) &&
+			   is_conditional_arg(as_text(first_sibling), &right_siblings[1..]) {
+				return FunctionNameCertainty::True;
+			}
 	
 			if is_single_arg(as_text(first_sibling), &right_siblings[1..]) {
 				// debug!("      ...is single arg");
@@ -3103,6 +3608,23 @@ impl CanonicalizeContext {
 			return false;
 		}
 	
+		fn is_conditional_arg(open: &str, following_nodes: &[ChildOfElement]) -> bool {
+			// following_nodes are nodes after "("
+			// an odd number of "|"s before the close means one of them isn't part of an absolute value (e.g., "(A | |X| < 1)")
+			let mut n_vertical_bars = 0usize;
+			for child in following_nodes {
+				let child = as_element(*child);
+				if name(&child) == "mo" {
+					if as_text(child) == "|" {
+						n_vertical_bars += 1;
+					} else if is_matching_right_paren(open, child) {
+						return !n_vertical_bars.is_multiple_of(2);
+					}
+				}
+			}
+			return false;
+		}
+	
 		fn is_left_paren(node: Element) -> bool {
 			if name(&node) != "mo" {
 				return false;
@@ -3270,6 +3792,108 @@ impl CanonicalizeContext {
 		}
 	}
 	
+	/// Returns true if the operand on the top of the stack ends the bound variable(s) of a quantifier (e.g., "x ∈ S" in "∀x ∈ S, x > 0").
+	/// That is the case when there is a quantifier on the stack and everything above it has higher priority.
+	fn is_end_of_quantifier_binder(&self, parse_stack: &[StackInfo]) -> bool {
+		if !top(parse_stack).is_operand {
+			return false;
+		}
+		let mut min_priority = usize::MAX;
+		for stack_info in parse_stack.iter().rev() {
+			if QUANTIFIERS.contains(stack_info.op_pair.ch) && stack_info.op_pair.op.is_prefix() {
+				return stack_info.priority() < min_priority;
+			}
+			min_priority = min_priority.min(stack_info.priority());
+		}
+		return false;
+	}
+
+	/// Returns true if 'ch' is a "," that separates bound variables rather than ending the binder.
+	/// That is the case when it is followed by identifiers (separated by ","s), a relation, and then something that ends the binder
+	///   (e.g., the first "," in "∀x, y ∈ ℝ, x+y = y+x", but not the "," in "∃y, x < y").
+	fn is_bound_variable_separator(&self, ch: &str, following: &[ChildOfElement]) -> bool {
+		if ch != "," {
+			return false;
+		}
+		let mut expect_identifier = true;
+		for (i, &child) in following.iter().enumerate() {
+			let child = as_element(child);
+			if expect_identifier {
+				if name(&child) != "mi" {
+					return false;
+				}
+			} else if name(&child) != "mo" {
+				return false;
+			} else if as_text(child) != "," {
+				return self.is_or_has_relation(child) &&
+					following[i+1..].iter().any(|&child| {
+						let child = as_element(child);
+						name(&child) == "mo" && (QUANTIFIER_SEPARATORS.contains(as_text(child)) || QUANTIFIERS.contains(as_text(child)))
+					});
+			}
+			expect_identifier = !expect_identifier;
+		}
+		return false;
+	}
+
+	/// Returns true if 'ch' is the separator in set-builder notation (e.g., the "|" in "{x | x > 0, x < 5}").
+	/// That is the case for the first "|", "∣", or ":" directly inside of "{...}".
+	fn is_set_builder_separator(&self, ch: &str, parse_stack: &[StackInfo]) -> bool {
+		if !(ch == "|" || ch == "∣" || ch == ":") {
+			return false;
+		}
+		for stack_info in parse_stack.iter().rev() {
+			if ptr_eq(stack_info.op_pair.op, &*SET_BUILDER_SEPARATOR) {
+				return false;
+			}
+			if stack_info.op_pair.op.is_left_fence() {
+				return stack_info.op_pair.ch == "{";
+			}
+		}
+		return false;
+	}
+
+	/// Returns true if the children of an mrow look like a logical formula -- they contain a quantifier or an operator such as "¬" or "⇒".
+	/// "∧" and "∨" are also used for wedge products and lattices (e.g., "α∧β = -β∧α"),
+	///   so they only indicate a logical formula if there is a relation on both sides of them (e.g., "x > 0 ∧ x < 5").
+	fn is_logical_formula(&self, children: &[ChildOfElement]) -> bool {
+		let mut has_relation = false;					// since the last "∧" or "∨"
+		let mut has_relation_before_and_or = false;
+		for &child in children {
+			let child = as_element(child);
+			if name(&child) == "mo" {
+				let text = as_text(child);
+				if QUANTIFIERS.contains(text) || LOGICAL_OPERATORS.contains(text) {
+					return true;
+				}
+				if text == "∧" || text == "∨" {
+					has_relation_before_and_or = has_relation;
+					has_relation = false;
+					continue;
+				}
+			}
+			if self.is_or_has_relation(child) {
+				if has_relation_before_and_or {
+					return true;
+				}
+				has_relation = true;
+			}
+		}
+		return false;
+	}
+
+	/// Returns true if 'node' is a relational operator (e.g., "=", "<", "∈") or is an mrow with one at the top level
+	fn is_or_has_relation(&self, node: Element) -> bool {
+		if name(&node) == "mrow" {
+			return node.children().iter().any(|&child| name(&as_element(child)) == "mo" && self.is_or_has_relation(as_element(child)));
+		}
+		if name(&node) != "mo" {
+			return false;
+		}
+		let priority = self.find_operator(node, None, None, None).priority;
+		return (OPERATORS.get("∈").unwrap().priority..=*EQUAL_PRIORITY).contains(&priority);
+	}
+
 	// Add the current operator if it's not n-ary to the stack
 	// 'current_child' and it the operator to the stack.
 	fn shift_stack<'s, 'a:'s, 'op:'a>(
@@ -3474,6 +4098,7 @@ impl CanonicalizeContext {
 		let mut parse_stack = vec![StackInfo::new(mrow.document())];
 		let mut children = mrow.children();
 		let num_children = children.len();
+		let is_logical_formula = self.is_logical_formula(&children);
 	
 		for i_child in 0..num_children {
 			// debug!("\nDealing with child #{}: {}", i_child, mml_to_string(&as_element(children[i_child])));
@@ -3502,13 +4127,35 @@ impl CanonicalizeContext {
 					&mut parse_stack,
 					self.n_vertical_bars_on_right(&children[i_child+1..], current_op.ch)
 				);
+				// a scripted bar after an operand that doesn't close an earlier bar is an "evaluated at" bar
+				if current_op.ch == "|" && current_child != base_of_child && top(&parse_stack).is_operand &&
+				   self.n_vertical_bars_on_right(&children[..i_child], "|").is_multiple_of(2) {
+					current_op.op = &EVALUATED_AT;
+				}
+				if is_logical_formula {
+					current_op.op = logical_operator_info(current_op.ch, current_op.op);
+				}
+				if current_op.op.is_infix() && top(&parse_stack).is_operand {
+					if QUANTIFIER_SEPARATORS.contains(current_op.ch) && self.is_end_of_quantifier_binder(&parse_stack) {
+						current_op.op = if self.is_bound_variable_separator(current_op.ch, &children[i_child+1..]) {
+							&BOUND_VARIABLE_SEPARATOR
+						} else {
+							&QUANTIFIER_SCOPE
+						};
+					} else if self.is_set_builder_separator(current_op.ch, &parse_stack) {
+						current_op.op = &SET_BUILDER_SEPARATOR;
+					}
+				}
 			} else if top(&parse_stack).last_child_in_mrow().is_some() {
 				let previous_child = top(&parse_stack).last_child_in_mrow().unwrap();
 				let base_of_previous_child = get_possible_embellished_node(previous_child);
 				if name(&base_of_previous_child) != "mo" {
 					// consecutive operands -- add an invisible operator as appropriate
 					let likely_function_name = self.is_function_name(previous_child, Some(&children[i_child..]));
-					current_op = if likely_function_name == FunctionNameCertainty::True {
+					current_op = if QUANTIFIERS.contains(top(&parse_stack).op_pair.ch) && self.is_end_of_quantifier_binder(&parse_stack) {
+								// a single bound variable followed by the body (e.g., "∀x P(x)")
+								OperatorPair{ ch: "\u{2063}", op: &QUANTIFIER_SCOPE }
+							} else if likely_function_name == FunctionNameCertainty::True {
 								OperatorPair{ ch: "\u{2061}", op: &INVISIBLE_FUNCTION_APPLICATION }
 							} else if self.is_mixed_fraction(&previous_child, &children[i_child..])? {
 								OperatorPair{ ch: "\u{2064}", op: &IMPLIED_INVISIBLE_PLUS }
@@ -3532,9 +4179,10 @@ impl CanonicalizeContext {
 						self.reduce_stack(&mut parse_stack, current_op.op.priority);
 		
 						let implied_mo = create_mo(current_child.document(), current_op.ch, ADDED_ATTR_VALUE);
-						if likely_function_name == FunctionNameCertainty::Maybe {
+						if likely_function_name == FunctionNameCertainty::Maybe && !ptr_eq(current_op.op, &*QUANTIFIER_SCOPE) {
 							implied_mo.set_attribute_value("data-function-guess", "true");
 						}
+						report_invisible_operator(implied_mo, previous_child, current_child);
 						let shift_result = self.shift_stack(&mut parse_stack, implied_mo, current_op.clone());
 						// ignore shift_result.0 which is just 'implied_mo'
 						assert_eq!(implied_mo, shift_result.0);
@@ -3553,17 +4201,27 @@ impl CanonicalizeContext {
 						// will end up with operand operand -- need to choose operator associated with prev child
 						// we use the original input here because in this case, we need to look to the right of the ()s to deal with chemical states
 						let likely_function_name = self.is_function_name(as_element(children[i_child-1]), Some(&children[i_child..]));
-						let implied_operator = if likely_function_name== FunctionNameCertainty::True {
+						let implied_operator = if self.is_end_of_quantifier_binder(&parse_stack) {
+								// the body of a quantifier starts with a fence or prefix operator (e.g., "∀x (P(x) → Q(x))" or "∀x ∃y P(x,y)")
+								OperatorPair{ ch: "\u{2063}", op: &QUANTIFIER_SCOPE }
+							} else if QUANTIFIERS.contains(current_op.ch) {
+								// a quantifier after an expression qualifies it (e.g., "f(x) = 0 ∀x ∈ ℝ")
+								OperatorPair{ ch: "\u{2063}", op: &IMPLIED_INVISIBLE_COMMA }
+							} else if likely_function_name== FunctionNameCertainty::True {
 								OperatorPair{ ch: "\u{2061}", op: &INVISIBLE_FUNCTION_APPLICATION }
 							} else {
 								OperatorPair{ ch: "\u{2062}", op: &IMPLIED_TIMES }
 							};
+						if implied_operator.ch == "\u{2063}" {
+							self.reduce_stack(&mut parse_stack, implied_operator.op.priority);
+						}
 						// debug!("  adding implied {}", if ptr_eq(implied_operator.op,*IMPLIED_TIMES) {"times"} else {"function apply"});
 	
 						let implied_mo = create_mo(current_child.document(), implied_operator.ch, ADDED_ATTR_VALUE);
-						if likely_function_name == FunctionNameCertainty::Maybe {
+						if likely_function_name == FunctionNameCertainty::Maybe && implied_operator.ch != "\u{2063}" {
 							implied_mo.set_attribute_value("data-function-guess", "true");
 						}
+						report_invisible_operator(implied_mo, as_element(children[i_child-1]), current_child);
 						let shift_result = self.shift_stack(&mut parse_stack, implied_mo, implied_operator.clone());
 						// ignore shift_result.0 which is just 'implied_mo'
 						assert_eq!(implied_mo, shift_result.0);
@@ -3615,6 +4273,106 @@ impl CanonicalizeContext {
 }
 
 // ---------------- useful utility functions --------------------
+/// The operator info to use for 'op' (with text 'ch') when it is part of a logical formula (see `is_logical_formula`)
+fn logical_operator_info(ch: &str, op: &'static OperatorInfo) -> &'static OperatorInfo {
+	if op.is_prefix() {
+		return if ch == "¬" || ch == "∼" {&LOGICAL_NOT} else {op};
+	} else if !op.is_infix() {
+		return op;
+	}
+	return match ch {
+		"∧" | "⊼" => &LOGICAL_AND,
+		"∨" | "⊻" | "⊽" => &LOGICAL_OR,
+		"⇒" | "⇐" | "⟹" | "⟸" | "→" => &LOGICAL_IMPLIES,
+		"⇔" | "⟺" | "↔" => &LOGICAL_IFF,
+		"⊢" | "⊨" | "⊩" | "⊬" | "⊭" => &SEQUENT,
+		_ => op,
+	};
+}
+
+/// Look up 'text' in the operator dictionary: the `OperatorDictionary` definitions take precedence over the built-in dictionary
+fn lookup_operator(text: &str) -> Option<&'static OperatorInfo> {
+	let found = OPERATOR_OVERRIDES.with(|overrides| overrides.borrow().operators.get(text).copied());
+	return found.or_else(|| OPERATORS.get(text));
+}
+
+/// Gather the `OperatorDictionary` definitions (entries in `OperatorDictionary{subject_area}` override those in `OperatorDictionary`)
+/// and rebuild the operator overrides if they changed.
+fn update_operator_overrides(subject_area: &str) {
+	let definitions = crate::definitions::DEFINITIONS.with(|definitions| {
+		let definitions = definitions.borrow();
+		let mut merged = HashMap::new();
+		for name in ["OperatorDictionary".to_string(), format!("OperatorDictionary{}", subject_area)] {
+			if let Some(entries) = definitions.get_hashmap(&name) {
+				merged.extend(entries.iter().map(|(op, value)| (op.clone(), value.clone())));
+			}
+		}
+		return merged;
+	});
+	OPERATOR_OVERRIDES.with(|overrides| {
+		let mut overrides = overrides.borrow_mut();
+		if overrides.definitions == definitions {
+			return;
+		}
+		let mut operators = HashMap::with_capacity(definitions.len());
+		for (op, value) in &definitions {
+			match parse_operator_entry(value) {
+				Ok(forms) => {
+					let info = *overrides.interned.entry(forms).or_insert_with_key(|forms| build_operator_info(forms));
+					operators.insert(op.clone(), info);
+				},
+				Err(e) => error!("OperatorDictionary entry for '{}': {}", op, e),		// shouldn't happen -- checked when read in
+			}
+		}
+		overrides.definitions = definitions;
+		overrides.operators = operators;
+	});
+
+	fn build_operator_info(forms: &[(OperatorTypes, usize)]) -> &'static OperatorInfo {
+		let mut next: &'static Option<OperatorInfo> = Box::leak(Box::new(None));
+		for &(op_type, priority) in forms.iter().rev() {
+			next = Box::leak(Box::new( Some(OperatorInfo{ op_type, priority, next }) ));
+		}
+		return next.as_ref().unwrap();
+	}
+}
+
+/// Check that 'value' is a legal `OperatorDictionary` value (see [`parse_operator_entry`])
+pub fn check_operator_entry(value: &str) -> std::result::Result<(), String> {
+	return parse_operator_entry(value).map(|_| ());
+}
+
+/// Parse an `OperatorDictionary` value such as "infix 280, prefix 690": a comma separated list of up to three forms
+/// (`prefix`, `infix`, `postfix`, `left-fence`, or `right-fence`), each followed by its priority.
+/// The first form is used when the position of the operator doesn't pick one of the others.
+fn parse_operator_entry(value: &str) -> std::result::Result<Vec<(OperatorTypes, usize)>, String> {
+	let mut forms = Vec::with_capacity(3);
+	for form in value.split(',') {
+		let mut parts = form.split_whitespace();
+		let (Some(form_name), Some(priority), None) = (parts.next(), parts.next(), parts.next()) else {
+			return Err( format!("'{}' should be a form followed by a priority (e.g., 'infix 280')", form.trim()) );
+		};
+		let op_type = match form_name {
+			"prefix" => OperatorTypes::PREFIX,
+			"infix" => OperatorTypes::INFIX,
+			"postfix" => OperatorTypes::POSTFIX,
+			"left-fence" => OperatorTypes::LEFT_FENCE,
+			"right-fence" => OperatorTypes::RIGHT_FENCE,
+			_ => return Err( format!("unknown form '{}' (should be one of prefix, infix, postfix, left-fence, or right-fence)", form_name) ),
+		};
+		let priority = priority.parse::<usize>()
+				.map_err(|_| format!("priority '{}' for '{}' is not a non-negative integer", priority, form_name))?;
+		if forms.iter().any(|&(previous, _)| previous == op_type) {
+			return Err( format!("form '{}' is given more than once", form_name) );
+		}
+		forms.push( (op_type, priority) );
+	}
+	if forms.len() > 3 {
+		return Err( format!("at most three forms are allowed, found {}", forms.len()) );
+	}
+	return Ok(forms);
+}
+
 fn top<'s, 'a:'s, 'op:'a>(vec: &'s[StackInfo<'a, 'op>]) -> &'s StackInfo<'a, 'op> {
 	return &vec[vec.len()-1];
 }
@@ -3638,7 +4396,13 @@ pub fn add_attrs<'a>(mathml: Element<'a>, attrs: Vec<Attribute>) -> Element<'a>
 
 	// add in 'attrs'
 	for attr in attrs {
-		mathml.set_attribute_value(attr.name(), attr.value());
+		if attr.name().local_part() == DIAGNOSTIC_ATTR && mathml.attribute(DIAGNOSTIC_ATTR).is_some() {
+			// keep the diagnostics for both elements
+			let diagnostics = mathml.attribute_value(DIAGNOSTIC_ATTR).unwrap().to_string() + " " + attr.value();
+			mathml.set_attribute_value(DIAGNOSTIC_ATTR, &diagnostics);
+		} else {
+			mathml.set_attribute_value(attr.name(), attr.value());
+		}
 	}
 	return mathml;
 }
@@ -3674,7 +4438,6 @@ pub fn as_text(leaf_child: Element) -> &str {
 	}
 }
 
-#[allow(dead_code)] // for debugging with println
 fn element_summary(mathml: Element) -> String {
 	return format!("{}<{}>", name(&mathml),
 	              if is_leaf(mathml) {show_invisible_op_char(as_text(mathml)).to_string()}
@@ -3706,7 +4469,25 @@ pub fn get_possible_embellished_node(node: Element) -> Element {
 	return node;
 }		
 
-#[allow(dead_code)] // for debugging with println
+/// Record that the leaf 'mathml' is being changed to 'new_name'
+fn report_retag(mathml: Element, new_name: &str) {
+	report(mathml, DiagnosticKind::LeafRetagged, Severity::Warning,
+		   format!("'{}' was changed from '{}' to '{}'", as_text(mathml), name(&mathml), new_name));
+}
+
+/// Record that the invisible operator 'mo' was added between 'left' and 'right'
+fn report_invisible_operator(mo: Element, left: Element, right: Element) {
+	let operator_name = match as_text(mo) {
+		"\u{2061}" => "function application",
+		"\u{2062}" => "times",
+		"\u{2063}" => "separator",
+		"\u{2064}" => "plus",
+		_ => "operator",
+	};
+	report(mo, DiagnosticKind::InvisibleOperatorAdded, Severity::Info,
+		   format!("invisible {} was added between {} and {}", operator_name, element_summary(left), element_summary(right)));
+}
+
 fn show_invisible_op_char(ch: &str) -> &str {
 	return match ch.chars().next().unwrap() {
 		'\u{2061}' => "&#x2061;",
@@ -4342,18 +5123,203 @@ mod canonicalize_tests {
     }
 
     #[test]
-    fn no_implied_comma() {
-        let test_str = "<math><mfrac><mi>b</mi><mrow><mn>1</mn><mn>2</mn></mrow></mfrac></math>";
+    fn integral_differential() {
+        let test_str = "<math><mo>∫</mo><msup><mi>x</mi><mn>2</mn></msup><mi>d</mi><mi>x</mi></math>";
         let target_str = "<math>
-			 <mfrac><mi>b</mi><mrow><mn>1</mn><mo data-changed='added'>&#x2062;</mo><mn>2</mn></mrow></mfrac>
+			<mrow data-changed='added'>
+				<mo>∫</mo>
+				<mrow data-changed='added'>
+					<msup><mi>x</mi><mn>2</mn></msup>
+					<mo data-changed='added'>&#x2062;</mo>
+					<mrow data-changed='added'><mi>d</mi><mo data-changed='added'>&#x2062;</mo><mi>x</mi></mrow>
+				</mrow>
+			</mrow>
 		</math>";
         assert!(are_strs_canonically_equal(test_str, target_str));
     }
 
     #[test]
-    fn vertical_bars() {
-        let test_str = "<math>
-		<mo>|</mo> <mi>x</mi> <mo>|</mo><mo>+</mo><mo>|</mo>
+    fn partial_differentials() {
+        let test_str = "<math><mrow><mo>∂</mo><mi>x</mi><mo>∂</mo><mi>y</mi></mrow></math>";
+        let target_str = "<math>
+			<mrow>
+				<mrow data-changed='added'><mo>∂</mo><mi>x</mi></mrow>
+				<mo data-changed='added'>&#x2062;</mo>
+				<mrow data-changed='added'><mo>∂</mo><mi>y</mi></mrow>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn d_not_differential() {
+        // without an integral, 'd' is just a variable
+        let test_str = "<math><mi>a</mi><mi>d</mi><mi>x</mi></math>";
+        let target_str = "<math>
+			<mrow data-changed='added'>
+				<mi>a</mi><mo data-changed='added'>&#x2062;</mo><mi>d</mi><mo data-changed='added'>&#x2062;</mo><mi>x</mi>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    /// Set the 'name' definition (e.g., "OperatorDictionary") to 'entries' (after forcing the definitions to be read)
+    fn set_operator_dictionary(name: &str, entries: &[(&str, &str)]) {
+        use std::rc::Rc;
+        use crate::definitions::{DEFINITIONS, Contains};
+        crate::interface::set_rules_dir(abs_rules_dir_path()).unwrap();
+        crate::interface::set_preference("DecimalSeparators".to_string(), ".".to_string()).unwrap();
+        let map = entries.iter().map(|&(op, value)| (op.to_string(), value.to_string())).collect::<HashMap<String, String>>();
+        DEFINITIONS.with(|definitions| {
+            definitions.borrow_mut().name_to_var_mapping.insert(name.to_string(), Contains::Map( Rc::new( RefCell::new(map) ) ));
+        });
+    }
+
+    #[test]
+    fn operator_dictionary_override() {
+        // make '⊕' lower priority than '='
+        set_operator_dictionary("OperatorDictionary", &[("⊕", "infix 100")]);
+        let test_str = "<math><mi>x</mi><mo>⊕</mo><mi>y</mi><mo>=</mo><mi>z</mi></math>";
+        let target_str = "<math>
+			<mrow data-changed='added'>
+				<mi>x</mi>
+				<mo>⊕</mo>
+				<mrow data-changed='added'><mi>y</mi><mo>=</mo><mi>z</mi></mrow>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn operator_dictionary_new_prefix_operator() {
+        // '⫯' isn't in the built-in dictionary; making it a prefix operator binds it tightly to 'x'
+        set_operator_dictionary("OperatorDictionary", &[("⫯", "prefix 800, infix 100")]);
+        let test_str = "<math><mn>2</mn><mo>+</mo><mo>⫯</mo><mi>x</mi></math>";
+        let target_str = "<math>
+			<mrow data-changed='added'>
+				<mn>2</mn>
+				<mo>+</mo>
+				<mrow data-changed='added'><mo>⫯</mo><mi>x</mi></mrow>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn operator_dictionary_subject_area() {
+        // the subject area's entries are merged over the general ones
+        set_operator_dictionary("OperatorDictionary", &[("⊕", "infix 100")]);
+        set_operator_dictionary("OperatorDictionaryCalculus", &[("⊕", "infix 400")]);
+        crate::interface::set_preference("SubjectArea".to_string(), "Calculus".to_string()).unwrap();
+        let test_str = "<math><mi>x</mi><mo>⊕</mo><mi>y</mi><mo>=</mo><mi>z</mi></math>";
+        let target_str = "<math>
+			<mrow data-changed='added'>
+				<mrow data-changed='added'><mi>x</mi><mo>⊕</mo><mi>y</mi></mrow>
+				<mo>=</mo>
+				<mi>z</mi>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn operator_dictionary_reuses_entries() {
+        // switching between dictionaries shouldn't build (leak) new entries for values that were seen before
+        set_operator_dictionary("OperatorDictionary", &[("⊕", "infix 100")]);
+        update_operator_overrides("");
+        let first = lookup_operator("⊕").unwrap();
+        set_operator_dictionary("OperatorDictionary", &[("⊕", "infix 400")]);
+        update_operator_overrides("");
+        assert_eq!(lookup_operator("⊕").unwrap().priority, 400);
+        set_operator_dictionary("OperatorDictionary", &[("⊕", "infix 100"), ("⊗", "infix 100")]);
+        update_operator_overrides("");
+        assert!(std::ptr::eq(first, lookup_operator("⊕").unwrap()));
+        assert!(std::ptr::eq(first, lookup_operator("⊗").unwrap()));
+    }
+
+    #[test]
+    fn operator_dictionary_entries() {
+        assert!(check_operator_entry("infix 280, prefix 690").is_ok());
+        assert!(check_operator_entry("left-fence 20").is_ok());
+        assert!(check_operator_entry("infix").is_err());
+        assert!(check_operator_entry("infix -3").is_err());
+        assert!(check_operator_entry("circumfix 20").is_err());
+        assert!(check_operator_entry("infix 280, infix 300").is_err());
+    }
+
+    #[test]
+    fn units_after_number() {
+        let test_str = "<math><mn>9.8</mn><mi mathvariant='normal'>m</mi><mo>/</mo><msup><mi mathvariant='normal'>s</mi><mn>2</mn></msup></math>";
+        let target_str = "<math>
+			<mrow data-changed='added'>
+				<mn>9.8</mn>
+				<mo data-changed='added'>&#x2062;</mo>
+				<mrow data-changed='added'>
+					<mi mathvariant='normal' data-unit='m'>m</mi>
+					<mo>/</mo>
+					<msup><mi mathvariant='normal' data-unit='s'>s</mi><mn>2</mn></msup>
+				</mrow>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn units_degrees() {
+        let test_str = "<math><msup><mn>25</mn><mo>°</mo></msup><mi mathvariant='normal'>C</mi></math>";
+        let target_str = "<math>
+			<mrow data-changed='added'>
+				<msup><mn>25</mn><mo>°</mo></msup>
+				<mo data-changed='added'>&#x2062;</mo>
+				<mi mathvariant='normal' data-unit='°C'>C</mi>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn units_not_italic_var() {
+        // an italic 'm' is a variable, not meters
+        let test_str = "<math><mn>3</mn><mi>m</mi></math>";
+        let target_str = "<math>
+			<mrow data-changed='added'>
+				<mn>3</mn><mo data-changed='added'>&#x2062;</mo><mi>m</mi>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn evaluated_at_bar() {
+        // the bar applies to the right hand side, not the whole equation
+        let test_str = "<math><mi>y</mi><mo>=</mo><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mi>x</mi>
+                <msubsup><mo>|</mo><mn>0</mn><mn>1</mn></msubsup></math>";
+        let target_str = "<math>
+			<mrow data-changed='added'>
+				<mi>y</mi>
+				<mo>=</mo>
+				<mrow data-changed='added'>
+					<mrow data-changed='added'><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mi>x</mi></mrow>
+					<msubsup><mo>|</mo><mn>0</mn><mn>1</mn></msubsup>
+				</mrow>
+			</mrow>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn no_implied_comma() {
+        let test_str = "<math><mfrac><mi>b</mi><mrow><mn>1</mn><mn>2</mn></mrow></mfrac></math>";
+        let target_str = "<math>
+			 <mfrac><mi>b</mi><mrow><mn>1</mn><mo data-changed='added'>&#x2062;</mo><mn>2</mn></mrow></mfrac>
+		</math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn vertical_bars() {
+        let test_str = "<math>
+		<mo>|</mo> <mi>x</mi> <mo>|</mo><mo>+</mo><mo>|</mo>
 		 <mi>a</mi><mo>+</mo><mn>1</mn> <mo>|</mo>
 	  </math>";
 	  let target_str = " <math>
@@ -4456,6 +5422,221 @@ mod canonicalize_tests {
         assert!(are_strs_canonically_equal(test_str, target_str));
     }
 
+    #[test]
+    fn set_builder_with_conditions() {
+        let test_str = "<math>
+				<mo>{</mo><mi>x</mi><mo>:</mo><mi>x</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mi>x</mi><mo>&lt;</mo><mn>5</mn><mo>}</mo>
+            </math>";
+        let target_str = "<math>
+		<mrow data-changed='added'>
+		  <mo>{</mo>
+		  <mrow data-changed='added'>
+			<mi>x</mi>
+			<mo>:</mo>
+			<mrow data-changed='added'>
+			  <mrow data-changed='added'>
+				<mi>x</mi>
+				<mo>&gt;</mo>
+				<mn>0</mn>
+			  </mrow>
+			  <mo>,</mo>
+			  <mrow data-changed='added'>
+				<mi>x</mi>
+				<mo>&lt;</mo>
+				<mn>5</mn>
+			  </mrow>
+			</mrow>
+		  </mrow>
+		  <mo>}</mo>
+		</mrow>
+	   </math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn quantifier_scope() {
+        let test_str = "<math>
+				<mo>∀</mo><mi>x</mi><mo>∈</mo><mi>S</mi><mo>,</mo><mo>∃</mo><mi>y</mi><mo>,</mo><mi>x</mi><mo>&lt;</mo><mi>y</mi>
+            </math>";
+        let target_str = "<math>
+		<mrow data-changed='added'>
+		  <mrow data-changed='added'>
+			<mo>∀</mo>
+			<mrow data-changed='added'>
+			  <mi>x</mi>
+			  <mo>∈</mo>
+			  <mi>S</mi>
+			</mrow>
+		  </mrow>
+		  <mo>,</mo>
+		  <mrow data-changed='added'>
+			<mrow data-changed='added'>
+			  <mo>∃</mo>
+			  <mi>y</mi>
+			</mrow>
+			<mo>,</mo>
+			<mrow data-changed='added'>
+			  <mi>x</mi>
+			  <mo>&lt;</mo>
+			  <mi>y</mi>
+			</mrow>
+		  </mrow>
+		</mrow>
+	   </math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn quantifier_scope_multiple_variables() {
+        let test_str = "<math>
+				<mo>∀</mo><mi>x</mi><mo>,</mo><mi>y</mi><mo>∈</mo><mi>ℝ</mi><mo>,</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>=</mo><mi>y</mi><mo>+</mo><mi>x</mi>
+            </math>";
+        let target_str = "<math>
+		<mrow data-changed='added'>
+		  <mrow data-changed='added'>
+			<mo>∀</mo>
+			<mrow data-changed='added'>
+			  <mrow data-changed='added'>
+				<mi>x</mi>
+				<mo>,</mo>
+				<mi>y</mi>
+			  </mrow>
+			  <mo>∈</mo>
+			  <mi>ℝ</mi>
+			</mrow>
+		  </mrow>
+		  <mo>,</mo>
+		  <mrow data-changed='added'>
+			<mrow data-changed='added'>
+			  <mi>x</mi>
+			  <mo>+</mo>
+			  <mi>y</mi>
+			</mrow>
+			<mo>=</mo>
+			<mrow data-changed='added'>
+			  <mi>y</mi>
+			  <mo>+</mo>
+			  <mi>x</mi>
+			</mrow>
+		  </mrow>
+		</mrow>
+	   </math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn quantifier_implied_scope() {
+        let test_str = "<math>
+				<mo>∃</mo><mo>!</mo><mi>x</mi><mo>(</mo><mi>P</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>)</mo>
+            </math>";
+        let target_str = "<math>
+		<mrow data-changed='added'>
+		  <mrow data-changed='added'>
+			<mo>∃!</mo>
+			<mi>x</mi>
+		  </mrow>
+		  <mo data-changed='added'>&#x2063;</mo>
+		  <mrow data-changed='added'>
+			<mo>(</mo>
+			<mrow data-changed='added'>
+			  <mi>P</mi>
+			  <mo data-changed='added'>&#x2061;</mo>
+			  <mrow data-changed='added'>
+				<mo>(</mo>
+				<mi>x</mi>
+				<mo>)</mo>
+			  </mrow>
+			</mrow>
+			<mo>)</mo>
+		  </mrow>
+		</mrow>
+	   </math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn logical_connectives() {
+        let test_str = "<math>
+				<mo>¬</mo><mi>x</mi><mo>&gt;</mo><mn>0</mn><mo>∨</mo><mi>x</mi><mo>&lt;</mo><mn>5</mn><mo>⇒</mo><mi>x</mi><mo>≠</mo><mn>0</mn>
+            </math>";
+        let target_str = "<math>
+		<mrow data-changed='added'>
+		  <mrow data-changed='added'>
+			<mrow data-changed='added'>
+			  <mo>¬</mo>
+			  <mrow data-changed='added'>
+				<mi>x</mi>
+				<mo>&gt;</mo>
+				<mn>0</mn>
+			  </mrow>
+			</mrow>
+			<mo>∨</mo>
+			<mrow data-changed='added'>
+			  <mi>x</mi>
+			  <mo>&lt;</mo>
+			  <mn>5</mn>
+			</mrow>
+		  </mrow>
+		  <mo>⇒</mo>
+		  <mrow data-changed='added'>
+			<mi>x</mi>
+			<mo>≠</mo>
+			<mn>0</mn>
+		  </mrow>
+		</mrow>
+	   </math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn wedge_product_is_not_logical() {
+        let test_str = "<math>
+				<mi>α</mi><mo>∧</mo><mi>β</mi><mo>=</mo><mo>-</mo><mi>β</mi><mo>∧</mo><mi>α</mi>
+            </math>";
+        let target_str = "<math>
+		<mrow data-changed='added'>
+		  <mrow data-changed='added'>
+			<mi>α</mi>
+			<mo>∧</mo>
+			<mi>β</mi>
+		  </mrow>
+		  <mo>=</mo>
+		  <mrow data-changed='added'>
+			<mrow data-changed='added'>
+			  <mo>-</mo>
+			  <mi>β</mi>
+			</mrow>
+			<mo>∧</mo>
+			<mi>α</mi>
+		  </mrow>
+		</mrow>
+	   </math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
+    #[test]
+    fn sequent() {
+        let test_str = "<math>
+				<mi>Γ</mi><mo>,</mo><mi>φ</mi><mo>⊢</mo><mi>ψ</mi><mo>,</mo><mi>Δ</mi>
+            </math>";
+        let target_str = "<math>
+		<mrow data-changed='added'>
+		  <mrow data-changed='added'>
+			<mi>Γ</mi>
+			<mo>,</mo>
+			<mi>φ</mi>
+		  </mrow>
+		  <mo>⊢</mo>
+		  <mrow data-changed='added'>
+			<mi>ψ</mi>
+			<mo>,</mo>
+			<mi>Δ</mi>
+		  </mrow>
+		</mrow>
+	   </math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+    }
+
     #[test]
 	#[ignore]  // need to figure out a test for this ("|" should have a precedence around ":" since that is an alternative notation for "such that", but "∣" is higher precedence)
     fn vertical_bar_divides() {
@@ -5373,6 +6554,47 @@ mod canonicalize_tests {
 	}
 
 
+	#[test]
+    fn mstack_columns() {
+        let test_str = "<math><mstack>
+				<mscarries><none/><mn>1</mn></mscarries>
+				<mn>178</mn>
+				<msrow><mo>+</mo><mn>46</mn></msrow>
+				<msline/>
+				<mn>224</mn>
+			</mstack></math>";
+        let target_str = "<math><mstack>
+				<mscarries><mscarry data-changed='added' data-column='2'><none/></mscarry><mscarry data-changed='added' data-column='3'><mn>1</mn></mscarry></mscarries>
+				<msrow data-changed='added'><mn data-column='1'>1</mn><mn data-column='2'>7</mn><mn data-column='3'>8</mn></msrow>
+				<msrow><mo>+</mo><mn data-column='2'>4</mn><mn data-column='3'>6</mn></msrow>
+				<msline/>
+				<msrow data-changed='added'><mn data-column='1'>2</mn><mn data-column='2'>2</mn><mn data-column='3'>4</mn></msrow>
+			</mstack></math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+	}
+
+	#[test]
+    fn mlongdiv_msgroup_columns() {
+        let test_str = "<math><mlongdiv>
+				<mn>3</mn>
+				<mn>45</mn>
+				<mn>135</mn>
+				<msgroup position='1' shift='-1'>
+					<msgroup><mn>12</mn><msline/></msgroup>
+					<msgroup><mn>15</mn></msgroup>
+				</msgroup>
+			</mlongdiv></math>";
+        let target_str = "<math><mlongdiv>
+				<mn>3</mn>
+				<msrow data-changed='added'><mn data-column='2'>4</mn><mn data-column='3'>5</mn></msrow>
+				<msrow data-changed='added'><mn data-column='1'>1</mn><mn data-column='2'>3</mn><mn data-column='3'>5</mn></msrow>
+				<msrow data-changed='added' position='1'><mn data-column='1'>1</mn><mn data-column='2'>2</mn></msrow>
+				<msline position='1'/>
+				<msrow data-changed='added'><mn data-column='2'>1</mn><mn data-column='3'>5</mn></msrow>
+			</mlongdiv></math>";
+        assert!(are_strs_canonically_equal(test_str, target_str));
+	}
+
 	#[test]
 	#[ignore]	// this fails -- need to figure out grabbing base from previous or next child
     fn tensor() {
@@ -42,11 +42,15 @@ use crate::pretty_print::mml_to_string;
 use crate::xpath_functions::is_leaf;
 use regex::Regex;
 use crate::xpath_functions::IsBracketed;
+use crate::infer_intent::has_intent_property;
 use phf::{phf_map, phf_set};
 use std::convert::TryInto;
 use std::collections::HashSet;
 use std::cmp::Ordering;
 use crate::errors::*;
+use sxd_xpath::{Value, context, nodeset::Node};
+use sxd_xpath::function::{Function, Args, Error as XPathError};
+use std::result::Result as StdResult;
 
 
 pub static NOT_CHEMISTRY: isize = -10000;  // should overwhelm any positive signal
@@ -83,13 +87,8 @@ static CHEM_EQUATION_ARROWS: phf::Set<char> = phf_set! {
 
 
 pub fn is_chemistry_off(mathml: Element) -> bool {
-    lazy_static! {
-        static ref INTENT_STRUCTURE: Regex = Regex::new(r":structure([ \t\n:(]|$)").unwrap(); 
-    }
-    if let Some(intent) = mathml.attribute_value("intent") {
-        if INTENT_STRUCTURE.is_match(intent) {
-            return true;
-        }
+    if has_intent_property(mathml, "structure") {
+        return true;
     }
     let pref_manager = crate::prefs::PreferenceManager::get();
     return pref_manager.borrow().pref_to_string("Chemistry") == "Off";
@@ -326,6 +325,7 @@ pub fn convert_leaves_to_chem_elements(mathml: Element) -> Option<Vec<Element>>
             leaf.set_text(as_text(chem_element));
             leaf.set_attribute_value(MAYBE_CHEMISTRY, chem_element.attribute_value(MAYBE_CHEMISTRY).unwrap());
             leaf.set_attribute_value(MERGED_TOKEN, "true");
+            crate::source_map::merge_sources(leaf, &[second_element]);
             second_element.remove_from_parent();
             return Some(vec![chem_element]);
         }
@@ -586,6 +586,7 @@ fn is_changed_after_unmarking_chemistry(mathml: Element) -> bool {
         mathml.set_text(&merged_text);
         mathml.remove_attribute("mathvariant");
         mathml.remove_attribute(ADDED_ATTR_VALUE);
+        crate::source_map::merge_sources(mathml, &[start_token]);
         start_token.remove_from_parent();
         return Ok( () );
     }
@@ -1556,6 +1557,84 @@ pub fn is_chemical_element(node: Element) -> bool {
 }
 
 
+/// XPath function that returns the text of a chemical formula in the intent tree (e.g., "H2SO4", "Ca(OH)2", "NH4^+").
+/// Subscripts are written as is and superscripts are preceded by a "^".
+/// This is used as the key for looking up compound names (see `ChemicalCompounds` in the definitions files).
+/// A state at the end (e.g., "(aq)") is ignored.
+/// If the node is not a formula made up of elements, parens, bonds, and scripts (e.g., it has a coefficient), "" is returned.
+pub struct ChemicalFormulaText;
+impl ChemicalFormulaText {
+    fn formula_text(mathml: Element) -> Option<String> {
+        return match name(&mathml) {
+            "chemical-element" | "chemical-formula-operator" | "mn" => Some(ChemicalFormulaText::text(mathml).to_string()),
+            "mo" => match ChemicalFormulaText::text(mathml) {
+                "\u{2063}" => Some("".to_string()),      // invisible separator between elements
+                text @ ("(" | ")" | "[" | "]") => Some(text.to_string()),
+                _ => None,
+            },
+            "mrow" => {
+                // a state at the end (e.g, "(aq)") isn't part of the name of the compound
+                let mut children = mathml.children();
+                if children.len() > 1 && name(&as_element(children[children.len()-1])) == "chemical-state" {
+                    children.pop();
+                }
+                let mut result = String::new();
+                for child in children {
+                    result += &ChemicalFormulaText::formula_text(as_element(child))?;
+                }
+                Some(result)
+            },
+            "chemical-formula" => {
+                // the first child is the name of the presentation element it came from
+                let children = mathml.children();
+                let mut result = ChemicalFormulaText::formula_text(as_element(children[1]))?;
+                match (ChemicalFormulaText::text(as_element(children[0])), children.len()) {
+                    ("msub", 3) => result += &ChemicalFormulaText::formula_text(as_element(children[2]))?,
+                    ("msup", 3) => result = result + "^" + &ChemicalFormulaText::formula_text(as_element(children[2]))?,
+                    ("msubsup", 4) => {
+                        result += &ChemicalFormulaText::formula_text(as_element(children[2]))?;
+                        result = result + "^" + &ChemicalFormulaText::formula_text(as_element(children[3]))?;
+                    },
+                    _ => return None,
+                }
+                Some(result)
+            },
+            _ => None,
+        };
+    }
+
+    // intent names such as "chemical-element" aren't MathML leaves, so 'as_text' can't be used
+    fn text<'a>(mathml: Element<'a>) -> &'a str {
+        return match mathml.children().as_slice() {
+            [ChildOfElement::Text(text)] => text.text(),
+            _ => "",
+        };
+    }
+}
+
+impl Function for ChemicalFormulaText {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> StdResult<Value<'d>, XPathError>
+    {
+        let mut args = Args(args);
+        args.exactly(1)?;
+        let node = crate::xpath_functions::validate_one_node(args.pop_nodeset()?, "ChemicalFormulaText")?;
+        if let Node::Element(e) = node {
+            // a lone element isn't a compound
+            let is_formula = name(&e) == "chemical-formula" || (name(&e) == "mrow" && e.children().len() > 1);
+            if is_formula {
+                if let Some(text) = ChemicalFormulaText::formula_text(e) {
+                    return Ok( Value::String(text) );
+                }
+            }
+        }
+        return Ok( Value::String("".to_string()) );
+    }
+}
+
+
 #[cfg(test)]
 mod chem_tests {
 	#[allow(unused_imports)]
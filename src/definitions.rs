@@ -16,7 +16,10 @@
 //! //! Note: some of the variable are `vec`s and some are `hashset`s.
 //! Numbers are typically vectors so that indexing a digit is easy.
 //! Others such a `functions_names` are a hashset because you just want to know if an `mi` is a known name or not.
-//! The functions `as_vec` and `as_hashset` should be used on the appropriate variable.
+//! Definitions whose value is a dictionary (e.g., `ChemicalCompounds`) are a hashmap so that a value can be looked up.
+//! The functions `get_vec`, `get_hashset`, and `get_hashmap` should be used on the appropriate variable.
+//!
+//! A definitions file can include another file with `include: file_name`. This is useful for large lists such as the compound names.
 //! ## Names
 //! The names of "variables" in the definition files use camel case (e.g., "FunctionNames"). In the code, to fit with rust
 //! naming conventions, snake case is used (e.g, "function_names"). 
@@ -49,6 +52,7 @@ use crate::shim_filesystem::read_to_string_shim;
 pub enum Contains {
     Vec(Rc<RefCell<Vec<String>>>),
     Set(Rc<RefCell<HashSet<String>>>),
+    Map(Rc<RefCell<HashMap<String, String>>>),
 }
 
 impl Contains {
@@ -100,22 +104,24 @@ impl Definitions {
     pub fn get_hashset(&self, name: &str) -> Option<Ref<HashSet<String>>> {
         let names = self.name_to_var_mapping.get(name);
         return match names {
-            None => None,
-            Some(contains) => match contains {
-                Contains::Vec(_) => None,
-                Contains::Set(hashset) => Some(hashset.borrow()),
-            }
+            Some(Contains::Set(hashset)) => Some(hashset.borrow()),
+            _ => None,
         }
     }
 
     pub fn get_vec(&self, name: &str) -> Option<Ref<Vec<String>>> {
         let names = self.name_to_var_mapping.get(name);
         return match names {
-            None => None,
-            Some(contains) => match contains {
-                Contains::Vec(v) => Some(v.borrow()),
-                Contains::Set(_) => None,
-            }
+            Some(Contains::Vec(v)) => Some(v.borrow()),
+            _ => None,
+        }
+    }
+
+    pub fn get_hashmap(&self, name: &str) -> Option<Ref<'_, HashMap<String, String>>> {
+        let names = self.name_to_var_mapping.get(name);
+        return match names {
+            Some(Contains::Map(map)) => Some(map.borrow()),
+            _ => None,
         }
     }
 }
@@ -178,7 +184,7 @@ fn verify_definitions() -> Result<()> {
     //   numbers-plural, which should have a single entry
     lazy_static! {
         static ref USED_SETS: Vec<&'static str> = vec!["TrigFunctionNames", "AdditionalFunctionNames", "LikelyFunctionNames", 
-                                "LargeOperators"];
+                                "LargeOperators", "SIPrefixes", "SIPrefixableUnits", "UnitsWithoutPrefixes"];
         static ref USED_VECTORS: Vec<&'static str> = vec![
                 "NumbersHundreds", "NumbersTens", "NumbersOnes",
                 "NumbersOrdinalPluralLarge", "NumbersOrdinalLarge", "NumbersLarge",
@@ -204,6 +210,18 @@ fn verify_definitions() -> Result<()> {
             }
         }
         for (name,collection) in name_definition_map.iter() {
+            if name.starts_with("OperatorDictionary") {
+                match collection {
+                    Contains::Map(map) => {
+                        for (op, value) in map.borrow().iter() {
+                            if let Err(e) = crate::canonicalize::check_operator_entry(value) {
+                                bail!("In {}, the entry for '{}' is not valid: {}", name, op, e);
+                            }
+                        }
+                    },
+                    _ =>  bail!("{} is not a dictionary!", name),
+                }
+            }
             if name.contains("number") && !name.contains("fraction") {
                 match collection {
                     Contains::Vec(v) => {
@@ -233,6 +251,10 @@ fn read_one_definitions_file(path: &Path) -> Result<()> {
         let vec = crate::speech::as_vec_checked(variable_def_list)
                     .chain_err(||format!("in file {:?}", path.to_str()))?;
         for variable_def in vec {
+            if let Some(include_file_name) = variable_def["include"].as_str() {
+                crate::speech::process_include(path, include_file_name, read_one_definitions_file)?;
+                continue;
+            }
             build_values(variable_def).chain_err(||format!("in file {:?}", path.to_str()))?;
         }
         return Ok(());
@@ -252,6 +274,9 @@ fn build_values(definition: &Yaml) -> Result<()> {
     }
     let (key, value) = dictionary.iter().next().unwrap();
     let name = key.as_str().ok_or_else(|| format!("definition list name '{}' is not a string", yaml_to_type(key)))?;
+    if let Some(entries) = value.as_hash() {
+        return build_map(name, entries);
+    }
     let values = value.as_vec().ok_or_else(|| format!("definition list value '{}' is not an array", yaml_to_type(value)))?;
 
     return DEFINITIONS.with(|definitions| {
@@ -266,6 +291,7 @@ fn build_values(definition: &Yaml) -> Result<()> {
         match collection {
             Contains::Vec(v) => v.borrow_mut().clear(),
             Contains::Set(s) => s.borrow_mut().clear(),
+            Contains::Map(_) => bail!("definition '{}' was previously defined as a dictionary", name),
         };
         for yaml_value in values {
             let value = yaml_value.as_str()
@@ -274,12 +300,32 @@ fn build_values(definition: &Yaml) -> Result<()> {
             match collection {
                 Contains::Vec(v) => { v.borrow_mut().push(value); },
                 Contains::Set(s) => { s.borrow_mut().insert(value); },
+                Contains::Map(_) => unreachable!(),
             }
         }
         return Ok( () );
     });
 }
 
+// Convert a YAML dictionary def into a hashmap (e.g., chemical formula -> compound name)
+fn build_map(name: &str, entries: &yaml_rust::yaml::Hash) -> Result<()> {
+    let mut map = HashMap::with_capacity(entries.len());
+    for (key, value) in entries {
+        let key = key.as_str().ok_or_else(|| format!("dictionary key '{}' in '{}' is not a string", yaml_to_type(key), name))?;
+        let value = value.as_str().ok_or_else(|| format!("dictionary value '{}' in '{}' is not a string", yaml_to_type(value), name))?;
+        map.insert(key.to_string(), value.to_string());
+    }
+    return DEFINITIONS.with(|definitions| {
+        let name_definition_map = &mut definitions.borrow_mut().name_to_var_mapping;
+        match name_definition_map.get(name) {
+            Some(Contains::Map(old_map)) => { *old_map.borrow_mut() = map; },
+            Some(_) => bail!("definition '{}' was previously defined as a list", name),
+            None => { name_definition_map.insert(name.to_string(), Contains::Map( Rc::new( RefCell::new(map) ) )); },
+        };
+        return Ok( () );
+    });
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -309,4 +355,26 @@ mod tests {
             assert!(!names.contains("a"));
         });
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_read_def_map() {
+        let str = r#"[ChemicalCompounds: {"H2O": "water", "NaCl": "sodium chloride"}]"#;
+        let defs_build_fn = |variable_def_list: &Yaml| {
+            for variable_def in variable_def_list.as_vec().unwrap() {
+                build_values(variable_def)?;
+            }
+            return Ok(());
+        };
+        compile_rule(str, defs_build_fn).unwrap();
+        DEFINITIONS.with(|defs| {
+            let defs = defs.borrow();
+            assert!(defs.get_hashset("ChemicalCompounds").is_none());
+            let names = defs.get_hashmap("ChemicalCompounds");
+            assert!(names.is_some());
+            let names = names.unwrap();
+            assert_eq!(names.len(), 2);
+            assert_eq!(names.get("NaCl").map(|s| s.as_str()), Some("sodium chloride"));
+            assert!(names.get("CO2").is_none());
+        });
+    }
+}
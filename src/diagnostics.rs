@@ -0,0 +1,201 @@
+//! Diagnostics about the repairs canonicalization makes to the MathML (e.g., adding invisible times or retagging `<mo>sin</mo>`).
+//!
+//! Content authors (e.g., publishers using MathCAT as a quality gate) can use these to find problems in their source.
+//! The diagnostics are only collected while [`crate::set_mathml`] is canonicalizing (see [`start`] and [`finish`]);
+//! they are available afterwards from [`crate::get_canonicalization_report`].
+//!
+//! While canonicalizing, a diagnostic is attached to the element it is about by adding its index to [`DIAGNOSTIC_ATTR`].
+//! That way the diagnostic follows the element as the tree is restructured and it can be given the element's id
+//! once the ids are added.
+#![allow(clippy::needless_return)]
+
+use std::cell::RefCell;
+use std::fmt;
+use sxd_document::dom::*;
+use crate::canonicalize::as_element;
+use crate::xpath_functions::is_leaf;
+
+/// The attribute used to attach diagnostics to elements during canonicalization
+pub const DIAGNOSTIC_ATTR: &str = "data-diagnostic";
+
+/// The kind of repair made by canonicalization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// An invisible operator (times, function application, separator, or plus) was added between two operands
+    InvisibleOperatorAdded,
+    /// A leaf was changed to a different element (e.g., `<mo>sin</mo>` to `<mi>sin</mi>`)
+    LeafRetagged,
+    /// Digit blocks in separate elements were merged into a single `mn` (e.g., "1" "," "000")
+    NumberMerged,
+    /// A leaf was split into several leaves (e.g., "ABC" in "∠ABC" or the "-" in `<mn>-2</mn>`)
+    LeafSplit,
+    /// Adjacent leaves were merged into one (e.g., "|" "|" to "‖" or "arc" "cos" to "arccos")
+    LeavesMerged,
+    /// A (deprecated) `mfenced` was converted to an `mrow` with fences
+    MfencedConverted,
+}
+
+/// How likely it is that the author should fix the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The repair is normal inference (e.g., adding invisible times) -- many authoring tools never add these
+    Info,
+    /// The MathML is poorly authored and should be fixed (e.g., a function name in an `mo`)
+    Warning,
+}
+
+/// A repair made by canonicalization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub severity: Severity,
+    /// The id of the node (in the MathML returned by [`crate::set_mathml`]) that was repaired.
+    /// `None` if the node didn't survive canonicalization.
+    pub id: Option<String>,
+    /// A short description of the repair
+    pub description: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{:?} ({:?}) at '{}': {}", self.severity, self.kind, self.id.as_deref().unwrap_or("?"), self.description);
+    }
+}
+
+thread_local!{
+    /// The diagnostics for the MathML being canonicalized. `None` if they aren't being collected.
+    static DIAGNOSTICS: RefCell<Option<Vec<Diagnostic>>> = const { RefCell::new(None) };
+}
+
+/// Start collecting diagnostics (any uncollected diagnostics are thrown away)
+pub fn start() {
+    DIAGNOSTICS.with(|diagnostics| diagnostics.replace(Some(vec![])));
+}
+
+/// Stop collecting diagnostics and throw away any that were collected (e.g., canonicalization failed)
+pub fn stop() {
+    DIAGNOSTICS.with(|diagnostics| diagnostics.replace(None));
+}
+
+/// Record that `element` was repaired. Does nothing if diagnostics aren't being collected.
+pub fn report(element: Element, kind: DiagnosticKind, severity: Severity, description: String) {
+    DIAGNOSTICS.with(|diagnostics| {
+        if let Some(diagnostics) = diagnostics.borrow_mut().as_mut() {
+            let index = diagnostics.len().to_string();
+            let value = match element.attribute_value(DIAGNOSTIC_ATTR) {
+                None => index,
+                Some(value) => value.to_string() + " " + &index,
+            };
+            element.set_attribute_value(DIAGNOSTIC_ATTR, &value);
+            diagnostics.push( Diagnostic{ kind, severity, id: None, description } );
+        }
+    });
+}
+
+/// Stop collecting diagnostics and return them.
+/// `mathml` is the canonicalized MathML (with ids) -- the diagnostics get the ids of their elements and [`DIAGNOSTIC_ATTR`] is removed.
+pub fn finish(mathml: Element) -> Vec<Diagnostic> {
+    let mut diagnostics = DIAGNOSTICS.with(|diagnostics| diagnostics.replace(None)).unwrap_or_default();
+    set_ids(mathml, &mut diagnostics);
+    return diagnostics;
+
+    fn set_ids(mathml: Element, diagnostics: &mut [Diagnostic]) {
+        if let Some(value) = mathml.attribute_value(DIAGNOSTIC_ATTR) {
+            for index in value.split_whitespace() {
+                if let Some(diagnostic) = index.parse::<usize>().ok().and_then(|i| diagnostics.get_mut(i)) {
+                    diagnostic.id = mathml.attribute_value("id").map(|id| id.to_string());
+                }
+            }
+            mathml.remove_attribute(DIAGNOSTIC_ATTR);
+        }
+        if !is_leaf(mathml) {
+            for child in mathml.children() {
+                set_ids(as_element(child), diagnostics);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::*;
+
+    fn report_for(mathml: &str) -> Vec<Diagnostic> {
+        set_rules_dir(crate::abs_rules_dir_path()).unwrap();
+        let canonical = set_mathml(mathml.to_string()).unwrap();
+        assert!(!canonical.contains(DIAGNOSTIC_ATTR), "canonical MathML has diagnostic attrs:\n{}", canonical);
+        return get_canonicalization_report().unwrap();
+    }
+
+    fn summary(report: &[Diagnostic]) -> Vec<(DiagnosticKind, Severity, &str)> {
+        return report.iter().map(|d| (d.kind, d.severity, d.description.as_str())).collect();
+    }
+
+    #[test]
+    fn no_repairs() {
+        assert!(report_for("<math><mi>x</mi><mo>+</mo><mi>y</mi></math>").is_empty());
+    }
+
+    #[test]
+    fn merged_number_and_invisible_times() {
+        let report = report_for("<math><mn>1</mn><mo>,</mo><mn>000</mn><mi>x</mi></math>");
+        assert_eq!(summary(&report), vec![
+            (DiagnosticKind::NumberMerged, Severity::Warning, "3 elements were merged into the number '1,000'"),
+            (DiagnosticKind::InvisibleOperatorAdded, Severity::Info, "invisible times was added between mn<1,000> and mi<x>"),
+        ]);
+        assert!(report.iter().all(|d| d.id.is_some()));
+    }
+
+    #[test]
+    fn retagged_function_name() {
+        let report = report_for("<math><mo>sin</mo><mi>x</mi></math>");
+        assert_eq!(summary(&report), vec![
+            (DiagnosticKind::LeafRetagged, Severity::Warning, "'sin' was changed from 'mo' to 'mi'"),
+            (DiagnosticKind::InvisibleOperatorAdded, Severity::Info, "invisible function application was added between mi<sin> and mi<x>"),
+        ]);
+    }
+
+    #[test]
+    fn mfenced() {
+        let report = report_for("<math><mfenced id='f'><mi>x</mi><mi>y</mi></mfenced></math>");
+        assert_eq!(summary(&report), vec![
+            (DiagnosticKind::MfencedConverted, Severity::Warning, "'mfenced' is deprecated; converted to an 'mrow' with fences '(' and ')'"),
+        ]);
+        assert_eq!(report[0].id.as_deref(), Some("f"));
+    }
+
+    #[test]
+    fn vertical_bars() {
+        let report = report_for("<math><mo>|</mo><mo>|</mo><mi>x</mi><mo>|</mo><mo>|</mo></math>");
+        assert_eq!(summary(&report), vec![
+            (DiagnosticKind::LeavesMerged, Severity::Warning, "'|' '|' was merged into '‖' (U+2016)"),
+            (DiagnosticKind::LeavesMerged, Severity::Warning, "'|' '|' was merged into '‖' (U+2016)"),
+        ]);
+    }
+
+    #[test]
+    fn split_leaves() {
+        let report = report_for("<math><mn id='n'>-2</mn></math>");
+        assert_eq!(summary(&report), vec![
+            (DiagnosticKind::LeafSplit, Severity::Warning, "'-2' in an 'mn' was split into a minus sign and the number '2'"),
+        ]);
+        assert_eq!(report[0].id.as_deref(), Some("n"));
+
+        let report = report_for("<math><mo>∠</mo><mi>ABC</mi></math>");
+        assert_eq!(summary(&report), vec![
+            (DiagnosticKind::LeafSplit, Severity::Info, "'ABC' was split into separate letters"),
+            (DiagnosticKind::InvisibleOperatorAdded, Severity::Info, "invisible separator was added between mi<A> and mi<B>"),
+            (DiagnosticKind::InvisibleOperatorAdded, Severity::Info, "invisible separator was added between mi<B> and mi<C>"),
+        ]);
+    }
+
+    #[test]
+    fn failed_canonicalization() {
+        // the report from a previous expression shouldn't be returned when the MathML can't be canonicalized
+        assert!(!report_for("<math><mo>sin</mo><mi>x</mi></math>").is_empty());
+        assert!(set_mathml("<math><msup><mi>x</mi></msup></math>".to_string()).is_err());
+        assert!(get_canonicalization_report().unwrap().is_empty());
+        assert!(DIAGNOSTICS.with(|diagnostics| diagnostics.borrow().is_none()), "diagnostics are still being collected");
+    }
+}
@@ -18,19 +18,35 @@ use crate::canonicalize::{name, as_element};
 use crate::navigate::*;
 use crate::pretty_print::mml_to_string;
 use crate::xpath_functions::is_leaf;
+pub use crate::source_map::{SourceRef, SourceMap};
+pub use crate::diagnostics::{Diagnostic, DiagnosticKind, Severity};
 
 // wrap up some common functionality between the call from 'main' and AT
-fn cleanup_mathml(mathml: Element) -> Result<Element> {
+fn cleanup_mathml(mathml: Element) -> Result<(Element, SourceMap)> {
+    let originals = crate::source_map::mark_sources(mathml);
     trim_element(&mathml);
-    let mathml = crate::canonicalize::canonicalize(mathml)?;
+    crate::diagnostics::start();
+    let mathml = match crate::canonicalize::canonicalize(mathml) {
+        Ok(mathml) => mathml,
+        Err(e) => {
+            crate::diagnostics::stop();
+            return Err(e);
+        },
+    };
     let mathml = add_ids(mathml);
-    return Ok(mathml);
+    let report = crate::diagnostics::finish(mathml);
+    CANONICALIZATION_REPORT.with(|old_report| old_report.replace(report));
+    let source_map = crate::source_map::build_source_map(mathml, &originals);
+    return Ok( (mathml, source_map) );
 }
 
 
 thread_local!{
     /// The current node being navigated (also spoken and brailled) is stored in `MATHML_INSTANCE`.
     pub static MATHML_INSTANCE: RefCell<Package> = init_mathml_instance();
+
+    /// The repairs made when canonicalizing `MATHML_INSTANCE` (see [`get_canonicalization_report`]).
+    static CANONICALIZATION_REPORT: RefCell<Vec<Diagnostic>> = const { RefCell::new(vec![]) };
 }
 
 fn init_mathml_instance() -> RefCell<Package> {
@@ -58,6 +74,15 @@ pub fn get_version() -> String {
 /// This returns canonical MathML with 'id's set on any node that doesn't have an id.
 /// The ids can be used for sync highlighting if the `Bookmark` API preference is true.
 pub fn set_mathml(mathml_str: String) -> Result<String> {
+    return set_mathml_with_source_map(mathml_str).map(|(mathml, _)| mathml);
+}
+
+/// Same as [`set_mathml`], but also returns a map from the id of every node in the canonical MathML to the node(s)
+/// in `mathml_str` that it was derived from. Canonicalization merges number blocks, splits some leaves,
+/// converts `mfenced`, adds invisible operators and `mrow`s, etc., so the canonical ids often don't exist in the original
+/// (e.g., MathML rendered by MathJax). The map can be used to highlight the original nodes when navigating.
+/// See [`SourceRef`] for how an original node is identified.
+pub fn set_mathml_with_source_map(mathml_str: String) -> Result<(String, SourceMap)> {
     lazy_static! {
         // if these are present when resent to MathJaX, MathJaX crashes (https://github.com/mathjax/MathJax/issues/2822)
         static ref MATHJAX_V2: Regex = Regex::new(r#"class *= *['"]MJX-.*?['"]"#).unwrap();
@@ -70,6 +95,8 @@ pub fn set_mathml(mathml_str: String) -> Result<String> {
     NAVIGATION_STATE.with(|nav_stack| {
         nav_stack.borrow_mut().reset();
     });
+    // the report is for the new MathML, so don't leave the old one around if this fails
+    CANONICALIZATION_REPORT.with(|report| report.borrow_mut().clear());
     return MATHML_INSTANCE.with(|old_package| {
         // FIX: convert this to an included file once I get the full entity list
         static HTML_ENTITIES_MAPPING: phf::Map<&str, &str> = include!("entities.in");
@@ -106,14 +133,29 @@ pub fn set_mathml(mathml_str: String) -> Result<String> {
 
         let new_package = new_package.unwrap();
         let mathml = get_element(&new_package);
-        let mathml = cleanup_mathml(mathml)?;
+
+        // the expression can say what subject area it is from (e.g., statistics) -- that overrides the 'SubjectArea' pref
+        let pref_manager = crate::prefs::PreferenceManager::get();
+        let subject_area_changed = pref_manager.borrow_mut().set_expr_subject_area(mathml.attribute_value("data-subject-area"));
+        if subject_area_changed {
+            crate::speech::SpeechRules::invalidate(FilesChanged::new("SubjectArea").unwrap());
+        }
+        let (mathml, source_map) = cleanup_mathml(mathml)?;
         let mathml_string = mml_to_string(&mathml);
         old_package.replace(new_package);
 
-        return Ok( mathml_string );
+        return Ok( (mathml_string, source_map) );
     })
 }
 
+/// Get the repairs that were made when canonicalizing the MathML that was set (e.g., adding an invisible times or
+/// changing `<mo>sin</mo>` to `<mi>sin</mi>`).
+/// Content authors can use these to fix problems in their MathML; see [`Diagnostic`] for what is reported.
+/// The report is empty if the last call to `set_mathml` failed.
+pub fn get_canonicalization_report() -> Result<Vec<Diagnostic>> {
+    return Ok( CANONICALIZATION_REPORT.with(|report| report.borrow().clone()) );
+}
+
 /// Get the spoken text of the MathML that was set.
 /// The speech takes into account any AT or user preferences.
 pub fn get_spoken_text() -> Result<String> {
@@ -127,13 +169,34 @@ pub fn get_spoken_text() -> Result<String> {
         debug!("Intent tree:\n{}", mml_to_string(&intent));
         let speech = crate::speech::speak_mathml(intent, "")?;
         // info!("Time taken: {}ms", instant.elapsed().as_millis());
-        return Ok( speech );
+        let pref_manager = crate::prefs::PreferenceManager::get();
+        let pref_manager = pref_manager.borrow();
+        return Ok( pref_manager.get_tts().wrap_speech(speech, &pref_manager) );
+    });
+}
+
+/// The `(start, end, id)` entries returned by [`get_spoken_text_with_sync_map`].
+pub type SyncMap = Vec<(usize, usize, String)>;
+
+/// Get the spoken text of the MathML that was set along with a map that can be used to highlight each word as it is spoken.
+/// The map is an ordered list of `(start, end, id)` entries, one per word: `[start, end)` is the range of *characters* (not bytes)
+/// of the word in the spoken text and `id` is the id of the node (in the MathML returned by `set_mathml`) that generated the word.
+/// TTS markup is not part of any word, so the map can be used with any TTS setting (including `None`).
+pub fn get_spoken_text_with_sync_map() -> Result<(String, SyncMap)> {
+    return MATHML_INSTANCE.with(|package_instance| {
+        let package_instance = package_instance.borrow();
+        let mathml = get_element(&package_instance);
+        let new_package = Package::new();
+        let intent = crate::speech::intent_from_mathml(mathml, new_package.as_document())?;
+        let marked_speech = crate::speech::speak_mathml_with_sync_marks(intent)?;
+        let pref_manager = crate::prefs::PreferenceManager::get();
+        return crate::speech::sync_map(&marked_speech, &pref_manager.borrow());
     });
 }
 
 /// Get the spoken text for an overview of the MathML that was set.
 /// The speech takes into account any AT or user preferences.
-/// Note: this implementation for is currently minimal and should not be used.
+/// The structure is spoken down to `OverviewDepth` levels; below that, large subexpressions are named (e.g., "a fraction").
 pub fn get_overview_text() -> Result<String> {
     // use std::time::{Instant};
     // let instant = Instant::now();
@@ -142,7 +205,9 @@ pub fn get_overview_text() -> Result<String> {
         let mathml = get_element(&package_instance);
         let speech = crate::speech::overview_mathml(mathml, "")?;
         // info!("Time taken: {}ms", instant.elapsed().as_millis());
-        return Ok( speech );
+        let pref_manager = crate::prefs::PreferenceManager::get();
+        let pref_manager = pref_manager.borrow();
+        return Ok( pref_manager.get_tts().wrap_speech(speech, &pref_manager) );
     });
 }
 
@@ -169,7 +234,7 @@ pub fn get_preference(name: String) -> Result<String> {
 /// The value should either be a string or a number (depending upon the preference being set)
 /// The list of known user preferences is in the MathCAT user documentation.
 /// Here are common preferences set by programs (not settable by the user):
-/// * TTS -- SSML, SAPI5, None
+/// * TTS -- SSML, SSMLDocument, SAPI5, Eloquence, Mac, None
 /// * Pitch -- normalized at '1.0'
 /// * Rate -- words per minute (should match current speech rate).
 ///       There is a separate "MathRate" that is user settable that causes a relative percentage change from this rate.
@@ -210,7 +275,7 @@ pub fn set_preference(name: String, value: String) -> Result<()> {
             pref_manager.set_api_boolean_pref(&name, value.to_lowercase()=="true"); 
         } else { 
             match name.as_str() {
-                "Pitch" | "Rate" | "Volume" | "CapitalLetters_Pitch"=> {
+                "Pitch" | "Rate" | "Volume" | "CapitalLetters_Pitch" | "OverviewDepth" => {
                     pref_manager.set_api_float_pref(&name, to_float(&name, &value)?);    
                 },
                 _ => {
@@ -238,6 +303,35 @@ pub fn set_preference(name: String, value: String) -> Result<()> {
     }
 }
 
+/// Add (or replace) a user override for how `text` is spoken in the current language.
+/// `text` can be a character ("≤"), a word in the math ("mod"), or an `intent` concept name ("greatest-common-divisor").
+/// `speech` is either the words to speak ("at most") or a YAML list of replacements as used in `unicode.yaml`
+/// (e.g., `[pronounce: [{text: theta}, {ipa: "θiːtə"}]]`).
+///
+/// Overrides set here take precedence over the user's `speech-overrides.yaml` file and last until removed.
+pub fn set_speech_override(text: String, speech: String) -> Result<()> {
+    return crate::speech::SPEECH_RULES.with(|rules| {
+        let rules = rules.borrow();
+        if let Some(error_string) = rules.get_error() {
+            bail!("{}", error_string);
+        }
+        return rules.pref_manager.borrow_mut().set_speech_override(&text, &speech);
+    });
+}
+
+/// Remove the override for how `text` is spoken in the current language.
+/// This also hides an override for `text` in the user's `speech-overrides.yaml` file.
+pub fn remove_speech_override(text: String) -> Result<()> {
+    return crate::speech::SPEECH_RULES.with(|rules| {
+        let rules = rules.borrow();
+        if let Some(error_string) = rules.get_error() {
+            bail!("{}", error_string);
+        }
+        rules.pref_manager.borrow_mut().remove_speech_override(&text);
+        return Ok( () );
+    });
+}
+
 /// Get the braille associated with the MathML that was set by [`set_mathml`].
 /// The braille returned depends upon the preference for the `code` preference (default `Nemeth`).
 pub fn get_braille(nav_node_id: String) -> Result<String> {
@@ -252,6 +346,19 @@ pub fn get_braille(nav_node_id: String) -> Result<String> {
     });
 }
 
+/// Get the braille associated with the MathML that was set by [`set_mathml`] along with the location of `nav_node_id` in it.
+/// The location is the (0-based) start and end (exclusive) char positions of the node's braille.
+/// If there is no node with that id, start == end.
+/// The location is returned regardless of the `BrailleNavHighlight` pref so that apps can show the position themselves.
+pub fn get_braille_with_nav_range(nav_node_id: String) -> Result<(String, usize, usize)> {
+    return MATHML_INSTANCE.with(|package_instance| {
+        let package_instance = package_instance.borrow();
+        let mathml = get_element(&package_instance);
+        let (braille, nav_range) = crate::braille::braille_mathml_with_nav_range(mathml, &nav_node_id)?;
+        return Ok( (braille, nav_range.start, nav_range.end) );
+    });
+}
+
 /// Given a key code along with the modifier keys, the current node is moved accordingly (or value reported in some cases).
 /// `key` is the [keycode](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/keyCode#constants_for_keycode_value) for the key (in JavaScript, `ev.key_code`)
 /// The spoken text for the new current node is returned.
@@ -812,4 +919,4 @@ mod tests {
         let target = "<math><mn>1</mn> <mtext>a aa</mtext> <mi>y</mi></math>";
         assert!(are_parsed_strs_equal(test, target));
     }
-}
\ No newline at end of file
+}
@@ -5,6 +5,8 @@
 //! 2. Set whatever preferences are need with repeated calls to [`set_preference`].
 //! 3. Set MathML via [`set_mathml`]
 //!    A string representing the cleaned up MathML along with `id`s on each node is returned for highlighting if desired
+//!    [`set_mathml_with_source_map`] also returns a map from those `id`s back to the nodes in the MathML that was passed in
+//!    The repairs made to the MathML (useful for content authors) are available from [`get_canonicalization_report`]
 //! 4. Get the speech [`get_spoken_text`] or (Unicode) braille [`get_braille`].
 //!
 //! The expression can be navigated also.
@@ -57,6 +59,8 @@ mod xpath_functions;
 mod definitions;
 mod pretty_print;
 mod chemistry;
+mod source_map;
+mod diagnostics;
 
 pub mod shim_filesystem; // really just for override_file_for_debugging_rules, but the config seems to throw it off
 pub use shim_filesystem::ZIPPED_RULE_FILES;
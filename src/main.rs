@@ -224,6 +224,10 @@ fn main() {
   if let Err(e) = set_mathml(expr.to_string()) {
     panic!("Error: exiting -- {}", errors_to_string(&e));
   };
+  match get_canonicalization_report() {
+    Ok(report) => report.iter().for_each(|diagnostic| info!("Canonicalization: {}", diagnostic)),
+    Err(e) => panic!("{}", errors_to_string(&e)),
+  }
 
   match get_spoken_text() {
     Ok(speech) => info!("Computed speech string:\n   '{}'", speech),
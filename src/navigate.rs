@@ -224,6 +224,8 @@ impl NavigationState {
 
         // used by nav rules for speech -- needs an initial value so tests don't fail
         context.set_variable("Move2D", "" );
+        context.set_variable("PlayedEarcon", false );      // see the "into-or-out-of-earcon" rule
+        context.set_variable("SpeakRowLabel", false );     // see the "row-label" rule
         context.set_variable("SpeakExpression", true );    // default is to speak the expr after navigation
         return;
 
@@ -244,8 +246,8 @@ fn convert_last_char_to_number(str: &str) -> usize {
 
 /// Get the node associated with 'id'
 /// This can be called on an intent tree -- it does not make use of is_leaf()
-fn get_node_by_id<'a>(mathml: Element<'a>, id: &str) -> Option<Element<'a>> {
-    if mathml.attribute_value("id").unwrap() == id {
+pub(crate) fn get_node_by_id<'a>(mathml: Element<'a>, id: &str) -> Option<Element<'a>> {
+    if mathml.attribute_value("id") == Some(id) {
         return Some(mathml);
     }
 
@@ -363,7 +365,8 @@ pub fn do_navigate_command_string(mathml: Element, nav_command: &'static str) ->
                     Ok( (speech, done)) => {
                         cumulative_speech = cumulative_speech + if loop_count==0 {""} else {" "} + speech.trim();
                         if done {
-                            return Ok(cumulative_speech);
+                            let pref_manager = rules.pref_manager.borrow();
+                            return Ok( pref_manager.get_tts().wrap_speech(cumulative_speech, &pref_manager) );
                         }
                     },
                     Err(e) => {
@@ -518,7 +521,16 @@ fn speak<'r, 'c, 's:'c, 'm:'c>(rules_with_context: &'r mut SpeechRulesWithContex
                 },
             }
         }
-        return crate::speech::speak_mathml(mathml, &nav_node_id);
+        return match crate::speech::speak_mathml(mathml, &nav_node_id) {
+            Err(e) if e.to_string() == crate::speech::NAV_NODE_SPEECH_NOT_FOUND => {
+                // the node's speech can be part of the speech of an ancestor (e.g., a row of digits in an mstack read as a number)
+                match get_node_by_id(mathml, &nav_node_id) {
+                    Some(nav_node) => crate::speech::speak_mathml(nav_node, ""),
+                    None => Err(e),
+                }
+            },
+            result => result,
+        };
     } else {
         return crate::speech::overview_mathml(mathml, &nav_node_id);
     }
@@ -878,6 +890,101 @@ mod tests {
         });
     }
 
+    #[test]
+    fn earcons() -> Result<()> {
+        let mathml_str = "<math id='math'><mfrac id='mfrac'>
+                <msup id='msup'><mi id='base'>b</mi><mn id='exp'>2</mn></msup>
+                <mi id='denom'>d</mi>
+            </mfrac></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        set_preference("SpeechSound".to_string(), "Beep".to_string()).unwrap();
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&*package_instance);
+            let speech = test_command("ZoomIn", mathml, "msup");
+            assert_eq!(speech, "zoom in; [sound:nav-in.mp4] in numerator; b squared");
+            let speech = test_command("MoveNext", mathml, "denom");
+            assert_eq!(speech, "move right, [sound:nav-in.mp4] in denominator; d");
+            let speech = test_command("MoveNext", mathml, "denom");
+            assert_eq!(speech, "[sound:nav-bump.mp4] cannot move right, end of math;");
+            let speech = test_command("ZoomOut", mathml, "mfrac");
+            assert!(speech.contains("[sound:nav-out.mp4]"), "speech is '{}'", speech);
+            test_command("ZoomIn", mathml, "msup");
+            test_command("MoveNext", mathml, "denom");
+            set_preference("TTS".to_string(), "SSML".to_string()).unwrap();
+            let speech = test_command("MoveNext", mathml, "denom");
+            assert!(speech.contains("<audio src='nav-bump.mp4'>bump</audio>"), "speech is '{}'", speech);
+            set_preference("SpeechSound".to_string(), "None".to_string()).unwrap();
+            let speech = test_command("MoveNext", mathml, "denom");
+            assert!(!speech.contains("audio"), "speech is '{}'", speech);
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn low_vision() -> Result<()> {
+        let mathml_str = "<math id='math'><mfrac id='mfrac'>
+                <msup id='msup'><mi id='base'>b</mi><mn id='exp'>2</mn></msup>
+                <mi id='denom'>d</mi>
+            </mfrac></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        set_preference("Impairment".to_string(), "LowVision".to_string()).unwrap();
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&*package_instance);
+            // the highlighting shows where we are, so "in numerator", etc., isn't spoken
+            assert_eq!(test_command("ZoomIn", mathml, "msup"), "zoom in; b squared");
+            assert_eq!(test_command("MoveNext", mathml, "denom"), "move right, d");
+            set_preference("SpeechSound".to_string(), "Beep".to_string()).unwrap();
+            assert_eq!(test_command("MovePrevious", mathml, "msup"), "move left, [sound:nav-in.mp4] b squared");
+            set_preference("SpeechSound".to_string(), "None".to_string()).unwrap();
+            set_preference("Impairment".to_string(), "Blindness".to_string()).unwrap();
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn low_vision_translated() -> Result<()> {
+        // every language uses the same rule to skip speaking the move into/out of 2D notations
+        let mathml_str = "<math id='math'><mfrac id='mfrac'>
+                <msup id='msup'><mi id='base'>b</mi><mn id='exp'>2</mn></msup>
+                <mi id='denom'>d</mi>
+            </mfrac></math>";
+        for (language, move_next) in [("es", "desplazar derecha, d"), ("id", "pindah kanan, d"), ("vi", "chuyển qua phải, d")] {
+            init_default_prefs(mathml_str, "Enhanced");
+            set_preference("Language".to_string(), language.to_string()).unwrap();
+            set_preference("Impairment".to_string(), "LowVision".to_string()).unwrap();
+            MATHML_INSTANCE.with(|package_instance| {
+                let package_instance = package_instance.borrow();
+                let mathml = get_element(&package_instance);
+                test_command("ZoomIn", mathml, "msup");
+                assert_eq!(test_command("MoveNext", mathml, "denom"), move_next, "language '{}'", language);
+            });
+        }
+        set_preference("Language".to_string(), "en".to_string()).unwrap();
+        set_preference("Impairment".to_string(), "Blindness".to_string()).unwrap();
+        return Ok( () );
+    }
+
+    #[test]
+    fn learning_disability() -> Result<()> {
+        let mathml_str = "<math id='math'><mfrac id='mfrac'>
+                <msup id='msup'><mi id='base'>b</mi><mn id='exp'>2</mn></msup>
+                <mi id='denom'>d</mi>
+            </mfrac></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        set_preference("Impairment".to_string(), "LearningDisability".to_string()).unwrap();
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&package_instance);
+            // everyday words are used for the parts of a fraction
+            assert_eq!(test_command("ZoomIn", mathml, "msup"), "zoom in; in the top; b squared");
+            assert_eq!(test_command("MoveNext", mathml, "denom"), "move right, in the bottom; d");
+            set_preference("Impairment".to_string(), "Blindness".to_string()).unwrap();
+            return Ok( () );
+        });
+    }
+
     #[test]
     fn test_init_navigate_move_right() -> Result<()> {
         // this is how navigation typically starts up
@@ -1260,6 +1367,53 @@ mod tests {
         });
     }
         
+    #[test]
+    fn chemistry_as_compound() -> Result<()> {
+        let mathml_str = "<math id='math'><msub id='msub'><mi id='H'>H</mi><mn id='2'>2</mn></msub><mi id='O'>O</mi></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        set_preference("Chemistry".to_string(), "AsCompound".to_string()).unwrap();
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&*package_instance);
+            let speech = test_command("ZoomOutAll", mathml, "");
+            assert!(speech.ends_with("water,"), "speech is '{}'", speech);
+            // can still explore the elements in the compound
+            assert_eq!("zoom in; cap h sub 2", test_command("ZoomIn", mathml, "msub"));
+            assert_eq!("move right, cap o", test_command("MoveNext", mathml, "O"));
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn describe_current() -> Result<()> {
+        let mathml_str = "<math id='math'><mrow id='mrow'>
+                <mi id='y'>y</mi><mo id='eq'>=</mo>
+                <mrow id='rhs'>
+                    <mfrac id='mfrac'>
+                        <mrow id='num'><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mi>c</mi></mrow>
+                        <mrow id='denom'><mi>x</mi><mo>-</mo><mn>1</mn></mrow>
+                    </mfrac>
+                    <mo id='plus'>+</mo>
+                    <mn id='one'>1</mn>
+                </mrow>
+            </mrow></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&*package_instance);
+            test_command("ZoomInAll", mathml, "y");
+            test_command("MoveNext", mathml, "eq");
+            test_command("MoveNext", mathml, "rhs");
+            // the overview starts at the nav node, so the fraction is one level down
+            assert_eq!("describe current; a fraction plus 1", test_command("DescribeCurrent", mathml, "rhs"));
+            set_preference("OverviewDepth".to_string(), "2".to_string()).unwrap();
+            let speech = test_command("DescribeCurrent", mathml, "rhs");
+            assert!(speech.starts_with("describe current; fraction, eigh plus b plus c,"), "speech is '{}'", speech);
+            set_preference("OverviewDepth".to_string(), "1".to_string()).unwrap();
+            return Ok( () );
+        });
+    }
+
     #[test]
     fn move_mmultiscripts_char() -> Result<()> {
         let mathml_str = "<math display='block' id='id-0' data-id-added='true'>
@@ -1604,6 +1758,79 @@ mod tests {
             return Ok( () );
         });
     }
+
+    #[test]
+    fn move_cell_stack() -> Result<()> {
+        let mathml_str = "<math id='math'><mstack id='stack'>
+            <mscarries id='carries'><none/><mscarry id='c2'><mn id='carry'>1</mn></mscarry><none/></mscarries>
+            <msrow id='r1'><mn id='r1-1'>1</mn><mn id='r1-2'>7</mn><mn id='r1-3'>8</mn></msrow>
+            <msrow id='r2'><mo id='plus'>+</mo><mn id='r2-2'>4</mn><mn id='r2-3'>6</mn></msrow>
+            <msline id='line'/>
+            <msrow id='r3'><mn id='r3-1'>2</mn><mn id='r3-2'>2</mn><mn id='r3-3'>4</mn></msrow>
+          </mstack></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&*package_instance);
+            test_command("ZoomIn", mathml, "carries");
+            let speech = test_command("MoveCellDown", mathml, "r1");
+            assert_eq!(speech, "move down; row 1, 178");
+            test_command("ZoomIn", mathml, "r1-1");
+            let speech = test_command("MoveCellNext", mathml, "r1-2");
+            assert_eq!(speech, "move right, column 2; 7");
+            let speech = test_command("MoveCellUp", mathml, "c2");
+            assert_eq!(speech, "move up; carry 1 in column 2");
+            let speech = test_command("MoveCellUp", mathml, "c2");
+            assert_eq!(speech, "no previous row");
+            let speech = test_command("MoveCellNext", mathml, "c2");
+            assert_eq!(speech, "no next column");
+            test_command("MoveCellDown", mathml, "r1-2");
+            test_command("MoveCellDown", mathml, "r2-2");
+            let speech = test_command("MoveCellDown", mathml, "r3-2");
+            assert_eq!(speech, "move down, row 3; 2");
+            let speech = test_command("MoveCellDown", mathml, "r3-2");
+            assert_eq!(speech, "no next row");
+            let speech = test_command("MoveCellPrevious", mathml, "r3-1");
+            assert_eq!(speech, "move left, column 1; 2");
+            test_command("MoveCellUp", mathml, "r1-1");     // skips the empty space to the left of "46"
+            let speech = test_command("ReadCellCurrent", mathml, "r1-1");
+            assert_eq!(speech, "read current entry; row 1 column 1, 1");
+            return Ok( () );
+        });
+    }
+
+    #[test]
+    fn move_cell_long_division() -> Result<()> {
+        let mathml_str = "<math id='math'><mlongdiv id='div'>
+            <mn id='divisor'>3</mn>
+            <msrow id='result'><mn id='q1'>4</mn><mn id='q2'>5</mn></msrow>
+            <msrow id='dividend'><mn id='d1'>1</mn><mn id='d2'>3</mn><mn id='d3'>5</mn></msrow>
+            <msgroup position='1' shift='-1'>
+              <msgroup><msrow><mn id='s1'>1</mn><mn id='s2'>2</mn></msrow><msline/></msgroup>
+              <msgroup><msrow><mn id='s3'>1</mn><mn id='s4'>5</mn></msrow></msgroup>
+            </msgroup>
+          </mlongdiv></math>";
+        init_default_prefs(mathml_str, "Enhanced");
+        return MATHML_INSTANCE.with(|package_instance| {
+            let package_instance = package_instance.borrow();
+            let mathml = get_element(&*package_instance);
+            test_command("ZoomIn", mathml, "divisor");
+            test_command("MoveNext", mathml, "result");
+            test_command("ZoomIn", mathml, "q1");
+            let speech = test_command("MoveCellDown", mathml, "d2");
+            assert_eq!(speech, "move down, dividend; 3");
+            let speech = test_command("MoveCellDown", mathml, "s2");
+            assert_eq!(speech, "move down, row 1; 2");     // "12" is shifted left one column
+            let speech = test_command("MoveCellDown", mathml, "s3");
+            assert_eq!(speech, "move down, row 2; 1");
+            test_command("MoveCellNext", mathml, "s4");
+            let speech = test_command("MoveCellUp", mathml, "d3");
+            assert_eq!(speech, "move up, dividend; 5");
+            let speech = test_command("MoveCellUp", mathml, "q2");
+            assert_eq!(speech, "move up, result; 5");
+            return Ok( () );
+        });
+    }
     
     #[test]
     fn placemarker() -> Result<()> {
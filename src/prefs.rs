@@ -26,7 +26,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
-use crate::speech::{as_str_checked, RulesFor};
+use crate::speech::{as_str_checked, RulesFor, ReplacementArray};
 use crate::interface::errors_to_string;
 use std::collections::HashMap;
 use crate::shim_filesystem::*;
@@ -66,14 +66,19 @@ impl Preferences{
         prefs.insert("Blind".to_string(), Yaml::Boolean(true));
         prefs.insert("MathRate".to_string(), Yaml::String("100.0".to_string()));
         prefs.insert("PauseFactor".to_string(), Yaml::String("100.0".to_string()));
+        prefs.insert("SpeechSound".to_string(), Yaml::String("None".to_string()));
+        prefs.insert("ElementaryMath".to_string(), Yaml::String("ByRow".to_string()));
         prefs.insert("NavMode".to_string(), Yaml::String("enhanced".to_string()));
         prefs.insert("Overview".to_string(), Yaml::String("read".to_string()));
         prefs.insert("ResetOverView".to_string(), Yaml::Boolean(true));
+        prefs.insert("OverviewDepth".to_string(), Yaml::Integer(1));
         prefs.insert("NavVerbosity".to_string(), Yaml::String("verbose".to_string()));
         prefs.insert("AutoZoomOut".to_string(), Yaml::Boolean(true));
         prefs.insert("BrailleCode".to_string(), Yaml::String("Nemeth".to_string()));
         prefs.insert("BrailleNavHighlight".to_string(), Yaml::String("EndPoints".to_string()));
+        prefs.insert("BrailleSpatialLayout".to_string(), Yaml::Boolean(false));
         prefs.insert("UEB_START_MODE".to_string(), Yaml::String("Grade2".to_string()));
+        prefs.insert("LaTeX_BrailleTable".to_string(), Yaml::String("NorthAmerican".to_string()));
     
         return Preferences{ prefs };
     }
@@ -118,13 +123,7 @@ impl Preferences{
                         system_prefs_file.to_str().unwrap());
         }
 
-        let user_dir = dirs::config_dir();
-        if let Some(mut user_prefs_file) = user_dir {
-            user_prefs_file.push("MathCAT/prefs.yaml");
-            if is_file_shim(&user_prefs_file) {
-                result[1] = Some( user_prefs_file );
-            }            
-        }
+        result[1] = Preferences::user_file("prefs.yaml");
 
         return FileAndTime {
             times: if cfg!(target_family = "wasm") {
@@ -136,6 +135,18 @@ impl Preferences{
         }
     }
 
+    /// Return the path to 'file_name' in the user's MathCAT config dir if the file exists
+    fn user_file(file_name: &str) -> Option<PathBuf> {
+        if let Some(mut user_file) = dirs::config_dir() {
+            user_file.push("MathCAT");
+            user_file.push(file_name);
+            if is_file_shim(&user_file) {
+                return Some(user_file);
+            }
+        }
+        return None;
+    }
+
     fn read_file(file: &Option<PathBuf>, mut base_prefs: Preferences) -> Result<Preferences> {
         let unwrapped_file = match file {
             None => return Ok(base_prefs),
@@ -296,6 +307,121 @@ pub fn is_valid(locations: &Locations) -> bool {
     return locations[0].is_some();
 }
 
+/// Name of the user's pronunciation override file -- it lives next to the user's `prefs.yaml`
+static SPEECH_OVERRIDES_FILE: &str = "speech-overrides.yaml";
+
+/// User-level pronunciation overrides. These take precedence over `unicode.yaml`, `definitions.yaml` and the speech rules.
+///
+/// The overrides file is keyed by language and each language maps a string to what should be spoken for it.
+/// The string can be a character ("≤"), a word found in the math ("mod"), or an `intent` concept name ("greatest-common-divisor").
+/// The value is either a string or a list of replacements as in `unicode.yaml` (e.g., `[pronounce: [{text: theta}, {ipa: "θiːtə"}]]`):
+/// ```yaml
+/// en:
+///   "≤": "at most"
+///   "mod": "modulo"
+/// ```
+/// Overrides can also be added or removed by the AT at runtime -- those take precedence over the file.
+#[derive(Debug, Default)]
+struct SpeechOverrides {
+    file: Option<PathBuf>,      // user's overrides file (if it exists)
+    time: Option<SystemTime>,   // ~time file was read (used to see if it was updated and needs to be re-read)
+    user: HashMap<String, HashMap<String, ReplacementArray>>,          // language -> (text -> speech) from the file
+    api: HashMap<String, HashMap<String, Option<ReplacementArray>>>,   // language -> (text -> speech); 'None' removes an override
+}
+
+impl SpeechOverrides {
+    /// Convert the value of an override into replacements -- a string is shorthand for `[t: string]`
+    fn build_replacements(value: &Yaml) -> Result<ReplacementArray> {
+        return match value {
+            Yaml::String(s) => ReplacementArray::build(&Yaml::Array(vec![make_t_replacement(s)])),
+            Yaml::Integer(_) | Yaml::Real(_) => ReplacementArray::build(&Yaml::Array(vec![make_t_replacement(&yaml_to_string(value, 0))])),
+            _ => ReplacementArray::build(value),
+        };
+
+        fn make_t_replacement(text: &str) -> Yaml {
+            let mut hash = yaml_rust::yaml::Hash::new();
+            hash.insert(Yaml::String("t".to_string()), Yaml::String(text.to_string()));
+            return Yaml::Hash(hash);
+        }
+    }
+
+    /// Read the overrides from the string (the contents of the overrides file).
+    /// Errors in individual entries are logged and the entry is skipped so that one bad entry doesn't lose all of them.
+    fn from_str(file_contents: &str, file_name: &str) -> Result<HashMap<String, HashMap<String, ReplacementArray>>> {
+        let docs = match YamlLoader::load_from_str(file_contents) {
+            Err(e) => bail!("Yaml parse error ('{}') in file {}", e, file_name),
+            Ok(docs) => docs,
+        };
+        let mut result = HashMap::new();
+        if docs.is_empty() || docs[0].is_null() {
+            return Ok(result);
+        }
+        let languages = match docs[0].as_hash() {
+            None => bail!("Yaml error in file {}.\nThe top level should be a dictionary of languages. Found {}", file_name, yaml_to_string(&docs[0], 1)),
+            Some(languages) => languages,
+        };
+        for (language, overrides) in languages {
+            let language = as_str_checked(language).chain_err(|| format!("language name is not a string in file {}", file_name))?;
+            let mut language_overrides = HashMap::new();
+            if let Some(overrides) = overrides.as_hash() {
+                for (text, speech) in overrides {
+                    let text = match text {
+                        Yaml::String(s) => s.clone(),
+                        Yaml::Integer(_) | Yaml::Real(_) => yaml_to_string(text, 0),
+                        _ => {
+                            error!("Override key '{}' for language '{}' is not a string in file {}", yaml_to_string(text, 0), language, file_name);
+                            continue;
+                        }
+                    };
+                    match SpeechOverrides::build_replacements(speech) {
+                        Ok(replacements) => {language_overrides.insert(text, replacements);},
+                        Err(e) => error!("{}", errors_to_string(&e.chain_err(||
+                                        format!("override for '{}' (language '{}') in file {}", text, language, file_name)))),
+                    }
+                }
+            } else if !overrides.is_null() {
+                error!("Overrides for language '{}' are not a dictionary in file {}", language, file_name);
+            }
+            result.insert(language.to_string(), language_overrides);
+        }
+        return Ok(result);
+    }
+
+    /// Re-read the user's overrides file if it has been added, removed, or changed since it was last read.
+    fn update(&mut self) -> Result<()> {
+        let file = Preferences::user_file(SPEECH_OVERRIDES_FILE);
+        let time = if cfg!(target_family = "wasm") {None} else {PreferenceManager::get_metadata(&file)};
+        if file == self.file && time == self.time {
+            return Ok( () );
+        }
+        self.user = match &file {
+            None => HashMap::new(),
+            Some(path) => {
+                let file_name = path.to_str().unwrap_or(SPEECH_OVERRIDES_FILE);
+                let file_contents = read_to_string_shim(path).chain_err(|| format!("Couldn't read file {}", file_name))?;
+                SpeechOverrides::from_str(&file_contents, file_name)?
+            },
+        };
+        self.file = file;
+        self.time = time;
+        return Ok( () );
+    }
+
+    /// Find the override for 'text' in 'language' (e.g., "en-gb"), falling back to the main language ("en").
+    fn get(&self, language: &str, text: &str) -> Option<&ReplacementArray> {
+        let main_language = language.split('-').next().unwrap_or(language);
+        for language in [language, main_language] {
+            if let Some(api_override) = self.api.get(language).and_then(|overrides| overrides.get(text)) {
+                return api_override.as_ref();
+            }
+            if let Some(user_override) = self.user.get(language).and_then(|overrides| overrides.get(text)) {
+                return Some(user_override);
+            }
+        }
+        return None;
+    }
+}
+
 thread_local!{
     static DEFAULT_USER_PREFERENCES: Preferences = Preferences::user_defaults();
     static DEFAULT_API_PREFERENCES: Preferences = Preferences::api_defaults();
@@ -329,6 +455,10 @@ pub struct PreferenceManager {
     braille_unicode: FileAndTime,       // short braille unicode file
     braille_unicode_full: FileAndTime,  // full braille unicode file
     defs: FileAndTime,                  // the definition.yaml file(s)
+    intent_subject_area: FileAndTime,   // optional intent rules for the subject area (e.g., Intent/SubjectAreas/Calculus.yaml)
+    speech_subject_area: FileAndTime,   // optional speech rules for the subject area (e.g., en/SubjectAreas/Calculus.yaml)
+    expr_subject_area: Option<String>,  // subject area given by the current expression (overrides the 'SubjectArea' pref)
+    speech_overrides: SpeechOverrides,  // user's pronunciation overrides (speech-overrides.yaml + API changes)
 }
 
 
@@ -383,7 +513,7 @@ impl FilesChanged {
                 braille_rules: false,
                 braille_unicode_short: false,
                 braille_unicode_full: false,
-                intent: false,
+                intent: true,       // the subject area intent rules depend upon the language having speech for them
                 defs: true,
                 navigate_rules: true,
                 overview_rules: true,
@@ -392,6 +522,11 @@ impl FilesChanged {
                 speech_rules: true, 
                 ..Default::default()
             }),
+            "SubjectArea" => Some( FilesChanged {
+                intent: true,
+                speech_rules: true,
+                ..Default::default()
+            }),
             "BrailleCode" => Some( FilesChanged {
                 braille_rules: true, 
                 braille_unicode_short: true, 
@@ -429,6 +564,9 @@ impl PreferenceManager {
         } else {
             pref_files = None;
         }
+        if let Err(e) = self.speech_overrides.update() {
+            error!("{}", errors_to_string(&e));
+        }
 
         match PreferenceManager::find_rules_dir(&rules_dir) {
             Ok(rules_dir) => {
@@ -507,9 +645,32 @@ impl PreferenceManager {
             &speech_rules_dir, language, Some("en"), "intent.yaml", &mut self.intent)?;
         PreferenceManager::set_file_and_time(
             &speech_rules_dir, language, Some("en"), "definitions.yaml", &mut self.defs)?;
+
+        // the intent rules are language independent, but the speech for them is not, so don't fall back to English speech
+        // the intents are only useful if they can be spoken, so the intent rules are only used if the language has the speech rules
+        let subject_area = self.subject_area();
+        PreferenceManager::set_subject_area_file_and_time(
+            &speech_rules_dir, language, None, &format!("SubjectAreas/{}.yaml", subject_area), &mut self.speech_subject_area);
+        if self.speech_subject_area.is_valid() {
+            PreferenceManager::set_subject_area_file_and_time(
+                &speech_rules_dir, language, Some("en"), &format!("Intent/SubjectAreas/{}.yaml", subject_area), &mut self.intent_subject_area);
+        } else {
+            self.intent_subject_area.invalidate();
+        }
         return Ok(());
     }
 
+    /// Like [`PreferenceManager::set_file_and_time`], but for the optional subject area rule files.
+    /// Most subject areas (and languages) don't have their own rules, so not finding the file is not an error.
+    fn set_subject_area_file_and_time(rules_dir: &Path, lang: &str, default_lang: Option<&str>, file_name: &str, file_and_time: &mut FileAndTime) {
+        if file_and_time.is_valid() || file_name.ends_with("/General.yaml") {
+            return;
+        }
+        if let Err(e) = PreferenceManager::set_file_and_time(rules_dir, lang, default_lang, file_name, file_and_time) {
+            debug!("No subject area rules: {}", errors_to_string(&e));
+        }
+    }
+
 
     fn set_file_and_time(rules_dir: &Path, lang: &str, default_lang: Option<&str>, file_name: &str, file_and_time: &mut FileAndTime) -> Result<()> {
         if file_and_time.is_valid() {
@@ -644,15 +805,18 @@ impl PreferenceManager {
         if !PreferenceManager::is_file_up_to_date(&self.pref_files) {
             self.invalidate_old_prefs()?
         }
+        self.speech_overrides.update()?;
 
         let files_changed = FilesChanged {
-            speech_rules: !PreferenceManager::is_file_up_to_date(&self.speech),
+            speech_rules: !PreferenceManager::is_file_up_to_date(&self.speech) ||
+                          !PreferenceManager::is_optional_file_up_to_date(&self.speech_subject_area),
             speech_unicode_short: !PreferenceManager::is_file_up_to_date(&self.speech_unicode),
             speech_unicode_full: !PreferenceManager::is_file_up_to_date(&self.speech_unicode_full),
             braille_rules: !PreferenceManager::is_file_up_to_date(&self.braille),
             braille_unicode_short: !PreferenceManager::is_file_up_to_date(&self.braille_unicode),
             braille_unicode_full: !PreferenceManager::is_file_up_to_date(&self.braille_unicode_full),
-            intent: !PreferenceManager::is_file_up_to_date(&self.intent),
+            intent: !PreferenceManager::is_file_up_to_date(&self.intent) ||
+                    !PreferenceManager::is_optional_file_up_to_date(&self.intent_subject_area),
             defs: !PreferenceManager::is_file_up_to_date(&self.defs),
             navigate_rules: !PreferenceManager::is_file_up_to_date(&self.navigation),
             overview_rules: !PreferenceManager::is_file_up_to_date(&self.overview),
@@ -679,6 +843,7 @@ impl PreferenceManager {
         let old_language = self.user_prefs.prefs.get("Language").unwrap().as_str().unwrap().to_string();
         let old_style = self.user_prefs.prefs.get("SpeechStyle").unwrap().as_str().unwrap().to_string();
         let old_braille = self.user_prefs.prefs.get("BrailleCode").unwrap().as_str().unwrap().to_string();
+        let old_subject_area = self.subject_area();
         (self.user_prefs, self.pref_files) = Preferences::from_file(self.rules_dir.as_ref().unwrap().as_path())?;
 
         let new_language = self.user_prefs.prefs.get("Language").unwrap().as_str().unwrap().to_string();
@@ -693,9 +858,17 @@ impl PreferenceManager {
         if old_braille != new_braille {
             self.invalidate(&FilesChanged::new("BrailleCode").unwrap());
         }
+        if old_subject_area != self.subject_area() {
+            self.invalidate(&FilesChanged::new("SubjectArea").unwrap());
+        }
         return Ok( () )
     }
 
+    /// An optional file that wasn't found is up to date
+    fn is_optional_file_up_to_date(ft: &FileAndTime) -> bool {
+        return !ft.is_valid() || PreferenceManager::is_file_up_to_date(ft);
+    }
+
     fn is_file_up_to_date(ft: &FileAndTime) -> bool {
         return  ft.is_valid() &&
                 is_older(&ft.files[0], ft.times[0]) &&
@@ -722,6 +895,7 @@ impl PreferenceManager {
     pub fn invalidate(&mut self, files_changed: &FilesChanged) {
         if files_changed.speech_rules {
             self.speech.invalidate();
+            self.speech_subject_area.invalidate();
         }
         if files_changed.speech_unicode_short {
             self.speech_unicode.invalidate();
@@ -740,6 +914,7 @@ impl PreferenceManager {
         }
         if files_changed.intent {
             self.intent.invalidate();
+            self.intent_subject_area.invalidate();
         }
         if files_changed.defs {
             self.defs.invalidate();
@@ -767,6 +942,32 @@ impl PreferenceManager {
         };
     }
 
+    /// Return the subject area rule file locations (if any) that extend the rules given by [`PreferenceManager::get_rule_file`].
+    pub fn get_subject_area_file(&self, name: &RulesFor) -> Option<&Locations> {
+        let file_and_time = match name {
+            RulesFor::Intent => &self.intent_subject_area,
+            RulesFor::Speech => &self.speech_subject_area,
+            _ => return None,
+        };
+        return if file_and_time.is_valid() {Some(&file_and_time.files)} else {None};
+    }
+
+    /// The subject area is given by the 'SubjectArea' pref unless the current expression overrides it.
+    pub fn subject_area(&self) -> String {
+        return match &self.expr_subject_area {
+            Some(subject_area) => subject_area.clone(),
+            None => self.pref_to_string("SubjectArea"),
+        };
+    }
+
+    /// Set (or clear with `None`) the subject area given by the current expression.
+    /// Returns true if that changed the subject area in use (and hence the rules to use).
+    pub fn set_expr_subject_area(&mut self, subject_area: Option<&str>) -> bool {
+        let old_subject_area = self.subject_area();
+        self.expr_subject_area = subject_area.map(|s| s.to_string());
+        return old_subject_area != self.subject_area();
+    }
+
     /// Return the unicode.yaml file locations.
     pub fn get_speech_unicode_file(&self) ->(PathBuf, PathBuf) {
         if !self.error.is_empty() {
@@ -823,7 +1024,10 @@ impl PreferenceManager {
         return match self.pref_to_string("TTS").as_str().to_ascii_lowercase().as_str() {
             "none" => TTS::None,
             "ssml" => TTS::SSML,
+            "ssmldocument" => TTS::SSMLDocument,
             "sapi5" => TTS::SAPI5,
+            "eloquence" => TTS::Eloquence,
+            "mac" => TTS::Mac,
             _ => {
                 warn!("found unknown value for TTS: '{}'", self.pref_to_string("TTS").as_str());
                 TTS::None
@@ -897,6 +1101,52 @@ impl PreferenceManager {
         }
     }
 
+    /// Return the user's speech override for 'text' in the current language (if any).
+    /// The `SpeechOverrides_LeftParen` and `SpeechOverrides_RightParen` prefs are used if there is no entry for a paren.
+    pub fn get_speech_override(&self, text: &str) -> Option<ReplacementArray> {
+        if let Some(replacements) = self.speech_overrides.get(&self.current_language(), text) {
+            return Some(replacements.clone());
+        }
+        let paren_pref = match text {
+            "(" => "SpeechOverrides_LeftParen",
+            ")" => "SpeechOverrides_RightParen",
+            _ => return None,
+        };
+        let word = self.pref_to_string(paren_pref);
+        if word.is_empty() || word == NO_PREFERENCE {
+            return None;
+        }
+        return SpeechOverrides::build_replacements(&Yaml::String(word)).ok();
+    }
+
+    /// Add (or replace) a speech override for 'text' in the current language.
+    /// 'speech' is either the words to speak or a YAML list of replacements (as in `unicode.yaml`).
+    pub fn set_speech_override(&mut self, text: &str, speech: &str) -> Result<()> {
+        if text.is_empty() {
+            bail!("The text to override can not be empty");
+        }
+        let yaml = match YamlLoader::load_from_str(speech) {
+            Ok(docs) if docs.len() == 1 && (docs[0].is_array() || docs[0].as_hash().is_some()) => docs[0].clone(),
+            _ => Yaml::String(speech.to_string()),
+        };
+        let replacements = SpeechOverrides::build_replacements(&yaml)
+                .chain_err(|| format!("in speech override '{}' for '{}'", speech, text))?;
+        self.speech_overrides.api.entry(self.current_language()).or_default()
+                .insert(text.to_string(), Some(replacements));
+        return Ok( () );
+    }
+
+    /// Remove the speech override for 'text' in the current language (including one from the user's overrides file).
+    pub fn remove_speech_override(&mut self, text: &str) {
+        self.speech_overrides.api.entry(self.current_language()).or_default()
+                .insert(text.to_string(), None);
+    }
+
+    fn current_language(&self) -> String {
+        let language = self.pref_to_string("Language");
+        return if language == "Auto" {"en".to_string()} else {language};
+    }
+
     // occasionally useful to check a pref value when debugging
     // fn get_pref(&self, pref_name: &str) -> String {
     //     return yaml_to_string(self.user_prefs.prefs.get(pref_name).unwrap(), 1);
@@ -957,6 +1207,46 @@ mod tests {
         });
     }
 
+    #[test]
+    fn find_subject_area_files() {
+        PREF_MANAGER.with(|pref_manager| {
+            let mut pref_manager = pref_manager.borrow_mut();
+            pref_manager.initialize(abs_rules_dir_path()).unwrap();
+            pref_manager.set_user_prefs("Language", "en");
+            pref_manager.set_user_prefs("SubjectArea", "Calculus");
+            pref_manager.invalidate(&FilesChanged::new("SubjectArea").unwrap());
+            pref_manager.initialize(PathBuf::new()).unwrap();
+            assert_eq!(rel_path(&pref_manager.rules_dir, &pref_manager.intent_subject_area.files[0]), PathBuf::from("Intent/SubjectAreas/Calculus.yaml"));
+            assert_eq!(rel_path(&pref_manager.rules_dir, &pref_manager.speech_subject_area.files[0]), PathBuf::from("Languages/en/SubjectAreas/Calculus.yaml"));
+
+            // the expression's subject area overrides the pref
+            assert!(pref_manager.set_expr_subject_area(Some("Statistics")));
+            pref_manager.invalidate(&FilesChanged::new("SubjectArea").unwrap());
+            pref_manager.initialize(PathBuf::new()).unwrap();
+            assert_eq!(rel_path(&pref_manager.rules_dir, &pref_manager.intent_subject_area.files[0]), PathBuf::from("Intent/SubjectAreas/Statistics.yaml"));
+            assert!(!pref_manager.set_expr_subject_area(Some("Statistics")));
+
+            // no rules for the subject area is not an error
+            assert!(pref_manager.set_expr_subject_area(Some("Astronomy")));
+            pref_manager.invalidate(&FilesChanged::new("SubjectArea").unwrap());
+            pref_manager.initialize(PathBuf::new()).unwrap();
+            assert!(pref_manager.get_subject_area_file(&RulesFor::Intent).is_none());
+            assert!(pref_manager.get_subject_area_file(&RulesFor::Speech).is_none());
+
+            // the intent rules aren't used for a language that can't speak them
+            assert!(pref_manager.set_expr_subject_area(Some("Calculus")));
+            pref_manager.set_user_prefs("Language", "es");
+            pref_manager.invalidate(&FilesChanged::new("Language").unwrap());
+            pref_manager.initialize(PathBuf::new()).unwrap();
+            assert!(pref_manager.get_subject_area_file(&RulesFor::Intent).is_none());
+            assert!(pref_manager.get_subject_area_file(&RulesFor::Speech).is_none());
+            pref_manager.set_user_prefs("Language", "en");
+            pref_manager.invalidate(&FilesChanged::new("Language").unwrap());
+            pref_manager.initialize(PathBuf::new()).unwrap();
+            assert!(pref_manager.get_subject_area_file(&RulesFor::Intent).is_some());
+        });
+    }
+
     #[test]
     fn find_style_other_language() {
         PREF_MANAGER.with(|pref_manager| {
@@ -1183,4 +1473,34 @@ mod tests {
             // open the file, read all the contents, then write them back so the time changes
         });
     }
+
+    #[test]
+    fn test_speech_overrides() {
+        let file_contents = r#"
+en:
+  "≤": "at most"
+  "mod": [t: "modulo"]
+  "bad": [not_a_replacement: "x"]
+en-gb:
+  "mod": "mod"
+"#;
+        let user = SpeechOverrides::from_str(file_contents, "test").unwrap();
+        assert_eq!(user.len(), 2);
+        assert!(!user["en"].contains_key("bad"), "bad entry should have been skipped");
+        let mut overrides = SpeechOverrides { user, ..Default::default() };
+        assert_eq!(overrides.get("en", "≤").unwrap().to_string().trim(), "[t: \"at most\"]");
+        assert_eq!(overrides.get("en-gb", "≤").unwrap().to_string().trim(), "[t: \"at most\"]");
+        assert_eq!(overrides.get("en-gb", "mod").unwrap().to_string().trim(), "[t: \"mod\"]");
+        assert_eq!(overrides.get("en", "mod").unwrap().to_string().trim(), "[t: \"modulo\"]");
+        assert!(overrides.get("es", "≤").is_none());
+
+        // api values take precedence over those in the file and can remove them
+        overrides.api.entry("en".to_string()).or_default()
+                .insert("mod".to_string(), Some(SpeechOverrides::build_replacements(&Yaml::String("remainder".to_string())).unwrap()));
+        overrides.api.entry("en".to_string()).or_default().insert("≤".to_string(), None);
+        assert_eq!(overrides.get("en", "mod").unwrap().to_string().trim(), "[t: \"remainder\"]");
+        assert!(overrides.get("en", "≤").is_none());
+
+        assert!(SpeechOverrides::from_str("- not a dictionary", "test").is_err());
+    }
 }
@@ -0,0 +1,299 @@
+//! Keeps track of which nodes in the MathML given to [`crate::set_mathml`] each node in the canonical MathML came from.
+//!
+//! Before canonicalization, every element is marked with a [`SOURCE_REF_ATTR`] attribute whose value is the index
+//! of the element in a list of the original elements (see [`mark_sources`]).
+//! Most of canonicalization renames, moves, or wraps elements, so the attribute comes along for free.
+//! The places that merge leaves (e.g., number blocks) call [`merge_sources`] and the places that split a leaf
+//! (e.g., "ABC" after "∠") call [`split_sources`].
+//! After the ids are added, [`build_source_map`] turns the attributes into a [`SourceMap`] and removes them.
+//!
+//! The attribute value is a space separated list of entries; an entry is either `index` (the whole original element)
+//! or `index:start-end` (the characters `[start, end)` of the original leaf's text).
+#![allow(clippy::needless_return)]
+
+use sxd_document::dom::*;
+use crate::canonicalize::{as_element, as_text};
+use crate::xpath_functions::is_leaf;
+
+/// The attribute used to record the original element(s) during canonicalization
+pub const SOURCE_REF_ATTR: &str = "data-source-ref";
+
+/// A reference to a node in the MathML that was passed to [`crate::set_mathml`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRef {
+    /// The `id` of the original node (if it had one)
+    pub id: Option<String>,
+    /// The position of the original node: the index of each element (ignoring text and comments) on the path down from
+    /// the `math` element. The `math` element itself has an empty path.
+    pub path: Vec<usize>,
+    /// If the canonical node came from only part of an original leaf, the `[start, end)` range of *characters* (not bytes)
+    /// of the leaf's text; `None` means the whole node.
+    pub chars: Option<(usize, usize)>,
+}
+
+/// The `(id, sources)` entries returned by [`crate::set_mathml_with_source_map`], in document order.
+/// `id` is the id of a node in the canonical MathML and `sources` are the original nodes it was derived from.
+pub type SourceMap = Vec<(String, Vec<SourceRef>)>;
+
+/// Mark every element in `mathml` with [`SOURCE_REF_ATTR`].
+/// Returns the original elements, indexed by the value of the attribute.
+pub fn mark_sources(mathml: Element) -> Vec<SourceRef> {
+    let mut originals = Vec::new();
+    mark(mathml, &mut Vec::new(), &mut originals);
+    return originals;
+
+    fn mark(mathml: Element, path: &mut Vec<usize>, originals: &mut Vec<SourceRef>) {
+        mathml.set_attribute_value(SOURCE_REF_ATTR, &originals.len().to_string());
+        originals.push( SourceRef {
+            id: mathml.attribute_value("id").map(|id| id.to_string()),
+            path: path.clone(),
+            chars: None,
+        });
+        if is_leaf(mathml) {
+            return;     // anything inside of a leaf (e.g., HTML in mtext) becomes part of the text
+        }
+        let children = mathml.children().into_iter().filter_map(|child| child.element());
+        for (i, child) in children.enumerate() {
+            path.push(i);
+            mark(child, path, originals);
+            path.pop();
+        }
+    }
+}
+
+/// `target` has absorbed `others` (e.g., "1" "," "000" became the number "1,000").
+/// `target`'s sources are extended with those of `others`.
+pub fn merge_sources(target: Element, others: &[Element]) {
+    let mut entries = entries(target);
+    for &other in others {
+        for entry in self::entries(other) {
+            if !entries.contains(&entry) {
+                entries.push(entry);
+            }
+        }
+    }
+    set_entries(target, entries);
+}
+
+/// The leaf `leaf` is being split into `parts` (e.g., "ABC" into "A" "B" "C" or "-2" into "-" "2").
+/// This must be called before the leaf's text changes.
+/// Any of `parts` that don't already have a source are given the part of `leaf`'s text they came from.
+/// If the characters don't line up, each part gets all of `leaf`'s sources.
+pub fn split_sources(leaf: Element, parts: &[Element]) {
+    if !is_leaf(leaf) {
+        return;
+    }
+    let entries = entries(leaf);
+    if entries.is_empty() {
+        return;
+    }
+    let part_lengths = parts.iter()
+            .map(|&part| if is_leaf(part) {as_text(part).chars().count()} else {0})
+            .collect::<Vec<usize>>();
+    let total_length: usize = part_lengths.iter().sum();
+    let leaf_length = as_text(leaf).chars().count();
+
+    if entries.len() == 1 && total_length == leaf_length && !part_lengths.contains(&0) {
+        // the usual case -- the leaf came from one original node
+        let (index, range) = parse_entry(&entries[0]);
+        let mut start = range.map_or(0, |(start, _)| start);
+        for (&part, &length) in parts.iter().zip(&part_lengths) {
+            if part.attribute(SOURCE_REF_ATTR).is_none() {
+                part.set_attribute_value(SOURCE_REF_ATTR, &format!("{}:{}-{}", index, start, start + length));
+            }
+            start += length;
+        }
+    } else if entries.len() == parts.len() {
+        // undoing a merge (e.g., chemistry merges "H" "g" into "Hg" and then decides that was wrong)
+        for (&part, entry) in parts.iter().zip(entries) {
+            if part.attribute(SOURCE_REF_ATTR).is_none() {
+                part.set_attribute_value(SOURCE_REF_ATTR, &entry);
+            }
+        }
+    } else {
+        let value = entries.join(" ");
+        for &part in parts {
+            if part.attribute(SOURCE_REF_ATTR).is_none() {
+                part.set_attribute_value(SOURCE_REF_ATTR, &value);
+            }
+        }
+    }
+}
+
+/// Build the map from each id in `mathml` (which must already have ids) to the original nodes in `originals`.
+/// [`SOURCE_REF_ATTR`] is removed from all the elements.
+///
+/// A node without a source of its own (e.g., an `mrow` added to group an expression) maps to the union of the sources of its children.
+/// If there are none of those either (e.g., an added invisible times), the node maps to the sources of its closest ancestor that has some.
+pub fn build_source_map(mathml: Element, originals: &[SourceRef]) -> SourceMap {
+    let mut map = SourceMap::new();
+    let mut parents = Vec::new();
+    gather(mathml, originals, None, &mut map, &mut parents);
+    for i in 0..map.len() {
+        if map[i].1.is_empty() {
+            if let Some(parent) = parents[i] {
+                map[i].1 = map[parent].1.clone();
+            }
+        }
+    }
+    return map;
+
+    fn gather(mathml: Element, originals: &[SourceRef], parent: Option<usize>,
+              map: &mut SourceMap, parents: &mut Vec<Option<usize>>) -> Vec<SourceRef> {
+        let mut sources = entries(mathml).iter()
+                .filter_map(|entry| {
+                    let (index, chars) = parse_entry(entry);
+                    return originals.get(index).map(|original| SourceRef{ chars, ..original.clone() });
+                })
+                .collect::<Vec<SourceRef>>();
+        mathml.remove_attribute(SOURCE_REF_ATTR);
+        let has_own_sources = !sources.is_empty();
+
+        let i_map = map.len();
+        map.push( (mathml.attribute_value("id").unwrap_or_default().to_string(), vec![]) );
+        parents.push(parent);
+        if !is_leaf(mathml) {
+            for child in mathml.children() {
+                let child_sources = gather(as_element(child), originals, Some(i_map), map, parents);
+                if !has_own_sources {
+                    for source in child_sources {
+                        if !sources.contains(&source) {
+                            sources.push(source);
+                        }
+                    }
+                }
+            }
+        }
+        map[i_map].1 = sources.clone();
+        return sources;
+    }
+}
+
+fn entries(mathml: Element) -> Vec<String> {
+    return match mathml.attribute_value(SOURCE_REF_ATTR) {
+        None => vec![],
+        Some(value) => value.split_whitespace().map(|entry| entry.to_string()).collect(),
+    };
+}
+
+fn set_entries(mathml: Element, mut entries: Vec<String>) {
+    if entries.is_empty() {
+        return;
+    }
+    // the original elements are numbered in document order, so sorting puts the merged sources in document order
+    entries.sort_by_key(|entry| {
+        let (index, chars) = parse_entry(entry);
+        (index, chars.map_or(0, |(start, _)| start))
+    });
+    mathml.set_attribute_value(SOURCE_REF_ATTR, &entries.join(" "));
+}
+
+/// Parse "index" or "index:start-end"
+fn parse_entry(entry: &str) -> (usize, Option<(usize, usize)>) {
+    let (index, range) = match entry.split_once(':') {
+        None => (entry, None),
+        Some((index, range)) => (index, range.split_once('-')),
+    };
+    let index = index.parse::<usize>().unwrap_or(usize::MAX);
+    let chars = range.and_then(|(start, end)| Some( (start.parse::<usize>().ok()?, end.parse::<usize>().ok()?) ));
+    return (index, chars);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::*;
+    use sxd_document::parser;
+
+    /// (id, path, chars) of a [`SourceRef`]
+    type Source = (Option<String>, Vec<usize>, Option<(usize, usize)>);
+
+    /// Returns the (leaf text, sources) for the leaves in the canonical MathML and checks every node is in the map
+    fn leaf_sources(mathml: &str) -> Vec<(String, Vec<Source>)> {
+        set_rules_dir(crate::abs_rules_dir_path()).unwrap();
+        let (canonical, source_map) = set_mathml_with_source_map(mathml.to_string()).unwrap();
+        assert!(!canonical.contains(SOURCE_REF_ATTR), "canonical MathML has source attrs:\n{}", canonical);
+        let package = parser::parse(&canonical).unwrap();
+        trim_element(&get_element(&package));
+        let mut leaves = vec![];
+        let mut n_elements = 0;
+        gather_leaves(get_element(&package), &source_map, &mut n_elements, &mut leaves);
+        assert_eq!(n_elements, source_map.len());
+        return leaves;
+
+        fn gather_leaves(mathml: Element, source_map: &SourceMap, n_elements: &mut usize,
+                         leaves: &mut Vec<(String, Vec<Source>)>) {
+            let id = mathml.attribute_value("id").unwrap();
+            let (map_id, sources) = &source_map[*n_elements];
+            assert_eq!(id, map_id);
+            *n_elements += 1;
+            if is_leaf(mathml) {
+                let sources = sources.iter().map(|s| (s.id.clone(), s.path.clone(), s.chars)).collect();
+                leaves.push( (as_text(mathml).to_string(), sources) );
+            } else {
+                for child in mathml.children() {
+                    gather_leaves(as_element(child), source_map, n_elements, leaves);
+                }
+            }
+        }
+    }
+
+    fn source(id: Option<&str>, path: &[usize], chars: Option<(usize, usize)>) -> Source {
+        return (id.map(|id| id.to_string()), path.to_vec(), chars);
+    }
+
+    #[test]
+    fn source_map_merged_number() {
+        let expr = "<math><mn id='a'>1</mn><mo id='b'>,</mo><mn id='c'>000</mn><mo>+</mo><mi id='x'>x</mi></math>";
+        assert_eq!(leaf_sources(expr), vec![
+            ("1,000".to_string(), vec![source(Some("a"), &[0], None), source(Some("b"), &[1], None), source(Some("c"), &[2], None)]),
+            ("+".to_string(), vec![source(None, &[3], None)]),
+            ("x".to_string(), vec![source(Some("x"), &[4], None)]),
+        ]);
+    }
+
+    #[test]
+    fn source_map_split_leaf() {
+        let expr = "<math><mo>∠</mo><mi id='abc'>ABC</mi></math>";
+        assert_eq!(leaf_sources(expr), vec![
+            ("∠".to_string(), vec![source(None, &[0], None)]),
+            ("A".to_string(), vec![source(Some("abc"), &[1], Some((0, 1)))]),
+            ("\u{2063}".to_string(), vec![source(Some("abc"), &[1], None)]),
+            ("B".to_string(), vec![source(Some("abc"), &[1], Some((1, 2)))]),
+            ("\u{2063}".to_string(), vec![source(Some("abc"), &[1], None)]),
+            ("C".to_string(), vec![source(Some("abc"), &[1], Some((2, 3)))]),
+        ]);
+    }
+
+    #[test]
+    fn source_map_negative_number() {
+        let expr = "<math><mn id='n'>-2</mn></math>";
+        assert_eq!(leaf_sources(expr), vec![
+            ("-".to_string(), vec![source(Some("n"), &[0], Some((0, 1)))]),
+            ("2".to_string(), vec![source(Some("n"), &[0], Some((1, 2)))]),
+        ]);
+    }
+
+    #[test]
+    fn source_map_added_nodes() {
+        // the added mrow and invisible times come from both the 'mn' and the 'mfenced'; the mfenced's parens come from the mfenced
+        let expr = "<math><mn id='a'>2</mn><mfenced id='f'><mi id='b'>b</mi></mfenced></math>";
+        assert_eq!(leaf_sources(expr), vec![
+            ("2".to_string(), vec![source(Some("a"), &[0], None)]),
+            ("\u{2062}".to_string(), vec![source(Some("a"), &[0], None), source(Some("f"), &[1], None)]),
+            ("(".to_string(), vec![source(Some("f"), &[1], None)]),
+            ("b".to_string(), vec![source(Some("b"), &[1, 0], None)]),
+            (")".to_string(), vec![source(Some("f"), &[1], None)]),
+        ]);
+    }
+
+    #[test]
+    fn source_map_chemistry() {
+        let expr = "<math><mi>NaCl</mi></math>";
+        assert_eq!(leaf_sources(expr), vec![
+            ("Na".to_string(), vec![source(None, &[0], Some((0, 2)))]),
+            ("\u{2063}".to_string(), vec![source(None, &[0], Some((0, 2))), source(None, &[0], Some((2, 4)))]),
+            ("Cl".to_string(), vec![source(None, &[0], Some((2, 4)))]),
+        ]);
+    }
+}
@@ -20,6 +20,7 @@ use crate::pretty_print::{mml_to_string, yaml_to_string};
 use std::path::Path;
 use std::rc::Rc;
 use crate::shim_filesystem::read_to_string_shim;
+use regex::Regex;
 use crate::canonicalize::{as_element, create_mathml_element, set_mathml_name, name};
 
 pub const NAV_NODE_SPEECH_NOT_FOUND: &str = "NAV_NODE_NOT_FOUND";
@@ -42,11 +43,39 @@ pub fn intent_from_mathml<'m>(mathml: Element, doc: Document<'m>) -> Result<Elem
 }
 
 pub fn speak_mathml(mathml: Element, nav_node_id: &str) -> Result<String> {
-    return speak_rules(&SPEECH_RULES, mathml, nav_node_id);
+    return speak_rules(&SPEECH_RULES, mathml, nav_node_id, false);
 }
 
+/// Speak the MathML, surrounding the speech for each node that has an id with (internal) sync marks.
+/// The result is only useful as an argument to [`sync_map`].
+pub fn speak_mathml_with_sync_marks(mathml: Element) -> Result<String> {
+    return speak_rules(&SPEECH_RULES, mathml, "", true);
+}
+
+/// Give an overview of the MathML (or of the node with id `nav_node_id` if it is not empty).
+/// The overview is generated from the intent tree so that large subexpressions can be named by what they are.
 pub fn overview_mathml(mathml: Element, nav_node_id: &str) -> Result<String> {
-    return speak_rules(&OVERVIEW_RULES, mathml, nav_node_id);
+    let new_package = Package::new();
+    let doc = new_package.as_document();
+    let intent = intent_from_mathml(mathml, doc)?;
+    if nav_node_id.is_empty() {
+        return speak_rules(&OVERVIEW_RULES, intent, "", false);
+    }
+
+    match crate::navigate::get_node_by_id(intent, nav_node_id) {
+        Some(node) => {
+            // The intent tree is a copy, so we can move the node to its own 'math' element.
+            // That way the overview rules see it as the top of the expression.
+            let math = create_mathml_element(&doc, "math");
+            math.append_child(node);
+            return speak_rules(&OVERVIEW_RULES, math, "", false);
+        },
+        None => {
+            // something like the '3' in 'x^3' that isn't part of the intent tree -- it is almost certainly trivial
+            let node = crate::navigate::get_node_by_id(mathml, nav_node_id).unwrap_or(mathml);
+            return speak_rules(&OVERVIEW_RULES, node, "", false);
+        }
+    }
 }
 
 
@@ -70,7 +99,8 @@ fn intent_rules<'m>(rules: &'static std::thread::LocalKey<RefCell<SpeechRules>>,
 
 /// Speak the MathML
 /// If 'nav_node_id' is not an empty string, then the element with that id will have [[...]] around it
-fn speak_rules(rules: &'static std::thread::LocalKey<RefCell<SpeechRules>>, mathml: Element, nav_node_id: &str) -> Result<String> {
+/// If 'sync_marks' is true, then the speech for each element with an id is surrounded by sync marks
+fn speak_rules(rules: &'static std::thread::LocalKey<RefCell<SpeechRules>>, mathml: Element, nav_node_id: &str, sync_marks: bool) -> Result<String> {
     SpeechRules::update()?;
     rules.with(|rules| {
         rules.borrow_mut().read_files()?;
@@ -78,8 +108,17 @@ fn speak_rules(rules: &'static std::thread::LocalKey<RefCell<SpeechRules>>, math
         // debug!("speak_rules:\n{}", mml_to_string(&mathml));
         let new_package = Package::new();
         let mut rules_with_context = SpeechRulesWithContext::new(&rules, new_package.as_document(), nav_node_id);
+        rules_with_context.sync_marks = sync_marks;
         let mut speech_string = rules_with_context.match_pattern::<String>(mathml)
                     .chain_err(|| "Pattern match/replacement failure!")?;
+        if sync_marks {
+            // the marks can come between the space and the CONCAT_INDICATOR -- keep the marks, but remove the space
+            lazy_static! {
+                static ref SPACE_MARKS_CONCAT: Regex =
+                    Regex::new("[ ]((?:\u{F8F0}[^\u{F8F1}]*\u{F8F1}|\u{F8F2})*)\u{F8FE}").unwrap();
+            }
+            speech_string = SPACE_MARKS_CONCAT.replace_all(&speech_string, "$1").to_string();
+        }
         if !nav_node_id.is_empty() {
             // See https://github.com/NSoiffer/MathCAT/issues/174 for why we can just start the speech at the nav node
             if let Some(start) = speech_string.find("[[") {
@@ -177,6 +216,13 @@ pub const CONCAT_INDICATOR: &str = "\u{F8FE}";
 // This is the pattern that needs to be matched (and deleted)
 pub const CONCAT_STRING: &str = " \u{F8FE}";
 
+// When building a sync map (see `sync_map`), the speech for each node with an id is surrounded by
+//   SYNC_START id SYNC_ID_END ... SYNC_END
+// These are removed before the speech is returned.
+const SYNC_START: char = '\u{F8F0}';
+const SYNC_ID_END: char = '\u{F8F1}';
+const SYNC_END: char = '\u{F8F2}';
+
 // a similar hack to potentially delete (repetitive) optional replacements
 // the OPTIONAL_INDICATOR is added by "ot:" before and after the optional string
 const OPTIONAL_INDICATOR: &str  = "\u{F8FD}";
@@ -186,6 +232,102 @@ pub fn remove_optional_indicators(str: &str) -> String {
     return str.replace(OPTIONAL_INDICATOR, "");
 }
 
+/// Remove the sync marks (if any) from 'str'
+fn remove_sync_marks(str: &str) -> String {
+    if !str.contains(SYNC_END) {
+        return str.to_string();
+    }
+    let mut result = String::with_capacity(str.len());
+    let mut in_id = false;
+    for ch in str.chars() {
+        match ch {
+            SYNC_START => in_id = true,
+            SYNC_ID_END => in_id = false,
+            SYNC_END => (),
+            _ => if !in_id {result.push(ch)},
+        }
+    }
+    return result;
+}
+
+/// Returns the speech (without the sync marks) along with a list of (start, end, id) for its words.
+/// 'marked_speech' is speech generated with sync marks (see [`speak_mathml_with_sync_marks`]).
+/// The marks can keep pauses from being merged, so the speech is the marked speech with the marks removed and the pauses merged;
+///   this is the same as the speech generated without the marks.
+/// [start, end) is the range of *characters* (not bytes) of the word in the speech and 'id' is the id of the node that generated it.
+/// TTS markup and the punctuation used for pauses are not part of a word.
+/// Words that don't come from a node with an id (rare) are not in the list.
+pub fn sync_map(marked_speech: &str, pref_manager: &PreferenceManager) -> Result<(String, crate::interface::SyncMap)> {
+    let tts = pref_manager.get_tts();
+    let speech = tts.wrap_speech(tts.merge_pauses(remove_sync_marks(marked_speech).trim()), pref_manager);
+    let marked_speech = tts.wrap_speech(marked_speech.to_string(), pref_manager);
+    let words = sync_words(&speech, &tts);
+    let marked_words = sync_words(&marked_speech, &tts);
+    if words.len() != marked_words.len() {
+        bail!("Internal error: the speech has {} words but the marked speech has {} words\nspeech: '{}'\nmarked: '{}'",
+              words.len(), marked_words.len(), speech, remove_sync_marks(&marked_speech));
+    }
+    let map = words.into_iter()
+                .zip(marked_words)
+                .filter_map(|((start, end, _), (_, _, id))| id.map(|id| (start, end, id)))
+                .collect();
+    return Ok( (speech, map) );
+}
+
+/// Split 'speech' into words, returning the character range of each word and the id of the innermost sync mark it is in (if any).
+/// Sync marks don't count as characters.
+fn sync_words(speech: &str, tts: &TTS) -> Vec<(usize, usize, Option<String>)> {
+    let markup: Vec<(usize, usize)> = match tts.markup_regex() {
+        Some(regex) => regex.find_iter(speech).map(|m| (m.start(), m.end())).collect(),
+        None => vec![],
+    };
+    let mut markup = markup.into_iter().peekable();
+    let mut words = Vec::new();
+    let mut ids: Vec<String> = Vec::new();
+    let mut word: Option<(usize, String, Option<String>)> = None;     // start, text, id
+    let mut i_char = 0;
+    let mut chars = speech.char_indices();
+    while let Some((i_byte, ch)) = chars.next() {
+        if ch == SYNC_START {
+            ids.push( chars.by_ref().map(|(_, ch)| ch).take_while(|&ch| ch != SYNC_ID_END).collect() );
+            continue;
+        } else if ch == SYNC_END {
+            ids.pop();
+            continue;
+        }
+        while markup.peek().is_some_and(|&(_, end)| end <= i_byte) {
+            markup.next();
+        }
+        if ch.is_whitespace() || markup.peek().is_some_and(|&(start, _)| start <= i_byte) {
+            if let Some(word) = word.take() {
+                push_word(&mut words, word, i_char);
+            }
+        } else {
+            match &mut word {
+                None => word = Some( (i_char, ch.to_string(), ids.last().cloned()) ),
+                Some((_, text, _)) => text.push(ch),
+            }
+        }
+        i_char += 1;
+    }
+    if let Some(word) = word {
+        push_word(&mut words, word, i_char);
+    }
+    return words;
+
+    fn push_word(words: &mut Vec<(usize, usize, Option<String>)>, word: (usize, String, Option<String>), end: usize) {
+        // pauses are not part of a word (don't strip them if they are in the middle, e.g. "1,000")
+        let (start, text, id) = word;
+        let is_pause = |ch: &char| *ch == ',' || *ch == ';';
+        let n_leading = text.chars().take_while(is_pause).count();
+        if n_leading == text.chars().count() {
+            return;
+        }
+        let n_trailing = text.chars().rev().take_while(is_pause).count();
+        words.push( (start + n_leading, end - n_trailing, id) );
+    }
+}
+
 /// Given a string that should be Yaml, it calls `build_fn` with that string.
 /// The build function/closure should process the Yaml as appropriate and capture any errors and write them to `std_err`.
 pub fn compile_rule<F>(str: &str, mut build_fn: F) -> Result<()> where
@@ -204,7 +346,7 @@ pub fn compile_rule<F>(str: &str, mut build_fn: F) -> Result<()> where
     }
 }
 
-fn process_include<F>(current_file: &Path, new_file_name: &str, mut read_new_file: F) -> Result<()>
+pub fn process_include<F>(current_file: &Path, new_file_name: &str, mut read_new_file: F) -> Result<()>
                     where F: FnMut(&Path) -> Result<()> {
     let parent_path = current_file.parent();
     if parent_path.is_none() {
@@ -231,7 +373,9 @@ pub trait TreeOrString<'c, 'm:'c, T> {
     fn replace<'s:'c, 'r>(ra: &ReplacementArray, rules_with_context: &'r mut SpeechRulesWithContext<'c, 's,'m>, mathml: Element<'c>) -> Result<T>;
     fn replace_nodes<'s:'c, 'r>(rules: &'r mut SpeechRulesWithContext<'c, 's,'m>, nodes: Vec<Node<'c>>, mathml: Element<'c>) -> Result<T>;
     fn highlight_braille(braille: T, highlight_style: String) -> T;
+    fn latex_to_braille(latex: T, table_name: &str) -> T;
     fn mark_nav_speech(speech: T) -> T;
+    fn mark_sync_speech(speech: T, id: &str) -> T;
 }
 
 impl<'c, 'm:'c> TreeOrString<'c, 'm, String> for String {
@@ -259,9 +403,17 @@ impl<'c, 'm:'c> TreeOrString<'c, 'm, String> for String {
         return SpeechRulesWithContext::highlight_braille_string(braille, highlight_style);
     }
 
+    fn latex_to_braille(latex: String, table_name: &str) -> String {
+        return crate::braille::latex_to_braille(&latex, table_name);
+    }
+
     fn mark_nav_speech(speech: String) -> String {
         return SpeechRulesWithContext::mark_nav_speech(speech);
     }
+
+    fn mark_sync_speech(speech: String, id: &str) -> String {
+        return SpeechRulesWithContext::mark_sync_speech(speech, id);
+    }
 }
 
 impl<'c, 'm:'c> TreeOrString<'c, 'm, Element<'m>> for Element<'m> {
@@ -292,9 +444,17 @@ impl<'c, 'm:'c> TreeOrString<'c, 'm, Element<'m>> for Element<'m> {
         panic!("Internal error: highlight_braille called on a tree");
     }
 
+    fn latex_to_braille(_latex: Element<'c>, _table_name: &str) -> Element<'m> {
+        panic!("Internal error: latex_to_braille called on a tree");
+    }
+
     fn mark_nav_speech(_speech: Element<'c>) -> Element<'m> {
         panic!("Internal error: mark_nav_speech called on a tree");
     }
+
+    fn mark_sync_speech(_speech: Element<'c>, _id: &str) -> Element<'m> {
+        panic!("Internal error: mark_sync_speech called on a tree");
+    }
 }
 
 /// 'Replacement' is an enum that contains all the potential replacement types/structs
@@ -553,6 +713,11 @@ impl<'r> Intent {
         } else {
             panic!("Intent::replace: internal error -- neither 'name' nor 'xpath' is set");
         };
+        let n_args = result.children().iter().filter(|child| child.element().is_some()).count();
+        if let Err(message) = crate::xpath_functions::ConceptFixity::check_arity(name(&result), n_args) {
+            // the number of args isn't known until the rule is used -- a mismatch shouldn't prevent the math from being spoken
+            warn!("Error in intent rule: {}", message);
+        }
         
         for attr in mathml.attributes() {
             result.set_attribute_value(attr.name(), attr.value());           
@@ -565,21 +730,22 @@ impl<'r> Intent {
         return T::from_element(result);
 
         /// "lift" up the children any "TEMP_NAME" child -- could short circuit when only one child
+        /// Note: a "TEMP_NAME" child has more than one child when an 'x:' matched several nodes (e.g., "x: '*[2]/*'")
         fn lift_children(result: Element) -> Element {
             // debug!("lift_children:\n{}", mml_to_string(&result));
             result.replace_children(
                 result.children().iter()
-                    .map(|&child_of_element| {
+                    .flat_map(|&child_of_element| {
                         match child_of_element {
                             ChildOfElement::Element(child) => {
                                 if name(&child) == "TEMP_NAME" {
-                                    assert_eq!(child.children().len(), 1);
-                                    child.children()[0]
+                                    assert!(!child.children().is_empty());
+                                    child.children()
                                 } else {
-                                    child_of_element
+                                    vec![child_of_element]
                                 }
                             },
-                            _ => child_of_element,      // text()
+                            _ => vec![child_of_element],      // text()
                         }
                     })
                     .collect::<Vec<ChildOfElement>>()
@@ -781,18 +947,18 @@ impl<'r> ReplacementArray {
         //   but the match is harder (rust regex lacks look behind pattern match) and it is less efficient
         // Note: we skip the first string since it can't be repetitive of something at this level
         for i in 1..replacement_strings.len()-1 {
-            if let Some(bytes) = is_repetitive(&replacement_strings[i-1], &replacement_strings[i])  {
-                replacement_strings[i] = bytes.to_string();
+            if let Some(non_repetitive) = is_repetitive(&replacement_strings[i-1], &replacement_strings[i])  {
+                replacement_strings[i] = non_repetitive;
             } 
         }
                         
         for i in 0..replacement_strings.len() {
             if replacement_strings[i].contains(PAUSE_AUTO_STR) {
-                let before = if i == 0 {""} else {&replacement_strings[i-1]};
-                let after = if i+1 == replacement_strings.len() {""} else {&replacement_strings[i+1]};
+                let before = if i == 0 {"".to_string()} else {remove_sync_marks(&replacement_strings[i-1])};
+                let after = if i+1 == replacement_strings.len() {"".to_string()} else {remove_sync_marks(&replacement_strings[i+1])};
                 replacement_strings[i] = replacement_strings[i].replace(
                     PAUSE_AUTO_STR,
-                    &rules_with_context.speech_rules.pref_manager.borrow().get_tts().compute_auto_pause(&rules_with_context.speech_rules.pref_manager.borrow(), before, after));
+                    &rules_with_context.speech_rules.pref_manager.borrow().get_tts().compute_auto_pause(&rules_with_context.speech_rules.pref_manager.borrow(), &before, &after));
             }
         }
 
@@ -800,7 +966,8 @@ impl<'r> ReplacementArray {
         // concatenation (removal of spaces) is saved for the top level because they otherwise are stripped at the wrong sometimes
         return Ok( replacement_strings.join(" ") );
 
-        fn is_repetitive<'a>(prev: &str, optional: &'a str) -> Option<&'a str> {
+        /// Returns 'optional' without the optional text if the text is repetitive
+        fn is_repetitive(prev: &str, optional: &str) -> Option<String> {
             // OPTIONAL_INDICATOR surrounds the optional text
             // minor optimization -- lots of short strings and the OPTIONAL_INDICATOR takes a few bytes, so skip the check for those strings
             if optional.len() <=  2 * OPTIONAL_INDICATOR_LEN {
@@ -819,10 +986,12 @@ impl<'r> ReplacementArray {
                             let optional_word = &optional_word_start_slice[..end_index];
                             // debug!("check if '{}' is repetitive",  optional_word);
                             // debug!("   prev: '{}', next '{}'", prev, optional);
-                            let prev = prev.trim_end().as_bytes();
+                            let prev = prev.trim_end_matches(|ch: char| ch.is_whitespace() || ch == SYNC_END).as_bytes();
                             if prev.len() > optional_word.len() &&
                                &prev[prev.len()-optional_word.len()..] == optional_word.as_bytes() {
-                                return Some( optional_word_start_slice[optional_word.len() + OPTIONAL_INDICATOR_LEN..].trim_start() );
+                                // keep anything (sync marks) before the optional text
+                                return Some( optional[..start_index].to_string() +
+                                             optional_word_start_slice[optional_word.len() + OPTIONAL_INDICATOR_LEN..].trim_start() );
                             } else {
                                 return None;
                             }
@@ -1085,7 +1254,14 @@ impl<'r> MyXPath {
                     }
                     rules_with_context.replace_nodes(nodes.document_order(), mathml)
                 },
-                Value::String(t) => T::from_string(t, rules_with_context.doc),
+                Value::String(t) => {
+                    // strings are typically intent concept names (e.g., "greatest common divisor") -- the user might override those
+                    if let Some(replacements) = rules_with_context.speech_override(&t)
+                                .or_else(|| rules_with_context.speech_override(&t.replace(' ', "-"))) {
+                        return replacements.replace(rules_with_context, mathml);
+                    }
+                    T::from_string(rules_with_context.escape_text(t), rules_with_context.doc)
+                },
                 Value::Number(num) => T::from_string(num.to_string(), rules_with_context.doc ),
                 Value::Boolean(b) => T::from_string(b.to_string(), rules_with_context.doc ),          // FIX: is this right???
         };
@@ -1860,6 +2036,9 @@ pub struct SpeechRulesWithContext<'c, 's:'c, 'm:'c> {
     nav_node_id: &'m str,
     pub inside_spell: bool,     // hack to allow 'spell' to avoid infinite loop (see 'spell' implementation in tts.rs)
     pub translate_count: usize, // hack to avoid 'translate' infinite loop (see 'spell' implementation in tts.rs)
+    pub braille_nav_highlight: Option<String>,  // overrides the 'BrailleNavHighlight' pref (used to find the nav node's braille)
+    escape_text: bool,          // text needs to be escaped because the TTS engine uses XML markup (SSML)
+    pub sync_marks: bool,       // surround the speech for each node with an id with sync marks (see `sync_map`)
 }
 
 impl<'c, 's:'c, 'm:'c> fmt::Display for SpeechRulesWithContext<'c, 's,'m> {
@@ -1971,10 +2150,15 @@ impl SpeechRules {
     }
 
     pub fn read_files(&mut self) -> Result<()> {
+        // the definitions (e.g., the concept names) change with the language -- this is cheap if the files haven't changed
+        crate::definitions::read_definitions_file(self.pref_manager.borrow_mut().get_definitions_file())?;
         if self.rules.is_empty() {
             let rule_file = self.pref_manager.borrow().get_rule_file(&self.name).clone();
             self.read_patterns(&rule_file)?;
-
+            let subject_area_file = self.pref_manager.borrow().get_subject_area_file(&self.name).cloned();
+            if let Some(subject_area_file) = subject_area_file {
+                self.read_subject_area_patterns(&subject_area_file)?;
+            }
         }
         if self.unicode_short.borrow().is_empty()  {
             self.read_unicode(None, true)?;
@@ -2051,6 +2235,20 @@ impl SpeechRules {
         return Ok(());
     }
 
+    /// Read the rules for a subject area (e.g., calculus) -- these are read after the standard rules.
+    /// A rule with the same name as a standard rule replaces it. Other rules take precedence over the standard rules.
+    fn read_subject_area_patterns(&mut self, path: &Locations) -> Result<()> {
+        let old_lengths: HashMap<String, usize> = self.rules.iter()
+                    .map(|(tag_name, rules)| (tag_name.clone(), rules.len()))
+                    .collect();
+        self.read_patterns(path)?;
+        for (tag_name, rules) in self.rules.iter_mut() {
+            let n_new_rules = rules.len() - old_lengths.get(tag_name).unwrap_or(&0);
+            rules.rotate_right(n_new_rules);   // move the new rules to the front
+        }
+        return Ok( () );
+    }
+
     fn build_speech_patterns(&mut self, patterns: &Yaml, file_name: &Path) -> Result<()> {
         // Rule::SpeechPatternList
         let patterns_vec = patterns.as_vec();
@@ -2114,9 +2312,28 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
             nav_node_id,
             inside_spell: false,
             translate_count: 0,
+            braille_nav_highlight: None,
+            escape_text: matches!(speech_rules.name, RulesFor::Speech | RulesFor::OverView | RulesFor::Navigation) &&
+                         speech_rules.pref_manager.borrow().get_tts().is_xml(),
+            sync_marks: false,
         }
     }
 
+    /// Escape text that is part of the speech if the TTS engine requires it
+    fn escape_text(&self, text: String) -> String {
+        return if self.escape_text {crate::tts::escape_xml(&text)} else {text};
+    }
+
+    /// Return the user's override for how to speak 'text' (see [`PreferenceManager::get_speech_override`]).
+    /// Overrides only apply to speech -- not to braille or to building the intent tree.
+    fn speech_override(&self, text: &str) -> Option<ReplacementArray> {
+        return match self.speech_rules.name {
+            RulesFor::Speech | RulesFor::Navigation | RulesFor::OverView =>
+                self.speech_rules.pref_manager.borrow().get_speech_override(text),
+            RulesFor::Intent | RulesFor::Braille => None,
+        };
+    }
+
     pub fn get_rules(&mut self) -> &SpeechRules {
         return self.speech_rules;
     }
@@ -2183,6 +2400,14 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
                 }
                 return match result {
                     Ok(s) => {
+                        let s = if self.sync_marks {
+                            match mathml.attribute_value("id") {
+                                Some(id) => T::mark_sync_speech(s, id),
+                                None => s,
+                            }
+                        } else {
+                            s
+                        };
                         // for all except braille and navigation, nav_node_id will be an empty string and will not match
                         if self.nav_node_id.is_empty() {
                             Ok( Some(s) )
@@ -2228,7 +2453,16 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
         if let Some(id) = mathml.attribute_value("id") {
             if self.nav_node_id == id {
                 if self.speech_rules.name == RulesFor::Braille {
-                    let highlight_style =  self.speech_rules.pref_manager.borrow().pref_to_string("BrailleNavHighlight");
+                    let pref_manager = self.speech_rules.pref_manager.borrow();
+                    let highlight_style = match &self.braille_nav_highlight {
+                        Some(style) => style.clone(),
+                        None => pref_manager.pref_to_string("BrailleNavHighlight"),
+                    };
+                    if pref_manager.pref_to_string("BrailleCode") == "LaTeX" {
+                        // the LaTeX rules generate ASCII, so it needs to be converted to braille before it can be highlighted
+                        let table_name = pref_manager.pref_to_string("LaTeX_BrailleTable");
+                        return T::highlight_braille(T::latex_to_braille(speech, &table_name), highlight_style);
+                    }
                     return T::highlight_braille(speech, highlight_style);
                 } else {
                     return T::mark_nav_speech(speech)
@@ -2282,7 +2516,7 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
 
         fn add_dots_to_braille_char(ch: char) -> char {
             let as_u32 = ch as u32;
-            if (0x2800..0x28FF).contains(&as_u32) {
+            if (0x2800..=0x28FF).contains(&as_u32) {
                 return unsafe {char::from_u32_unchecked(as_u32 | 0xC0)};
             } else {
                 return ch;
@@ -2295,10 +2529,18 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
         return "[[".to_string() + &speech + "]]";
     }
 
+    fn mark_sync_speech(speech: String, id: &str) -> String {
+        // empty speech is dropped when joining replacements -- marking it would change the spacing
+        if speech.is_empty() {
+            return speech;
+        }
+        return format!("{}{}{}{}{}", SYNC_START, id, SYNC_ID_END, speech, SYNC_END);
+    }
+
     fn replace<T:TreeOrString<'c, 'm, T>>(&'r mut self, replacement: &Replacement, mathml: Element<'c>) -> Result<T> {
         return Ok(
             match replacement {
-                Replacement::Text(t) => T::from_string(t.clone(), self.doc)?,
+                Replacement::Text(t) => T::from_string(self.escape_text(t.clone()), self.doc)?,
                 Replacement::XPath(xpath) => xpath.replace(self, mathml)?,
                 Replacement::TTS(tts) => {
                     T::from_string(
@@ -2392,6 +2634,11 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
     /// Lookup unicode "pronunciation" of char.
     /// Note: TTS is not supported here (not needed and a little less efficient)
     pub fn replace_chars(&'r mut self, str: &str, mathml: Element<'c>) -> Result<String> {
+        if let Some(replacements) = self.speech_override(str) {
+            // the override is for the whole token -- it shouldn't be glued onto the previous word (e.g., by 'pronounce')
+            let speech: String = replacements.replace(self, mathml)?;
+            return Ok( speech.trim_start_matches(CONCAT_INDICATOR).to_string() );
+        }
         let rules = self.speech_rules;
         let mut chars = str.chars();
         // in a string, avoid "a" -> "eigh", "." -> "point", etc
@@ -2402,7 +2649,7 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
                 return replace_single_char(self, ch, mathml)
             } else {
                 // more than one char -- fix up non-breaking space
-                return Ok(str.replace('\u{00A0}', " ").replace(['\u{2061}', '\u{2062}', '\u{2063}', '\u{2064}'], ""));    
+                return Ok( self.escape_text(str.replace('\u{00A0}', " ").replace(['\u{2061}', '\u{2062}', '\u{2063}', '\u{2064}'], "")) );    
             }
         };
 
@@ -2429,7 +2676,7 @@ impl<'c, 's:'c, 'r, 'm:'c> SpeechRulesWithContext<'c, 's,'m> {
                 if replacements.is_none() {
                     // debug!("*** Did not find unicode {} for char '{}'/{:#06x}", rules_with_context.speech_rules.name, ch, ch_as_u32);
                     rules_with_context.translate_count = 0;     // not in loop
-                    return Ok(String::from(ch));   // no replacement, so just return the char and hope for the best
+                    return Ok( rules_with_context.escape_text(String::from(ch)) );   // no replacement, so just return the char and hope for the best
                 }
             };
 
@@ -2536,6 +2783,22 @@ mod tests {
         assert_eq!(result.unwrap(), str);
     }
 
+    #[test]
+    fn test_sync_map() -> Result<()> {
+        crate::interface::set_rules_dir(super::super::abs_rules_dir_path()).unwrap();
+        let pref_manager = crate::prefs::PreferenceManager::get();
+        crate::interface::set_preference("TTS".to_string(), "Eloquence".to_string())?;
+        let marked = format!("{s}a{i}x {s}b{i}`p300 y{e}, z{e} {s}c{i}w{e}", s=SYNC_START, i=SYNC_ID_END, e=SYNC_END);
+        assert_eq!(sync_map(&marked, &pref_manager.borrow())?, ("x `p300 y, z w".to_string(),
+                   vec![(0, 1, "a".to_string()), (8, 9, "b".to_string()), (11, 12, "a".to_string()), (13, 14, "c".to_string())]));
+        crate::interface::set_preference("TTS".to_string(), "None".to_string())?;
+        // the marks keep the pauses from being merged and the leading space from being trimmed
+        let marked = format!("{s}a{i} x,{e}{s}b{i}, y{e}{s}c{i},{e}", s=SYNC_START, i=SYNC_ID_END, e=SYNC_END);
+        assert_eq!(sync_map(&marked, &pref_manager.borrow())?,
+                   ("x; y,".to_string(), vec![(0, 1, "a".to_string()), (3, 4, "b".to_string())]));
+        return Ok( () );
+    }
+
     #[test]
     fn test_debug_no_debug_with_quote() {
         let str = r#"*[2]/*[3][text()='(']"#;
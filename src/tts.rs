@@ -18,10 +18,13 @@
 //! Note: an octave is a doubling of frequency, so pitch change of 100% should turn into +/- 24
 //! ### SSML: Relative pitch
 //! * pitch in hertz (default/current man's voice is about 100hz, woman's 180hz)
+//! * output as a signed relative change (e.g., '+50%') as required by SSML 1.1
 //!
 //! Note: other legal values for SSML are not supported, and all numbers are interpreted as relative changes
 //! ### Eloquence: Absolute pitch (relative pitch not supported by Eloquence)
 //! * Range is 0 - 100.  Guess is that 0 ~= 42hz, 100 ~= 422hz based on supported \"sapi\" values
+//! ### Mac: Relative pitch
+//! * `pbas` is the baseline pitch as a MIDI note number, so +/-12 is an octave
 //! ## Rate (default 180 words/min)
 //! ### SAPI4: Absolute rate
 //! * Number is relative to the default/current rate
@@ -45,6 +48,8 @@
 //!
 //!  Note: this means words/min = 4.18 * Eloquence rate + 66
 //!  So the relative pause rate is 180/computed value
+//! ### Mac: Relative rate
+//! * Rate is in words/min, relative changes are given with a leading '+' or '-'
 //!
 //!
 //! ## Volume (default 100 \[full])
@@ -59,11 +64,29 @@
 //! Note:  other legal values for SSML are not supported, and all numbers are interpreted as relative changes
 //! ### Eloquence: Absolute volume (relative volume not supported by Eloquence)
 //! * Range is 0 - 100
+//! ### Mac: Absolute volume
+//! * Range is 0.0 - 1.0
+//!
+//! Because Eloquence and Mac don't have end tags, the end of a pitch/rate/volume change emits a command that undoes it.
+//! For Eloquence, that is an absolute value, so nested changes are not restored correctly.
+//!
+//! ## SSML documents
+//! `TTS::SSML` produces SSML fragments that the caller is expected to embed in a `<speak>` element.
+//! `TTS::SSMLDocument` uses the same markup, but the result is a complete SSML 1.1 document
+//! (`<speak>` root with the SSML namespace and an `xml:lang` set from the `Language` pref).
+//! For both, text is escaped (`&`, `<`, `>`) and bookmarks are `<mark>` elements.
 //!
 //! ## Pause
 //! * All systems -- pauses are given in milliseconds
 //!
 //! Note: Pauses on output are scaled based on the ratio of the current rate to the default rate (180 wpm)
+//!
+//! ## Audio
+//! The `audio` command's value names the sound to play (e.g., "beep.mp4"); the rule files pick the names.
+//! Its replacement (if any) is a text fallback.
+//! * SSML: `<audio src='name'>fallback</audio>` (the engine speaks the fallback if it can't play the sound)
+//! * SAPI5, Eloquence, Mac: the fallback text (these engines don't support audio in their markup)
+//! * None: the structured cue `[sound:name]` so that the app can play the sound (the fallback is not included)
 #![allow(clippy::needless_return)]
 
 use crate::{errors::*, prefs::PreferenceManager, speech::ReplacementArray};
@@ -84,6 +107,9 @@ const PAUSE_MEDIUM:f64 = 300.0; // ms
 const PAUSE_LONG:f64 = 600.0;   // ms
 const PAUSE_AUTO:f64 = 987654321.5;   // ms -- hopefully unique
 pub const PAUSE_AUTO_STR: &str = "\u{F8FA}\u{F8FA}";
+/// In non-markup speech (TTS::None), audio is output as `[sound:name]`
+pub const AUDIO_CUE_START: &str = "[sound:";
+pub const AUDIO_CUE_END: &str = "]";
 const RATE_FROM_CONTEXT:f64 = 987654321.5;   // hopefully unique
 
 const MAX_TRANSLATE_RECURSION: usize = 5;   // probably never more than three -- prevents infinite loop/stack overflows bugs
@@ -111,6 +137,7 @@ pub struct Pronounce {
     ipa: String,        // ipa 
     sapi5: String,
     eloquence: String,
+    mac: String,
 }
 
 
@@ -135,6 +162,10 @@ impl fmt::Display for Pronounce {
         write!(f, "pronounce: [")?;
         if !self.eloquence.is_empty() {
             write!(f, "{}eloquence: '{}'", comma, self.eloquence)?;
+            comma = ",";
+        }
+        if !self.mac.is_empty() {
+            write!(f, "{}mac: '{}'", comma, self.mac)?;
         }
         return writeln!(f, "]");
     }
@@ -149,6 +180,7 @@ impl Pronounce {
         let mut ipa = "";
         let mut sapi5 = "";
         let mut eloquence = "";
+        let mut mac = "";
         // values should be an array with potential values for Pronounce
         let values = values.as_vec().ok_or_else(||
                                         format!("'pronounce' value '{}' is not an array", yaml_to_type(values)))?;
@@ -165,6 +197,7 @@ impl Pronounce {
                     "ipa" => ipa = as_str_checked(value)?,
                     "sapi5" => sapi5 = as_str_checked(value)?,
                     "eloquence" => eloquence = as_str_checked(value)?,
+                    "mac" => mac = as_str_checked(value)?,
                     _ => bail!("unknown pronounce type: {} with value {}", yaml_to_string(key, 0), yaml_to_string(value, 0)),
                 }
             }
@@ -176,7 +209,8 @@ impl Pronounce {
             text: text.to_string(),
             ipa: ipa.to_string(),
             sapi5: sapi5.to_string(),
-            eloquence: eloquence.to_string()
+            eloquence: eloquence.to_string(),
+            mac: mac.to_string(),
         } );
     
 
@@ -258,9 +292,19 @@ impl TTSCommandRule {
 pub enum TTS {
     None,
     SSML,
+    SSMLDocument,   // SSML wrapped in a <speak> element
     SAPI5,
-//    Eloquence,
-//    Mac,
+    Eloquence,
+    Mac,
+}
+
+/// Escape the characters that can't appear in XML text (`&`, `<`, and `>`).
+/// Attribute values (which are quoted with `'`) also need to have `'` escaped.
+pub fn escape_xml(text: &str) -> String {
+    if !text.contains(['&', '<', '>']) {
+        return text.to_string();
+    }
+    return text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
 }
 
 impl TTS {
@@ -347,13 +391,15 @@ impl TTS {
         // rather than pass a bunch of extra info into the generic handling routines, we just deal with them here
         if command.command == TTSCommand::Bookmark {
             // if we aren't suppose to generate bookmarks, short circuit and just return
-            if prefs.pref_to_string("Bookmark") != "true"{
+            // bookmarks are always generated for low vision so the AT can highlight what is being spoken
+            if prefs.pref_to_string("Bookmark") != "true" && prefs.pref_to_string("Impairment") != "LowVision" {
                 return Ok("".to_string());
             }
             return Ok( match self {
                 TTS::None  => "".to_string(),
-                TTS::SSML => compute_bookmark_element(&command.value, "mark name", rules_with_context, mathml)?,
+                TTS::SSML | TTS::SSMLDocument => compute_bookmark_element(&command.value, "mark name", rules_with_context, mathml)?,
                 TTS::SAPI5 => compute_bookmark_element(&command.value, "bookmark mark", rules_with_context, mathml)?,
+                TTS::Eloquence | TTS::Mac => "".to_string(),   // only numeric index marks are supported, so ids can't be used
             } );
         }
 
@@ -435,11 +481,18 @@ impl TTS {
             return command.replacements.replace::<String>(rules_with_context, mathml);
         }
 
+        if command.command == TTSCommand::Audio && self == &TTS::None {
+            // the cue is all that is needed -- the app is responsible for playing the sound (the text fallback is not wanted)
+            return Ok( self.get_string_none(&command, prefs, true) );
+        }
+
         let mut result = String::with_capacity(255);
         result += &match self {
             TTS::None  => self.get_string_none(&command, prefs, true),
-            TTS::SSML  => self.get_string_ssml(&command, prefs, true),
+            TTS::SSML | TTS::SSMLDocument => self.get_string_ssml(&command, prefs, true),
             TTS::SAPI5 => self.get_string_sapi5(&command, prefs, true),
+            TTS::Eloquence => self.get_string_eloquence(&command, prefs, true),
+            TTS::Mac   => self.get_string_mac(&command, prefs, true),
         };
 
 
@@ -452,8 +505,10 @@ impl TTS {
 
         let end_tag = match self {
             TTS::None  => self.get_string_none(&command, prefs, false),
-            TTS::SSML  => self.get_string_ssml(&command, prefs, false),
+            TTS::SSML | TTS::SSMLDocument => self.get_string_ssml(&command, prefs, false),
             TTS::SAPI5 => self.get_string_sapi5(&command, prefs, false),
+            TTS::Eloquence => self.get_string_eloquence(&command, prefs, false),
+            TTS::Mac   => self.get_string_mac(&command, prefs, false),
         };
 
         if end_tag.is_empty() {
@@ -467,7 +522,7 @@ impl TTS {
             match value {
                 TTSCommandValue::XPath(xpath) => {
                     let id = xpath.replace::<String>(rules_with_context, mathml)?;
-                    return Ok( format!("<{}='{}'/>", tag_and_attr, id) );
+                    return Ok( format!("<{}='{}'/>", tag_and_attr, id.replace('\'', "&apos;")) );
                 },
                 _ => bail!("Implementation error: found bookmark value that did not evaluate to a string"),
             }
@@ -502,6 +557,8 @@ impl TTS {
                 return command.value.get_string().to_string();
             } else if let TTSCommandValue::Pronounce(p) = &command.value {
                 return crate::speech::CONCAT_INDICATOR.to_string() + &p.text;
+            } else if command.command == TTSCommand::Audio {
+                return format!("{}{}{}", AUDIO_CUE_START, command.value.get_string(), AUDIO_CUE_END);
             }
         };
         return "".to_string();
@@ -530,7 +587,7 @@ impl TTS {
             // rate must be in [-10, 10], but we get relative %s. 300% => 10 (see comments at top of file)
             TTSCommand::Rate =>  if is_start_tag {format!("<rate speed='{:.1}'>", 10.0*(0.01*command.value.get_num()).log(3.0))} else {String::from("</rate>")},
             TTSCommand::Volume =>if is_start_tag {format!("<volume level='{}'>", command.value.get_num())} else {String::from("</volume>")},
-            TTSCommand::Audio => "".to_string(),    // SAPI5 doesn't support audio
+            TTSCommand::Audio => "".to_string(),    // SAPI5 doesn't support audio -- the replacement (if any) is used as a text fallback
             TTSCommand::Gender =>if is_start_tag {format!("<voice required=\"Gender={}\">", command.value.get_string())} else {String::from("</prosody>")},
             TTSCommand::Voice =>if is_start_tag {format!("<voice required=\"Name={}\">", command.value.get_string())} else {String::from("</prosody>")},
             TTSCommand::Spell =>if is_start_tag {format!("<spell>{}", command.value.get_string())} else {String::from("</spell>")},
@@ -562,15 +619,17 @@ impl TTS {
                     "".to_string()
                 }
             },
-            TTSCommand::Pitch => if is_start_tag {format!("<prosody pitch='{}%'>", command.value.get_num())} else {String::from("</prosody>")},
+            TTSCommand::Pitch => if is_start_tag {format!("<prosody pitch='{:+}%'>", command.value.get_num())} else {String::from("</prosody>")},
             TTSCommand::Rate =>  if is_start_tag {format!("<prosody rate='{}%'>", command.value.get_num())} else {String::from("</prosody>")},
-            TTSCommand::Volume =>if is_start_tag {format!("<prosody volume='{}db'>", command.value.get_num())} else {String::from("</prosody>")},
-            TTSCommand::Audio =>if is_start_tag {format!("<audio src='{}'>", command.value.get_string())} else {String::from("</audio>")}, // only 'beep' is supported for now
-            TTSCommand::Gender =>if is_start_tag {format!("<voice required='gender=\"{}\"'>", command.value.get_string())} else {String::from("</voice>")},
-            TTSCommand::Voice =>if is_start_tag {format!("<voice required='{}'>", command.value.get_string())} else {String::from("</voice>")},
-            TTSCommand::Spell =>if is_start_tag {format!("<say-as interpret-as='characters'>{}", command.value.get_string())} else {String::from("</say-as>")},
+            TTSCommand::Volume =>if is_start_tag {format!("<prosody volume='{:+}dB'>", command.value.get_num())} else {String::from("</prosody>")},
+            // values that come from an xpath have already had their text escaped, so only the quote needs escaping
+            TTSCommand::Audio =>if is_start_tag {format!("<audio src='{}'>", command.value.get_string().replace('\'', "&apos;"))} else {String::from("</audio>")}, // contents are the fallback
+            TTSCommand::Gender =>if is_start_tag {format!("<voice gender='{}'>", command.value.get_string().replace('\'', "&apos;"))} else {String::from("</voice>")},
+            TTSCommand::Voice =>if is_start_tag {format!("<voice name='{}'>", command.value.get_string().replace('\'', "&apos;"))} else {String::from("</voice>")},
+            TTSCommand::Spell =>if is_start_tag {format!("<say-as interpret-as='characters'>{}", escape_xml(command.value.get_string()))} else {String::from("</say-as>")},
             TTSCommand::Pronounce =>if is_start_tag {
-                format!("<phoneme alphabet='ipa' ph='{}'>{}", &command.value.get_pronounce().ipa, &command.value.get_pronounce().text)
+                let pronounce = command.value.get_pronounce();
+                format!("<phoneme alphabet='ipa' ph='{}'>{}", escape_xml(&pronounce.ipa).replace('\'', "&apos;"), escape_xml(&pronounce.text))
             } else {
                 String::from("</phoneme>")
             },
@@ -578,8 +637,126 @@ impl TTS {
         }
     }
 
+    // Eloquence uses "annotations" (a backquote followed by a command) and all values are absolute.
+    // The annotations need a trailing space so they don't run into the text that follows.
+    fn get_string_eloquence(&self, command: &TTSCommandRule, prefs: &PreferenceManager, is_start_tag: bool) -> String  {
+        return match &command.command {
+            TTSCommand::Pause => if is_start_tag {
+                let amount = command.value.get_num();
+                if amount == PAUSE_AUTO {
+                    PAUSE_AUTO_STR.to_string()
+                } else {
+                    let amount = amount * TTS::get_pause_multiplier(prefs);
+                    if amount > MIN_PAUSE {
+                        format!("`p{} ", (amount * 180.0/prefs.get_rate()).round())
+                    } else {
+                        "".to_string()
+                    }
+                }
+            } else {
+                "".to_string()
+            },
+            // pitch is in [0, 100]; the base pitch is 140hz (see comments at top of file)
+            TTSCommand::Pitch => {
+                let hz = 140.0 * (1.0 + if is_start_tag {command.value.get_num()/100.0} else {0.0});
+                format!("`vb{} ", ((hz - 42.0)/3.8).round().clamp(0.0, 100.0))
+            },
+            // rate is in [0, 250] and is words/min = 4.18 * rate + 66 (see comments at top of file)
+            TTSCommand::Rate => {
+                let words_per_min = prefs.get_rate() * if is_start_tag {0.01*command.value.get_num()} else {1.0};
+                format!("`vs{} ", ((words_per_min - 66.0)/4.18).round().clamp(0.0, 250.0))
+            },
+            TTSCommand::Volume => format!("`vv{} ",
+                    if is_start_tag {command.value.get_num()} else {TTS::get_default_volume(prefs)}.round().clamp(0.0, 100.0)),
+            TTSCommand::Audio => "".to_string(),    // Eloquence doesn't support audio -- the replacement (if any) is used as a text fallback
+            TTSCommand::Gender | TTSCommand::Voice => "".to_string(),   // voices are numbered presets that we know nothing about
+            TTSCommand::Spell => if is_start_tag {format!("`ts1 {}", command.value.get_string())} else {String::from("`ts0 ")},
+            TTSCommand::Pronounce => if is_start_tag {
+                    let pronounce = command.value.get_pronounce();
+                    if pronounce.eloquence.is_empty() {
+                        pronounce.text.clone()
+                    } else {
+                        format!("`[{}] ", pronounce.eloquence)
+                    }
+                } else {
+                    "".to_string()
+                },
+            TTSCommand::Bookmark => panic!("Internal error: bookmarks should have been handled earlier"),
+        };
+    }
+
+    // Mac (NSSpeechSynthesizer) uses embedded commands of the form [[cmd value]]
+    fn get_string_mac(&self, command: &TTSCommandRule, prefs: &PreferenceManager, is_start_tag: bool) -> String  {
+        return match &command.command {
+            TTSCommand::Pause => if is_start_tag {
+                let amount = command.value.get_num();
+                if amount == PAUSE_AUTO {
+                    PAUSE_AUTO_STR.to_string()
+                } else {
+                    let amount = amount * TTS::get_pause_multiplier(prefs);
+                    if amount > MIN_PAUSE {
+                        format!("[[slnc {}]]", (amount * 180.0/prefs.get_rate()).round())
+                    } else {
+                        "".to_string()
+                    }
+                }
+            } else {
+                "".to_string()
+            },
+            // pitch changes are relative and in semitones -- the end tag undoes the change
+            TTSCommand::Pitch => {
+                let semitones = (12.0*(1.0+command.value.get_num()/100.0).log2() * 10.0).round() / 10.0;
+                format!("[[pbas {:+}]]", if is_start_tag {semitones} else {-semitones})
+            },
+            // rate changes are relative and in words/min -- the end tag undoes the change
+            TTSCommand::Rate => {
+                let change = (prefs.get_rate() * (0.01*command.value.get_num() - 1.0)).round();
+                format!("[[rate {:+}]]", if is_start_tag {change} else {-change})
+            },
+            TTSCommand::Volume => format!("[[volm {}]]",
+                    0.01 * if is_start_tag {command.value.get_num()} else {TTS::get_default_volume(prefs)}.clamp(0.0, 100.0)),
+            TTSCommand::Audio => "".to_string(),    // embedded commands can't play audio -- the replacement (if any) is used as a text fallback
+            TTSCommand::Gender | TTSCommand::Voice => "".to_string(),   // voices can't be changed with embedded commands
+            TTSCommand::Spell => if is_start_tag {format!("[[char LTRL]]{}", command.value.get_string())} else {String::from("[[char NORM]]")},
+            TTSCommand::Pronounce => if is_start_tag {
+                    let pronounce = command.value.get_pronounce();
+                    if pronounce.mac.is_empty() {
+                        pronounce.text.clone()
+                    } else {
+                        format!("[[inpt PHON]]{}", pronounce.mac)
+                    }
+                } else if command.value.get_pronounce().mac.is_empty() {
+                    "".to_string()
+                } else {
+                    String::from("[[inpt TEXT]]")
+                },
+            TTSCommand::Bookmark => panic!("Internal error: bookmarks should have been handled earlier"),
+        };
+    }
+
+    fn get_default_volume(prefs: &PreferenceManager) -> f64 {
+        return prefs.pref_to_string("Volume").parse::<f64>().unwrap_or(100.0);
+    }
+
     fn get_pause_multiplier(prefs: &PreferenceManager) -> f64 {
-        return prefs.pref_to_string("PauseFactor").parse::<f64>().unwrap_or(100.)/100.0;
+        let multiplier = prefs.pref_to_string("PauseFactor").parse::<f64>().unwrap_or(100.)/100.0;
+        // longer pauses give more time to process the structure (the "end fraction" style words aren't spoken)
+        return if prefs.pref_to_string("Impairment") == "LearningDisability" {1.5 * multiplier} else {multiplier};
+    }
+
+    /// Returns the regex that matches the markup used by the TTS engine (`None` if there is no markup).
+    pub fn markup_regex(&self) -> Option<&'static Regex> {
+        lazy_static! {
+            static ref XML_MARKUP: Regex = Regex::new(r"<[^>]*>").unwrap();
+            static ref ELOQUENCE_MARKUP: Regex = Regex::new(r"`\S+").unwrap();
+            static ref MAC_MARKUP: Regex = Regex::new(r"\[\[.+?\]\]").unwrap();
+        }
+        return match self {
+            TTS::None => None,
+            TTS::SSML | TTS::SSMLDocument | TTS::SAPI5 => Some(&XML_MARKUP),
+            TTS::Eloquence => Some(&ELOQUENCE_MARKUP),
+            TTS::Mac => Some(&MAC_MARKUP),
+        };
     }
 
     /// Compute the length of the pause to use.
@@ -589,14 +766,24 @@ impl TTS {
     pub fn compute_auto_pause(&self, prefs: &PreferenceManager, before: &str, after: &str) -> String {
         lazy_static! {
             static ref REMOVE_XML: Regex = Regex::new(r"<.+?>").unwrap();    // punctuation ending with a '.'
+            static ref REMOVE_ELOQUENCE: Regex = Regex::new(r"`\S+").unwrap();
+            static ref REMOVE_MAC: Regex = Regex::new(r"\[\[.+?\]\]").unwrap();
         }
         let before_len;
         let after_len;
         match self {
-            TTS::SSML | TTS::SAPI5 => {
+            TTS::SSML | TTS::SSMLDocument | TTS::SAPI5 => {
                 before_len = REMOVE_XML.replace_all(before, "").len();
                 after_len = REMOVE_XML.replace_all(after, "").len();
             },
+            TTS::Eloquence => {
+                before_len = REMOVE_ELOQUENCE.replace_all(before, "").len();
+                after_len = REMOVE_ELOQUENCE.replace_all(after, "").len();
+            },
+            TTS::Mac => {
+                before_len = REMOVE_MAC.replace_all(before, "").len();
+                after_len = REMOVE_MAC.replace_all(after, "").len();
+            },
             _ => {
                 before_len = before.len();
                 after_len = after.len();
@@ -621,12 +808,28 @@ impl TTS {
         );
         return match self {
             TTS::None  => self.get_string_none(&command, prefs, true),
-            TTS::SSML  => self.get_string_ssml(&command, prefs, true),
+            TTS::SSML | TTS::SSMLDocument => self.get_string_ssml(&command, prefs, true),
             TTS::SAPI5 => self.get_string_sapi5(&command, prefs, true),
+            TTS::Eloquence => self.get_string_eloquence(&command, prefs, true),
+            TTS::Mac   => self.get_string_mac(&command, prefs, true),
         };
 
     }
 
+    /// Returns true if text in the speech needs to be escaped because it is embedded in markup
+    pub fn is_xml(&self) -> bool {
+        return matches!(self, TTS::SSML | TTS::SSMLDocument);
+    }
+
+    /// Make the (final) speech string into a complete document if the TTS engine requires that (currently only `SSMLDocument`)
+    pub fn wrap_speech(&self, speech: String, prefs: &PreferenceManager) -> String {
+        if *self != TTS::SSMLDocument {
+            return speech;
+        }
+        return format!("<speak version='1.1' xmlns='http://www.w3.org/2001/10/synthesis' xml:lang='{}'>{}</speak>",
+                        escape_xml(&prefs.pref_to_string("Language")).replace('\'', "&apos;"), speech);
+    }
+
     /// Take the longest of the pauses
     ///
     /// Two other options are:
@@ -638,8 +841,10 @@ impl TTS {
         // we need specialized merges for each TTS engine because we need to know the format of the commands
         return match self {
             TTS::None  => self.merge_pauses_none(str),
-            TTS::SSML  => self.merge_pauses_ssml(str),
+            TTS::SSML | TTS::SSMLDocument => self.merge_pauses_ssml(str),
             TTS::SAPI5 => self.merge_pauses_sapi5(str),
+            TTS::Eloquence => self.merge_pauses_eloquence(str),
+            TTS::Mac   => self.merge_pauses_mac(str),
         };        
     }
 
@@ -681,6 +886,24 @@ impl TTS {
         return TTS::merge_pauses_xml(str, &CONSECUTIVE_BREAKS, &PAUSE_AMOUNT, replacement);
     }
 
+    fn merge_pauses_eloquence(&self, str: &str) -> String {
+        lazy_static! {
+            static ref CONSECUTIVE_BREAKS: Regex = Regex::new(r"(`p\d+ *){2,}").unwrap();   // two or more pauses
+            static ref PAUSE_AMOUNT: Regex = Regex::new(r"`p(\d+)").unwrap();   // amount after 'p'
+        }
+        let replacement = |amount: usize| format!("`p{} ", amount);
+        return TTS::merge_pauses_xml(str, &CONSECUTIVE_BREAKS, &PAUSE_AMOUNT, replacement);
+    }
+
+    fn merge_pauses_mac(&self, str: &str) -> String {
+        lazy_static! {
+            static ref CONSECUTIVE_BREAKS: Regex = Regex::new(r"(\[\[slnc \d+\]\] *){2,}").unwrap();   // two or more pauses
+            static ref PAUSE_AMOUNT: Regex = Regex::new(r"slnc (\d+)").unwrap();   // amount after 'slnc'
+        }
+        let replacement = |amount: usize| format!("[[slnc {}]]", amount);
+        return TTS::merge_pauses_xml(str, &CONSECUTIVE_BREAKS, &PAUSE_AMOUNT, replacement);
+    }
+
     fn merge_pauses_ssml(&self, str: &str) -> String {
         lazy_static! {
             static ref CONSECUTIVE_BREAKS: Regex = Regex::new(r"(<break time=[^>]+?> *){2,}").unwrap();   // two or more pauses
@@ -689,4 +912,66 @@ impl TTS {
         let replacement = |amount: usize| format!("<break time='{}ms'/>", amount);
         return TTS::merge_pauses_xml(str, &CONSECUTIVE_BREAKS, &PAUSE_AMOUNT, replacement);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn command(command: TTSCommand, value: f64) -> TTSCommandRule {
+        return TTSCommandRule::new(command, TTSCommandValue::Number(value), ReplacementArray::build_empty());
+    }
+
+    #[test]
+    fn eloquence_markup() {
+        let pref_manager = PreferenceManager::get();
+        let mut pref_manager = pref_manager.borrow_mut();
+        pref_manager.initialize(PathBuf::from(super::super::abs_rules_dir_path())).unwrap();
+        pref_manager.set_user_prefs("Rate", "180");
+        pref_manager.set_user_prefs("PauseFactor", "100");
+        let tts = TTS::Eloquence;
+        assert_eq!(tts.get_string_eloquence(&command(TTSCommand::Pause, 300.0), &pref_manager, true), "`p300 ");
+        assert_eq!(tts.get_string_eloquence(&command(TTSCommand::Rate, 50.0), &pref_manager, true), "`vs6 ");
+        assert_eq!(tts.get_string_eloquence(&command(TTSCommand::Rate, 50.0), &pref_manager, false), "`vs27 ");
+        assert_eq!(tts.get_string_eloquence(&command(TTSCommand::Pitch, 50.0), &pref_manager, true), "`vb44 ");
+        assert_eq!(tts.get_string_eloquence(&command(TTSCommand::Pitch, 50.0), &pref_manager, false), "`vb26 ");
+        assert_eq!(tts.get_string_eloquence(&command(TTSCommand::Volume, 50.0), &pref_manager, true), "`vv50 ");
+        assert_eq!(tts.get_string_eloquence(&command(TTSCommand::Volume, 50.0), &pref_manager, false), "`vv100 ");
+    }
+
+    #[test]
+    fn mac_markup() {
+        let pref_manager = PreferenceManager::get();
+        let mut pref_manager = pref_manager.borrow_mut();
+        pref_manager.initialize(PathBuf::from(super::super::abs_rules_dir_path())).unwrap();
+        pref_manager.set_user_prefs("Rate", "180");
+        pref_manager.set_user_prefs("PauseFactor", "100");
+        let tts = TTS::Mac;
+        assert_eq!(tts.get_string_mac(&command(TTSCommand::Pause, 300.0), &pref_manager, true), "[[slnc 300]]");
+        assert_eq!(tts.get_string_mac(&command(TTSCommand::Rate, 50.0), &pref_manager, true), "[[rate -90]]");
+        assert_eq!(tts.get_string_mac(&command(TTSCommand::Rate, 50.0), &pref_manager, false), "[[rate +90]]");
+        assert_eq!(tts.get_string_mac(&command(TTSCommand::Pitch, 100.0), &pref_manager, true), "[[pbas +12]]");
+        assert_eq!(tts.get_string_mac(&command(TTSCommand::Pitch, 100.0), &pref_manager, false), "[[pbas -12]]");
+        assert_eq!(tts.get_string_mac(&command(TTSCommand::Volume, 50.0), &pref_manager, true), "[[volm 0.5]]");
+        assert_eq!(tts.get_string_mac(&command(TTSCommand::Volume, 50.0), &pref_manager, false), "[[volm 1]]");
+    }
+
+    #[test]
+    fn learning_disability_pauses() {
+        let pref_manager = PreferenceManager::get();
+        let mut pref_manager = pref_manager.borrow_mut();
+        pref_manager.initialize(PathBuf::from(super::super::abs_rules_dir_path())).unwrap();
+        pref_manager.set_user_prefs("PauseFactor", "100");
+        pref_manager.set_user_prefs("Impairment", "LearningDisability");
+        assert_eq!(TTS::Eloquence.get_string_eloquence(&command(TTSCommand::Pause, 300.0), &pref_manager, true), "`p450 ");
+        pref_manager.set_user_prefs("Impairment", "Blindness");
+        assert_eq!(TTS::Eloquence.get_string_eloquence(&command(TTSCommand::Pause, 300.0), &pref_manager, true), "`p300 ");
+    }
+
+    #[test]
+    fn merge_pauses() {
+        assert_eq!(TTS::Eloquence.merge_pauses("a `p300 `p600 b `p100 c"), "a `p600 b `p100 c");
+        assert_eq!(TTS::Mac.merge_pauses("a [[slnc 300]] [[slnc 600]]b [[slnc 100]] c"), "a [[slnc 600]]b [[slnc 100]] c");
+    }
+}
@@ -3,7 +3,7 @@
 //! The variables defined are all the preferences and also variables set in speech rules via the `variables` keyword.
 //! The function defined here are:
 //! * `IsNode(node, kind)`:  returns true if the node matches the "kind".
-//!    Valid values are "leaf", "2D", "simple", "common_fraction", "trig_name".
+//!    Valid values are "leaf", "2D", "simple", "common_fraction", "trig_name", "unit".
 //! * `ToOrdinal(number, fractional, plural)`: converts the number to an ordinal (e.g, third)
 //!   * `number` -- the number to translate
 //!   * `fractional` -- true if this is a fractional ordinal (e.g, "half")
@@ -12,6 +12,14 @@
 //! * `IsLargeOp(node)` -- returns true if the node is a large operator (e.g, integral or sum)
 //! * `IsBracketed(node, left, right, requires_comma)` -- returns true if the first/last element in the mrow match `left`/`right`.
 //!    If the optional `requires_comma` argument is given and is `true`, then there also must be a "," in the mrow (e.g., "f(x,y)")
+//! * `DefinitionValue(node, name)` -- returns the value of node's text in the dictionary `name` from definitions.yaml (or "" if not found)
+//! * `ChemicalFormulaText(node)` -- returns the text of a chemical formula (e.g., "H2SO4") or "" if the node is not a simple formula
+//! * `UnitName(node, plural)` -- returns the spoken name of a unit (e.g., "kilometers" for "km") or "" if it isn't known
+//! * `ConceptFixity(node)` -- returns how the node's intent concept is spoken ("prefix", "infix", "postfix", "function", or "silent")
+//! * `ConceptName(node, verbosity)` -- returns the spoken form of the node's intent concept from the concept dictionary
+//! * `NestingWords(node, word)` -- returns `word` repeated once for each level of nesting of the node's kind (e.g, "StartStartFraction")
+//! * `ScriptLevel(node, super_word, sub_word, suffix)` -- returns the script level of the node (e.g., "SuperSubscript") or "" if on the baseline
+//! * `EndsScriptLevel(node)` -- returns true if nothing is spoken after the node at its script level
 //! * `DEBUG(xpath)` -- _Very_ useful function for debugging speech rules.
 //!    This can be used to surround a whole or part of an xpath expression in a match or output.
 //!    The result will be printed to standard output and the result returned so that `DEBUG` does not affect the computation.    
@@ -19,7 +27,7 @@
 
 use sxd_document::dom::{Element, ChildOfElement};
 use sxd_xpath::{Value, Context, context, function::*, nodeset::*};
-use crate::definitions::DEFINITIONS;
+use crate::definitions::{DEFINITIONS, Definitions};
 use regex::Regex;
 use crate::pretty_print::mml_to_string;
 use std::cell::Ref;
@@ -302,6 +310,28 @@ impl IsNode {
         }
     }
 
+    /// true if the element is a unit (marked during canonicalization) or is built out of units (e.g., "m/s²")
+    pub fn is_unit(elem: &Element) -> bool {
+        return match name(elem) {
+            "mi" | "mtext" => elem.attribute(crate::canonicalize::UNIT_ATTR).is_some(),
+            "msup" | "msub" => IsNode::is_unit(&as_element(elem.children()[0])),
+            "mfrac" => elem.children().iter().all(|&child| IsNode::is_unit(&as_element(child))),
+            "mrow" => {
+                let children = elem.children();
+                children.iter().any(|&child| IsNode::is_unit(&as_element(child))) &&
+                children.iter().all(|&child| {
+                    let child = as_element(child);
+                    IsNode::is_unit(&child) || (name(&child) == "mo" && is_unit_operator(&get_text_from_element(child)))
+                })
+            },
+            _ => false,
+        };
+
+        fn is_unit_operator(text: &str) -> bool {
+            return matches!(text, "\u{2062}" | "/" | "⋅" | "·" | "∙");
+        }
+    }
+
     #[allow(non_snake_case)]
     pub fn is_2D(elem: &Element) -> bool {
         return MATHML_2D_NODES.contains(name(elem));
@@ -354,7 +384,7 @@ impl Function for IsNode {
         // FIX: there is some conflict problem with xpath errors and error-chain
         //                .chain_err(|e| format!("Second arg to is_leaf is not a string: {}", e.to_string()))?;
         match kind.as_str() {
-            "simple" | "leaf" | "common_fraction" | "2D" | "modified" | "scripted" => (), 
+            "simple" | "leaf" | "common_fraction" | "2D" | "modified" | "scripted" | "unit" => (), 
             _ => return Err( Error::Other(format!("Unknown argument value '{}' for IsNode",  kind.as_str())) ),
         };
 
@@ -373,6 +403,7 @@ impl Function for IsNode {
                                 "2D" => IsNode::is_2D(&e),
                                 "modified" => MATHML_MODIFIED_NODES.contains(name(&e)),
                                 "scripted" => MATHML_SCRIPTED_NODES.contains(name(&e)),
+                                "unit" => IsNode::is_unit(&e),
                                 "common_fraction" => IsNode::is_common_fraction(e, usize::MAX, usize::MAX), 
                                 _        => true,       // can't happen due to check above
                             }    
@@ -566,6 +597,10 @@ impl Function for ToOrdinal {
     {
         let mut args = Args(args);
         args.exactly(1)?;
+        if let Value::Number(number) = args[0] {
+            // e.g., ToOrdinal(count(preceding-sibling::*)+1)
+            return Ok( Value::String( ToOrdinal::convert(&number.to_string(), false, false) ) );
+        }
         let node = validate_one_node(args.pop_nodeset()?, "ToOrdinal")?;
         return match node {
             Node::Text(t) =>  Ok( Value::String( ToOrdinal::convert(t.text(), false, false) ) ),
@@ -906,6 +941,228 @@ impl IsInDefinition {
 }
 
 
+pub struct DefinitionValue;
+impl DefinitionValue {
+    fn value(key: &str, map_name: &str) -> String {
+        return DEFINITIONS.with(|definitions| {
+            let definitions = definitions.borrow();
+            return match definitions.get_hashmap(map_name) {
+                Some(map) => map.get(key).cloned().unwrap_or_default(),
+                None => "".to_string(),     // not all languages define all the dictionaries
+            };
+        });
+    }
+}
+
+/**
+ * Returns the value associated with the key in the named dictionary in definitions.yaml or "" if it isn't there
+ * element/string -- element (converted to string)/string to look up
+ * map_name -- name of the dictionary (e.g., "ChemicalCompounds")
+ */
+impl Function for DefinitionValue {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> Result<Value<'d>, Error>
+    {
+        let mut args = Args(args);
+        args.exactly(2)?;
+        let map_name = args.pop_string()?;
+        let key = match &args[0] {
+            Value::String(str) => str.clone(),
+            Value::Nodeset(nodes) if nodes.size() == 0 => "".to_string(),
+            Value::Nodeset(nodes) => match validate_one_node(nodes.clone(), "DefinitionValue")? {
+                Node::Element(e) => get_text_from_element(e),
+                _ => "".to_string(),
+            },
+            _ => return Err( Error::Other("DefinitionValue:: neither a node nor a string is passed for first argument".to_string()) ),
+        };
+        return Ok( Value::String( if key.is_empty() {key} else {DefinitionValue::value(&key, &map_name)} ) );
+    }
+}
+
+
+pub struct UnitName;
+impl UnitName {
+    /// Returns true if `text` is a unit symbol in definitions.yaml, possibly with an SI prefix (e.g., "m", "km", "°C")
+    pub fn is_unit(text: &str) -> bool {
+        let text = text.trim();
+        return DEFINITIONS.with(|definitions| {
+            let definitions = definitions.borrow();
+            return definitions.get_hashset("UnitsWithoutPrefixes").unwrap().contains(text) ||
+                   definitions.get_hashset("SIPrefixableUnits").unwrap().contains(text) ||
+                   UnitName::split_prefix(&definitions, text).is_some();
+        });
+    }
+
+    /// Splits a prefixed unit into the prefix and the unit (e.g., "km" -> ("k", "m"))
+    fn split_prefix<'a>(definitions: &Definitions, text: &'a str) -> Option<(&'a str, &'a str)> {
+        let prefixes = definitions.get_hashset("SIPrefixes").unwrap();
+        let units = definitions.get_hashset("SIPrefixableUnits").unwrap();
+        // prefixes are at most two chars ("da")
+        return text.char_indices().skip(1).take(2)
+                .map(|(i, _)| text.split_at(i))
+                .find(|(prefix, unit)| prefixes.contains(*prefix) && units.contains(*unit));
+    }
+
+    /// Returns the spoken name of the unit or "" if the speech language doesn't have a name for it
+    fn name(text: &str, plural: bool) -> String {
+        let text = text.trim();
+        return DEFINITIONS.with(|definitions| {
+            let definitions = definitions.borrow();
+            let lookup = |unit: &str| -> Option<String> {
+                if plural {
+                    if let Some(name) = definitions.get_hashmap("UnitNamesPlural").and_then(|names| names.get(unit).cloned()) {
+                        return Some(name);
+                    }
+                }
+                return definitions.get_hashmap("UnitNames").and_then(|names| names.get(unit).cloned());
+            };
+            if let Some(name) = lookup(text) {
+                return name;
+            }
+            if let Some((prefix, unit)) = UnitName::split_prefix(&definitions, text) {
+                let prefix_name = definitions.get_hashmap("UnitPrefixNames").and_then(|names| names.get(prefix).cloned());
+                if let (Some(prefix_name), Some(unit_name)) = (prefix_name, lookup(unit)) {
+                    return prefix_name + &unit_name;
+                }
+            }
+            return "".to_string();
+        });
+    }
+}
+
+/**
+ * Returns the spoken name of a unit (e.g., "km" -> "kilometers") or "" if the speech language doesn't have a name for it
+ * node -- the unit (its text is the unit symbol)
+ * plural -- true if the plural form should be used
+ */
+impl Function for UnitName {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> Result<Value<'d>, Error>
+    {
+        let mut args = Args(args);
+        args.exactly(2)?;
+        let plural = args.pop_boolean()?;
+        let node = validate_one_node(args.pop_nodeset()?, "UnitName")?;
+        return match node {
+            Node::Element(e) => Ok( Value::String( UnitName::name(&get_text_from_element(e), plural) ) ),
+            Node::Text(t) => Ok( Value::String( UnitName::name(t.text(), plural) ) ),
+            _   =>  Err( Error::ArgumentNotANodeset{actual: ArgumentType::String} ),
+        };
+    }
+}
+
+/// The properties in the intent concept dictionary (`IntentConcepts` in definitions.yaml)
+pub struct ConceptFixity;
+impl ConceptFixity {
+    const FIXITIES: [&'static str; 5] = ["prefix", "infix", "postfix", "function", "silent"];
+
+    /// Returns the (fixity, arity) of 'concept' in the concept dictionary or None if it isn't a known concept
+    fn lookup(concept: &str) -> Option<(String, String)> {
+        return DEFINITIONS.with(|definitions| {
+            let definitions = definitions.borrow();
+            let value = definitions.get_hashmap("IntentConcepts")?.get(concept)?.clone();
+            let (fixity, arity) = value.split_once(' ').unwrap_or((value.as_str(), "*"));
+            return Some( (fixity.to_string(), arity.trim().to_string()) );
+        });
+    }
+
+    /// Returns an error message if 'concept' is in the concept dictionary and 'n_args' is not a legal number of args for it.
+    /// Using a concept without args (e.g., as the name of a function) is always legal.
+    pub fn check_arity(concept: &str, n_args: usize) -> std::result::Result<(), String> {
+        if n_args == 0 {
+            return Ok( () );
+        }
+        let Some((_, arity)) = ConceptFixity::lookup(concept) else { return Ok( () ) };
+        let is_ok = match arity.strip_suffix('+') {
+            _ if arity == "*" => true,
+            Some(at_least) => at_least.parse::<usize>().is_ok_and(|at_least| n_args >= at_least),
+            None => arity.parse::<usize>() == Ok(n_args),
+        };
+        if is_ok {
+            return Ok( () );
+        }
+        return Err( format!("the intent concept '{}' has {} args, but the concept dictionary says it takes {}", concept, n_args, arity) );
+    }
+
+    /// Returns the fixity of the element: an explicit fixity property, the fixity in the concept dictionary, or "function"
+    fn fixity(element: Element) -> String {
+        if let Some(properties) = element.attribute_value("data-intent-property") {
+            if let Some(fixity) = properties.split(':').find(|property| ConceptFixity::FIXITIES.contains(property)) {
+                return fixity.to_string();
+            }
+        }
+        return match ConceptFixity::lookup(name(&element)) {
+            Some((fixity, _)) => fixity,
+            None => "function".to_string(),
+        };
+    }
+}
+
+/**
+ * Returns the fixity ("prefix", "infix", "postfix", "function", or "silent") that should be used to speak the node's concept
+ * node -- an element in the intent tree
+ */
+impl Function for ConceptFixity {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> Result<Value<'d>, Error>
+    {
+        let mut args = Args(args);
+        args.exactly(1)?;
+        let node = validate_one_node(args.pop_nodeset()?, "ConceptFixity")?;
+        return match node {
+            Node::Element(e) => Ok( Value::String( ConceptFixity::fixity(e) ) ),
+            _   =>  Err( Error::ArgumentNotANodeset{actual: ArgumentType::String} ),
+        };
+    }
+}
+
+/// The spoken forms of the concepts in the intent concept dictionary (`IntentConceptNames` in the language's definitions.yaml)
+pub struct ConceptName;
+impl ConceptName {
+    /// Returns the spoken form of the concept for the verbosity, falling back to the name with "-", "_", and "." replaced by spaces
+    fn name(concept: &str, verbosity: &str) -> String {
+        let default_name = concept.replace(['-', '_', '.'], " ");
+        if crate::prefs::PreferenceManager::get().borrow().get_speech_override(concept).is_some() {
+            return default_name;        // the speech override for the concept name is applied to the result
+        }
+        let spoken_name = DEFINITIONS.with(|definitions| {
+            let definitions = definitions.borrow();
+            let verbosity_map_name = format!("IntentConceptNames{}", verbosity);
+            return definitions.get_hashmap(&verbosity_map_name).and_then(|names| names.get(concept).cloned())
+                .or_else(|| definitions.get_hashmap("IntentConceptNames").and_then(|names| names.get(concept).cloned()));
+        });
+        return spoken_name.unwrap_or(default_name);
+    }
+}
+
+/**
+ * Returns the spoken form of the node's concept (e.g., "greatest common divisor")
+ * node -- an element in the intent tree
+ * verbosity -- the value of the 'Verbosity' pref
+ */
+impl Function for ConceptName {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> Result<Value<'d>, Error>
+    {
+        let mut args = Args(args);
+        args.exactly(2)?;
+        let verbosity = args.pop_string()?;
+        let node = validate_one_node(args.pop_nodeset()?, "ConceptName")?;
+        return match node {
+            Node::Element(e) => Ok( Value::String( ConceptName::name(name(&e), &verbosity) ) ),
+            _   =>  Err( Error::ArgumentNotANodeset{actual: ArgumentType::String} ),
+        };
+    }
+}
+
 pub struct DistanceFromLeaf;
 impl DistanceFromLeaf {
     fn distance(element: Element, use_left_side: bool, treat_2d_elements_as_tokens: bool) -> usize {
@@ -1030,6 +1287,153 @@ impl Function for EdgeNode {
     }
 }
 
+pub struct NestingWords;
+impl NestingWords {
+    /// Returns the number of levels of elements with the same name as 'element' nested inside of it.
+    /// Common fractions (e.g., 1/2) are spoken as a unit (e.g., "one half"), so they don't count as a level.
+    fn nesting_depth(element: Element, element_name: &str) -> usize {
+        let mut depth = 0;
+        for child in element.children() {
+            if let Some(child) = child.element() {
+                let mut child_depth = NestingWords::nesting_depth(child, element_name);
+                if name(&child) == element_name && !IsNode::is_common_fraction(child, 19, 10) {
+                    child_depth += 1;
+                }
+                depth = depth.max(child_depth);
+            }
+        }
+        return depth;
+    }
+}
+
+/**
+ * Returns 'word' repeated once for each level of nesting of the node's element name (e.g., "StartStartFraction")
+ * node -- node to test (e.g., a fraction)
+ * word -- the word to repeat
+ */
+impl Function for NestingWords {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> Result<Value<'d>, Error>
+    {
+        let mut args = Args(args);
+        args.exactly(2)?;
+        let word = args.pop_string()?;
+        let node = validate_one_node(args.pop_nodeset()?, "NestingWords")?;
+        if let Node::Element(e) = node {
+            return Ok( Value::String( word.repeat(NestingWords::nesting_depth(e, name(&e))) ) );
+        }
+        return Err(Error::Other(format!("NestingWords: first arg '{:?}' is not a node", node)));
+    }
+}
+
+
+pub struct ScriptLevel;
+impl ScriptLevel {
+    /// Returns true/false if 'child' is a superscript/subscript of 'parent' or None if it isn't a script.
+    /// The names are the intent names along with their MathML counterparts.
+    fn is_superscript(parent: Element, child: Element) -> Option<bool> {
+        let position = child.preceding_siblings().len();
+        return match (name(&parent), position) {
+            ("power" | "msup", 1) => Some(true),
+            ("sub" | "particular-value-of" | "msub" | "msubsup", 1) => Some(false),
+            ("msubsup", 2) => Some(true),
+            _ => None,
+        };
+    }
+
+    /// Returns the script level of 'element' as a string of 'super_word'/'sub_word's with 'suffix' appended.
+    /// The outermost script comes first (e.g., "SuperSubscript"). An empty string is returned for the baseline.
+    fn script_level(element: Element, super_word: &str, sub_word: &str, suffix: &str) -> String {
+        let mut words = Vec::new();
+        let mut child = element;
+        while let Some(parent) = child.parent().and_then(|p| p.element()) {
+            if let Some(is_superscript) = ScriptLevel::is_superscript(parent, child) {
+                words.push(if is_superscript {super_word} else {sub_word});
+            }
+            child = parent;
+        }
+        if words.is_empty() {
+            return "".to_string();
+        }
+        words.reverse();
+        return words.concat() + suffix;
+    }
+}
+
+/**
+ * Returns the (MathSpeak) script level of the node (e.g., "Superscript", "SuperSubscript") or "" if on the baseline
+ * node -- node to test
+ * super_word -- word used for a superscript level (e.g., "Super")
+ * sub_word -- word used for a subscript level (e.g., "Sub")
+ * suffix -- string added to the end if there are any script levels (e.g., "script")
+ */
+impl Function for ScriptLevel {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> Result<Value<'d>, Error>
+    {
+        let mut args = Args(args);
+        args.exactly(4)?;
+        let suffix = args.pop_string()?;
+        let sub_word = args.pop_string()?;
+        let super_word = args.pop_string()?;
+        let node = validate_one_node(args.pop_nodeset()?, "ScriptLevel")?;
+        if let Node::Element(e) = node {
+            return Ok( Value::String( ScriptLevel::script_level(e, &super_word, &sub_word, &suffix) ) );
+        }
+        return Err(Error::Other(format!("ScriptLevel: first arg '{:?}' is not a node", node)));
+    }
+}
+
+
+pub struct EndsScriptLevel;
+impl EndsScriptLevel {
+    /// Returns true if nothing is spoken after 'element' at its script level.
+    /// That happens if 'element' is at the end of the math or the end of a base/script of a scripted element
+    ///   (the scripted element takes care of saying what the new level is).
+    fn ends_script_level(element: Element) -> bool {
+        let mut child = element;
+        while let Some(parent) = child.parent().and_then(|p| p.element()) {
+            let parent_name = name(&parent);
+            if ["power", "msup", "sub", "particular-value-of", "msub", "msubsup"].contains(&parent_name) {
+                return true;
+            }
+            if !child.following_siblings().is_empty() {
+                return false;
+            }
+            match parent_name {
+                "math" => return true,
+                "mrow" | "intent-wrapper" | "negative" | "positive" => child = parent,
+                _ => return false,
+            }
+        }
+        return true;
+    }
+}
+
+/**
+ * Returns true if the node is the last thing spoken at its script level (so there is no need to say the level afterwards)
+ * node -- node to test (a scripted element)
+ */
+impl Function for EndsScriptLevel {
+    fn evaluate<'d>(&self,
+                        _context: &context::Evaluation<'_, 'd>,
+                        args: Vec<Value<'d>>)
+                        -> Result<Value<'d>, Error>
+    {
+        let mut args = Args(args);
+        args.exactly(1)?;
+        let node = validate_one_node(args.pop_nodeset()?, "EndsScriptLevel")?;
+        if let Node::Element(e) = node {
+            return Ok( Value::Boolean( EndsScriptLevel::ends_script_level(e) ) );
+        }
+        return Err(Error::Other(format!("EndsScriptLevel: first arg '{:?}' is not a node", node)));
+    }
+}
+
 /// Add all the functions defined in this module to `context`.
 pub fn add_builtin_functions(context: &mut Context) {
     // FIX: should be a static cache that gets regenerated on update
@@ -1042,10 +1446,18 @@ pub fn add_builtin_functions(context: &mut Context) {
     context.set_function("ToCommonFraction", ToCommonFraction);
     context.set_function("IsBracketed", IsBracketed);
     context.set_function("IsInDefinition", IsInDefinition);
+    context.set_function("DefinitionValue", DefinitionValue);
+    context.set_function("ChemicalFormulaText", crate::chemistry::ChemicalFormulaText);
+    context.set_function("UnitName", UnitName);
+    context.set_function("ConceptFixity", ConceptFixity);
+    context.set_function("ConceptName", ConceptName);
     context.set_function("BaseNode", BaseNode);
     context.set_function("IfThenElse", IfThenElse);
     context.set_function("DistanceFromLeaf", DistanceFromLeaf);
     context.set_function("EdgeNode", EdgeNode);
+    context.set_function("NestingWords", NestingWords);
+    context.set_function("ScriptLevel", ScriptLevel);
+    context.set_function("EndsScriptLevel", EndsScriptLevel);
     context.set_function("DEBUG", Debug);
 }
 
@@ -1244,4 +1656,60 @@ mod tests {
         let mn = as_element(as_element(fraction.children()[1]).children()[0]);
         assert_eq!(EdgeNode::edge_node(mn, true, "2D"), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn script_level() {
+        let mathml = "<math><mrow><msup><mi>x</mi><mrow><msub><mi>a</mi><mi>b</mi></msub><mo>+</mo><mn>1</mn></mrow></msup><mo>+</mo><mi>y</mi></mrow></math>";
+        let package = parser::parse(mathml).expect("failed to parse XML");
+        let mathml = get_element(&package);
+        trim_element(&mathml);
+        let msup = as_element(as_element(mathml.children()[0]).children()[0]);
+        let msub = as_element(as_element(msup.children()[1]).children()[0]);
+        let b = as_element(msub.children()[1]);
+        assert_eq!(ScriptLevel::script_level(msup, "Super", "Sub", "script"), "");
+        assert_eq!(ScriptLevel::script_level(msub, "Super", "Sub", "script"), "Superscript");
+        assert_eq!(ScriptLevel::script_level(b, "Sup", "Sub", ""), "SupSub");
+        assert!(!EndsScriptLevel::ends_script_level(msup));
+        assert!(!EndsScriptLevel::ends_script_level(msub));
+        assert!(EndsScriptLevel::ends_script_level(b));
+    }
+
+    #[test]
+    fn nesting_words() {
+        let mathml = "<math><mfrac><mn>1</mn><mrow><mn>1</mn><mo>+</mo>\
+                <mfrac><mi>x</mi><mrow><mn>1</mn><mo>+</mo><mfrac><mn>1</mn><mi>x</mi></mfrac></mrow></mfrac><mo>+</mo>\
+                <mfrac><mn>1</mn><mn>2</mn></mfrac></mrow></mfrac></math>";
+        let package = parser::parse(mathml).expect("failed to parse XML");
+        let mathml = get_element(&package);
+        trim_element(&mathml);
+        let fraction = as_element(mathml.children()[0]);
+        assert_eq!(NestingWords::nesting_depth(fraction, "mfrac"), 2);
+        let denominator = as_element(fraction.children()[1]);
+        assert_eq!(NestingWords::nesting_depth(as_element(denominator.children()[2]), "mfrac"), 1);
+        assert_eq!(NestingWords::nesting_depth(as_element(denominator.children()[4]), "mfrac"), 0);
+    }
+
+    #[test]
+    fn concept_dictionary() {
+        init_word_list();
+        assert!(ConceptFixity::check_arity("factorial", 1).is_ok());
+        assert!(ConceptFixity::check_arity("factorial", 2).is_err());
+        assert!(ConceptFixity::check_arity("factorial", 0).is_ok());         // used as a name
+        assert!(ConceptFixity::check_arity("plus", 5).is_ok());
+        assert!(ConceptFixity::check_arity("plus", 1).is_err());
+        assert!(ConceptFixity::check_arity("not-a-concept", 7).is_ok());     // open concepts can have any number of args
+        let mathml = "<math><factorial><mi>n</mi></factorial><foo data-intent-property=':infix:'/><equals data-intent-property=':silent:int:'/><bar/></math>";
+        let package = parser::parse(mathml).expect("failed to parse XML");
+        let mathml = get_element(&package);
+        trim_element(&mathml);
+        let children = mathml.children();
+        assert_eq!(ConceptFixity::fixity(as_element(children[0])), "postfix");
+        assert_eq!(ConceptFixity::fixity(as_element(children[1])), "infix");
+        assert_eq!(ConceptFixity::fixity(as_element(children[2])), "silent");
+        assert_eq!(ConceptFixity::fixity(as_element(children[3])), "function");
+        assert_eq!(ConceptName::name("greatest-common-divisor", "Medium"), "gcd");
+        assert_eq!(ConceptName::name("greatest-common-divisor", "Verbose"), "the greatest common divisor");
+        assert_eq!(ConceptName::name("determinant", "Terse"), "det");
+        assert_eq!(ConceptName::name("my.open_concept-name", "Medium"), "my open concept name");
+    }
+}
@@ -10,9 +10,12 @@ mod ClearSpeak {
     mod sets;
     mod symbols_and_adornments;
     mod multiline;
+    mod probability;
+    mod logic;
 }
 
 mod SimpleSpeak {
+    mod calculus;
     mod functions;
     mod large_ops;
     // mod menclose;
@@ -23,10 +26,23 @@ mod SimpleSpeak {
     mod geometry;
     mod linear_algebra;
     mod multiline;
+    mod probability;
+    mod logic;
+}
+
+mod MathSpeak {
+    mod mfrac;
+    mod mroot;
+    mod msup;
+    mod mtable;
+    mod symbols_and_adornments;
 }
 mod shared;
 mod chemistry;
 mod alphabets;
 mod intent;
+mod intent_conformance;
 mod mtable;
+mod overview;
+mod units;
 
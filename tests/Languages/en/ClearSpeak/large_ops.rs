@@ -186,7 +186,7 @@ fn integral_under() {
         <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
         <mi>d</mi><mi>x</mi>
         </math>";
-    test("en", "ClearSpeak", expr, "the integral over the real numbers of; f of x d x");
+    test("en", "ClearSpeak", expr, "the integral over the real numbers of f of x, with respect to x");
 }
 
 #[test]
@@ -196,5 +196,5 @@ fn integral() {
             <mrow><mi>f</mi><mrow><mo>(</mo><mi>x</mi> <mo>)</mo></mrow></mrow>
             <mi>d</mi><mi>x</mi>
             </math>";
-    test("en", "ClearSpeak", expr, "the integral of f of x d x");
+    test("en", "ClearSpeak", expr, "the integral of f of x, with respect to x");
 }
\ No newline at end of file
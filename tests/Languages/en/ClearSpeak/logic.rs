@@ -0,0 +1,19 @@
+use crate::common::*;
+
+#[test]
+fn for_all_there_exists() {
+  let expr = "<math> <mo>∀</mo><mi>x</mi><mo>∈</mo><mi>ℝ</mi><mo>,</mo><mo>∃</mo><mi>n</mi><mo>∈</mo><mi>ℕ</mi><mo>,</mo><mi>n</mi><mo>&gt;</mo><mi>x</mi> </math>";
+  test("en", "ClearSpeak", expr, "for all x in the real numbers; there exists n in the natural numbers such that n is greater than x");
+}
+
+#[test]
+fn implies() {
+  let expr = "<math> <mi>x</mi><mo>&gt;</mo><mn>1</mn><mo>⇒</mo><msup><mi>x</mi><mn>2</mn></msup><mo>&gt;</mo><mn>1</mn> </math>";
+  test("en", "ClearSpeak", expr, "x is greater than 1, implies x squared is greater than 1");
+}
+
+#[test]
+fn restricted_binders() {
+  let expr = "<math> <mo>∀</mo><mi>x</mi><mo>≥</mo><mn>0</mn><mo>,</mo><mo>∃</mo><mi>y</mi><mo>∉</mo><mi>ℚ</mi><mo>,</mo><mi>y</mi><mo>&gt;</mo><mi>x</mi> </math>";
+  test("en", "ClearSpeak", expr, "for all x greater than or equal to 0; there exists y not in the rational numbers such that y is greater than x");
+}
@@ -0,0 +1,19 @@
+use crate::common::*;
+
+#[test]
+fn conditional_probability() {
+  let expr = "<math> <mi>P</mi><mo>(</mo><mi>A</mi><mo>|</mo><mi>B</mi><mo>)</mo> </math>";
+  test("en", "ClearSpeak", expr, "the probability of cap eigh given cap b");
+}
+
+#[test]
+fn expected_value() {
+  let expr = "<math> <mi>E</mi><mo>[</mo><mi>X</mi><mo>]</mo> </math>";
+  test("en", "ClearSpeak", expr, "the expected value of cap x");
+}
+
+#[test]
+fn binomial_distribution() {
+  let expr = "<math> <mi>X</mi><mo>∼</mo><mi>Bin</mi><mo>(</mo><mi>n</mi><mo>,</mo><mi>p</mi><mo>)</mo> </math>";
+  test("en", "ClearSpeak", expr, "cap x is distributed as the binomial distribution with n trials and probability p");
+}
@@ -181,6 +181,14 @@ fn set_with_bar() {
     test("en", "ClearSpeak", expr, "the set of all x such that x is greater than 2");
 }
 
+#[test]
+fn set_with_bar_and_two_conditions() {
+    let expr = "<math>
+                    <mo>{</mo><mi>x</mi><mo>|</mo><mi>x</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mi>x</mi><mo>&lt;</mo><mn>5</mn><mo>}</mo>
+            </math>";
+    test("en", "ClearSpeak", expr, "the set of all x such that x is greater than 0, comma x is less than 5");
+}
+
 #[test]
 fn element_alone() {
     let expr = "<math>
@@ -172,7 +172,7 @@ fn vertical_line_divides() {
                 </mrow>
             </math>";
         test_ClearSpeak_prefs("en", vec![("ClearSpeak_VerticalLine", "Given"), ("ClearSpeak_ImpliedTimes", "None")]
-                        , expr, "cap p, open paren, cap eigh given cap b, close paren");
+                        , expr, "the probability of cap eigh given cap b");     // P(A|B) is a conditional probability
     }
 
 #[test]
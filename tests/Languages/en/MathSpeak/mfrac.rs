@@ -0,0 +1,53 @@
+use crate::common::*;
+
+#[test]
+fn common_fraction() {
+    let expr = "<math> <mfrac> <mn>3</mn> <mn>4</mn> </mfrac> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "3 fourths");
+}
+
+#[test]
+fn simple() {
+    let expr = "<math> <mfrac> <mi>x</mi> <mi>y</mi> </mfrac> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "StartFraction, x, Over, y, EndFraction;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "StartFrac, x, Over, y, EndFrac;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr, "Frac, x, Over, y, EndFrac;");
+}
+
+#[test]
+fn nested() {
+    let expr = "<math>
+        <mfrac>
+            <mn>1</mn>
+            <mrow> <mn>1</mn> <mo>+</mo> <mfrac> <mn>1</mn> <mi>x</mi> </mfrac> </mrow>
+        </mfrac>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+        "StartStartFraction, 1, OverOver, 1 plus, StartFraction, 1, Over, x, EndFraction; EndEndFraction;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr,
+        "StartStartFrac, 1, OverOver, 1 plus, StartFrac, 1, Over, x, EndFrac; EndEndFrac;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+        "NestFrac, 1, NestOver, 1 plus, Frac, 1, Over, x, EndFrac; NestEndFrac;");
+}
+
+#[test]
+fn nested_common_fraction() {
+    // common fractions are spoken as a unit, so they don't add a level of nesting
+    let expr = "<math>
+        <mfrac>
+            <mi>x</mi>
+            <mrow> <mn>1</mn> <mo>+</mo> <mfrac> <mn>1</mn> <mn>2</mn> </mfrac> </mrow>
+        </mfrac>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+        "StartFraction, x, Over, 1 plus 1 half, EndFraction;");
+}
+
+#[test]
+fn fraction_with_script() {
+    let expr = "<math> <mfrac> <mn>1</mn> <msup> <mi>x</mi> <mi>n</mi> </msup> </mfrac> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+        "StartFraction, 1, Over, x Superscript n Baseline; EndFraction;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+        "Frac, 1, Over, x Sup n Base; EndFrac;");
+}
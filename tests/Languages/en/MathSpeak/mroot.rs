@@ -0,0 +1,20 @@
+use crate::common::*;
+
+#[test]
+fn msqrt() {
+    let expr = "<math> <msqrt> <mi>x</mi> <mo>+</mo> <mn>1</mn> </msqrt> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "StartRoot, x plus 1, EndRoot;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "StartRoot, x plus 1, EndRoot;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr, "Root, x plus 1, EndRoot;");
+}
+
+#[test]
+fn nested_roots() {
+    let expr = "<math>
+        <msqrt> <mi>x</mi> <mo>+</mo> <mroot> <mi>y</mi> <mn>3</mn> </mroot> </msqrt>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+        "StartRoot, x plus, RootIndex 3 NestedStartRoot, y, NestedEndRoot; EndRoot;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+        "Root, x plus, RootIndex 3 NestedRoot, y, NestedEndRoot; EndRoot;");
+}
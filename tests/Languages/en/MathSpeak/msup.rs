@@ -0,0 +1,55 @@
+use crate::common::*;
+
+#[test]
+fn squared() {
+    let expr = "<math> <msup> <mi>x</mi> <mn>2</mn> </msup> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "x squared");
+}
+
+#[test]
+fn simple_power() {
+    let expr = "<math> <msup> <mi>x</mi> <mi>n</mi> </msup> <mo>+</mo> <mi>y</mi> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "x Superscript n Baseline; plus y");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "x Sup n Baseline, plus y");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr, "x Sup n Base, plus y");
+}
+
+#[test]
+fn power_at_end() {
+    let expr = "<math> <mi>y</mi> <mo>=</mo> <msup> <mi>x</mi> <mi>n</mi> </msup> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "y is equal to x Superscript n,");
+}
+
+#[test]
+fn numeric_subscript() {
+    let expr = "<math> <msub> <mi>x</mi> <mn>1</mn> </msub> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "x 1");
+}
+
+#[test]
+fn msubsup() {
+    let expr = "<math> <msubsup> <mi>x</mi> <mi>i</mi> <mi>n</mi> </msubsup> <mo>=</mo> <mn>0</mn> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "x Subscript i, Superscript n Baseline; is equal to 0");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "x Sub i, Sup n Baseline; is equal to 0");
+}
+
+#[test]
+fn nested_scripts() {
+    let expr = "<math>
+        <msup>
+            <mi>x</mi>
+            <mrow> <msub> <mi>a</mi> <mi>b</mi> </msub> <mo>+</mo> <mn>1</mn> </mrow>
+        </msup>
+        <mo>+</mo> <mi>y</mi>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+        "x Superscript eigh SuperSubscript b Superscript; plus 1 Baseline; plus y");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+        "x Sup eigh SupSub b Sup; plus 1 Base; plus y");
+}
+
+#[test]
+fn nested_numeric_subscript() {
+    let expr = "<math> <msup> <mi>e</mi> <msub> <mi>x</mi> <mn>1</mn> </msub> </msup> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "e Superscript x SuperSubscript 1;");
+}
@@ -0,0 +1,41 @@
+use crate::common::*;
+
+#[test]
+fn matrix_2x2() {
+    let expr = "<math>
+        <mrow> <mo>(</mo>
+            <mtable>
+                <mtr> <mtd><mi>a</mi></mtd> <mtd><mi>b</mi></mtd> </mtr>
+                <mtr> <mtd><mi>c</mi></mtd> <mtd><mi>d</mi></mtd> </mtr>
+            </mtable>
+        <mo>)</mo> </mrow>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+        "Start 2 By 2 Matrix; first Row, first Column eigh; second Column b; second Row, first Column c; second Column d; EndMatrix;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr,
+        "Start 2 By 2 Matrix; Row 1, Column 1 eigh; Column 2 b; Row 2, Column 1 c; Column 2 d; EndMatrix;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr,
+        "2 By 2 Matrix; Row 1, eigh; b; Row 2, c; d; EndMatrix;");
+}
+
+#[test]
+fn determinant_2x2() {
+    let expr = "<math>
+        <mrow> <mo>|</mo>
+            <mtable>
+                <mtr> <mtd><mi>a</mi></mtd> <mtd><mi>b</mi></mtd> </mtr>
+                <mtr> <mtd><mi>c</mi></mtd> <mtd><mi>d</mi></mtd> </mtr>
+            </mtable>
+        <mo>|</mo> </mrow>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr,
+        "Start 2 By 2 Determinant; Row 1, Column 1 eigh; Column 2 b; Row 2, Column 1 c; Column 2 d; EndDeterminant;");
+}
+
+#[test]
+fn binomial() {
+    let expr = "<math> <mrow> <mo>(</mo> <mfrac linethickness='0'> <mi>n</mi> <mi>k</mi> </mfrac> <mo>)</mo> </mrow> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "StartBinomialOrMatrix n Choose k EndBinomialOrMatrix;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "StartBinomOrMatrix n Choose k EndBinomOrMatrix;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr, "BinomOrMatrix n Choose k EndBinomOrMatrix;");
+}
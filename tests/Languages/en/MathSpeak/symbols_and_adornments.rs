@@ -0,0 +1,25 @@
+use crate::common::*;
+
+#[test]
+fn sum() {
+    let expr = "<math>
+        <munderover> <mo>∑</mo> <mrow> <mi>i</mi> <mo>=</mo> <mn>1</mn> </mrow> <mi>n</mi> </munderover> <mi>i</mi>
+    </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr,
+        "sum Underscript i is equal to 1 Overscript n Endscripts, i");
+}
+
+#[test]
+fn accent() {
+    let expr = "<math> <mover> <mi>x</mi> <mo>¯</mo> </mover> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "ModifyingAbove x With bar,");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "ModAbove x With bar,");
+}
+
+#[test]
+fn absolute_value() {
+    let expr = "<math> <mrow> <mo>|</mo> <mi>x</mi> <mo>|</mo> </mrow> </math>";
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Verbose")], expr, "StartAbsoluteValue, x, EndAbsoluteValue;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "Brief")], expr, "StartAbsValue, x, EndAbsValue;");
+    test_prefs("en", "MathSpeak", vec![("MathSpeak", "SuperBrief")], expr, "AbsValue, x, EndAbsValue;");
+}
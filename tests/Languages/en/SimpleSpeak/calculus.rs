@@ -0,0 +1,91 @@
+/// Tests for derivatives and integrals (intent inference from the calculus notation)
+use crate::common::*;
+
+#[test]
+fn leibniz_derivative() {
+    let expr = "<math><mfrac><mrow><mi>d</mi><mi>y</mi></mrow><mrow><mi>d</mi><mi>x</mi></mrow></mfrac></math>";
+    test("en", "SimpleSpeak", expr, "the derivative of y with respect to x");
+}
+
+#[test]
+fn leibniz_second_derivative() {
+    let expr = "<math><mfrac>
+            <mrow><msup><mi>d</mi><mn>2</mn></msup><mi>y</mi></mrow>
+            <mrow><mi>d</mi><msup><mi>x</mi><mn>2</mn></msup></mrow>
+        </mfrac></math>";
+    test("en", "SimpleSpeak", expr, "the second derivative of y with respect to x");
+}
+
+#[test]
+fn leibniz_derivative_operator() {
+    let expr = "<math>
+            <mfrac><mi>d</mi><mrow><mi>d</mi><mi>x</mi></mrow></mfrac>
+            <mo>(</mo><msup><mi>x</mi><mn>2</mn></msup><mo>)</mo>
+        </math>";
+    test("en", "SimpleSpeak", expr, "the derivative of x squared, with respect to x");
+}
+
+#[test]
+fn partial_derivative() {
+    let expr = "<math><mfrac><mrow><mo>∂</mo><mi>f</mi></mrow><mrow><mo>∂</mo><mi>x</mi></mrow></mfrac></math>";
+    test("en", "SimpleSpeak", expr, "the partial derivative of f with respect to x");
+}
+
+#[test]
+fn mixed_partial_derivative() {
+    let expr = "<math><mfrac>
+            <mrow><msup><mo>∂</mo><mn>2</mn></msup><mi>f</mi></mrow>
+            <mrow><mo>∂</mo><mi>x</mi><mo>∂</mo><mi>y</mi></mrow>
+        </mfrac></math>";
+    test("en", "SimpleSpeak", expr, "the second partial derivative of f with respect to x and y");
+}
+
+#[test]
+fn partial_derivative_operator() {
+    let expr = "<math><mfrac><mo>∂</mo><mrow><mo>∂</mo><mi>t</mi></mrow></mfrac><mi>u</mi></math>";
+    test("en", "SimpleSpeak", expr, "the partial derivative of u with respect to t");
+}
+
+#[test]
+fn indefinite_integral() {
+    let expr = "<math><mo>∫</mo><mn>2</mn><mi>x</mi><mi>d</mi><mi>x</mi></math>";
+    test("en", "SimpleSpeak", expr, "the integral of 2 x, with respect to x");
+}
+
+#[test]
+fn definite_integral() {
+    let expr = "<math>
+            <msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup>
+            <msup><mi>x</mi><mn>2</mn></msup><mi>d</mi><mi>x</mi>
+        </math>";
+    test("en", "SimpleSpeak", expr, "the integral from 0 to 1 of x squared, with respect to x");
+}
+
+#[test]
+fn double_integral() {
+    let expr = "<math><mo>∬</mo><mi>x</mi><mi>y</mi><mi>d</mi><mi>x</mi><mi>d</mi><mi>y</mi></math>";
+    test("en", "SimpleSpeak", expr, "the double integral of x y, with respect to x and y");
+}
+
+#[test]
+fn integral_evaluated_at() {
+    let expr = "<math>
+            <msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup>
+            <msup><mi>x</mi><mn>2</mn></msup><mi>d</mi><mi>x</mi>
+            <mo>=</mo>
+            <mfrac><msup><mi>x</mi><mn>3</mn></msup><mn>3</mn></mfrac>
+            <msubsup><mo>|</mo><mn>0</mn><mn>1</mn></msubsup>
+        </math>";
+    test("en", "SimpleSpeak", expr,
+        "the integral from 0 to 1 of x squared, with respect to x; is equal to; \
+         fraction, x cubed, over 3, end fraction; evaluated at 1 minus the same expression evaluated at 0");
+}
+
+#[test]
+fn integral_bracket_evaluated_at() {
+    let expr = "<math>
+            <msubsup><mrow><mo>[</mo><mfrac><msup><mi>x</mi><mn>3</mn></msup><mn>3</mn></mfrac><mo>]</mo></mrow><mn>0</mn><mn>1</mn></msubsup>
+        </math>";
+    test("en", "SimpleSpeak", expr,
+        "fraction, x cubed, over 3, end fraction; evaluated at 1 minus the same expression evaluated at 0");
+}
@@ -0,0 +1,65 @@
+use crate::common::*;
+
+#[test]
+fn for_all_there_exists() {
+  let expr = "<math> <mo>∀</mo><mi>x</mi><mo>∈</mo><mi>S</mi><mo>,</mo><mo>∃</mo><mi>y</mi><mo>:</mo><mi>P</mi><mo>(</mo><mi>x</mi><mo>,</mo><mi>y</mi><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "for all x in cap s; there exists y such that cap p of, open paren x comma y, close paren");
+}
+
+#[test]
+fn for_all_no_separator() {
+  let expr = "<math> <mo>∀</mo><mi>x</mi><mo>(</mo><mi>P</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>→</mo><mi>Q</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "for all x; open paren, cap p of x, implies cap q of x; close paren");
+}
+
+#[test]
+fn nested_quantifiers() {
+  let expr = "<math> <mo>∀</mo><mi>x</mi><mo>∀</mo><mi>y</mi><mi>P</mi><mo>(</mo><mi>x</mi><mo>,</mo><mi>y</mi><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "for all x; for all y; cap p of, open paren x comma y, close paren");
+}
+
+#[test]
+fn exists_unique() {
+  let expr = "<math> <mo>∃</mo><mo>!</mo><mi>x</mi><mo>∈</mo><mi>S</mi><mo>,</mo><mi>x</mi><mo>=</mo><mn>0</mn> </math>";
+  test("en", "SimpleSpeak", expr, "there exists a unique x in cap s such that x is equal to 0");
+}
+
+#[test]
+fn for_all_multiple_variables() {
+  let expr = "<math> <mo>∀</mo><mi>x</mi><mo>,</mo><mi>y</mi><mo>∈</mo><mi>ℝ</mi><mo>,</mo><mi>x</mi><mo>+</mo><mi>y</mi><mo>=</mo><mi>y</mi><mo>+</mo><mi>x</mi> </math>";
+  test("en", "SimpleSpeak", expr, "for all x comma y in the real numbers; x plus y, is equal to y plus x");
+}
+
+#[test]
+fn epsilon_delta() {
+  let expr = "<math>
+      <mo>∀</mo><mi>ε</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mo>∃</mo><mi>δ</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo>
+      <mo>|</mo><mi>x</mi><mo>|</mo><mo>&lt;</mo><mi>δ</mi><mo>⇒</mo><mo>|</mo><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>|</mo><mo>&lt;</mo><mi>ε</mi>
+    </math>";
+  test("en", "SimpleSpeak", expr, "for all epsilon greater than 0; there exists delta greater than 0 such that \
+        the absolute value of x; is less than delta, implies the absolute value of f of x, end absolute value; is less than epsilon");
+}
+
+#[test]
+fn trailing_quantifier() {
+  let expr = "<math> <mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>=</mo><mn>0</mn><mo>∀</mo><mi>x</mi><mo>∈</mo><mi>ℝ</mi> </math>";
+  test("en", "SimpleSpeak", expr, "f of x is equal to 0, for all x in the real numbers");
+}
+
+#[test]
+fn de_morgan() {
+  let expr = "<math> <mo>¬</mo><mo>(</mo><mi>p</mi><mo>∧</mo><mi>q</mi><mo>)</mo><mo>⇔</mo><mo>¬</mo><mi>p</mi><mo>∨</mo><mo>¬</mo><mi>q</mi> </math>";
+  test("en", "SimpleSpeak", expr, "not, open paren p and q close paren, if and only if not p or not q");
+}
+
+#[test]
+fn and_of_relations() {
+  let expr = "<math> <mi>x</mi><mo>&gt;</mo><mn>0</mn><mo>∧</mo><mi>x</mi><mo>&lt;</mo><mn>5</mn> </math>";
+  test("en", "SimpleSpeak", expr, "x is greater than 0, and x is less than 5");
+}
+
+#[test]
+fn sequent() {
+  let expr = "<math> <mi>Γ</mi><mo>,</mo><mi>φ</mi><mo>⊢</mo><mi>ψ</mi><mo>,</mo><mi>Δ</mi> </math>";
+  test("en", "SimpleSpeak", expr, "cap gamma comma phi, proves psi comma cap delta");
+}
@@ -0,0 +1,88 @@
+use crate::common::*;
+
+#[test]
+fn conditional_probability() {
+  let expr = "<math> <mi>P</mi><mo>(</mo><mi>A</mi><mo>|</mo><mi>B</mi><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "the probability of cap eigh given cap b");
+  test_prefs("en", "SimpleSpeak", vec![("Verbosity", "Terse")], expr, "probability of cap eigh given cap b");
+}
+
+#[test]
+fn conditional_probability_abs() {
+  // the "|"s for the absolute value are matched -- the remaining one is "given"
+  let expr = "<math> <mi>P</mi><mo>(</mo><mi>A</mi><mo>|</mo><mo>|</mo><mi>X</mi><mo>|</mo><mo>&lt;</mo><mn>1</mn><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "the probability of cap eigh given the absolute value of cap x; is less than 1");
+}
+
+#[test]
+fn polynomial_not_probability() {
+  let expr = "<math> <mi>P</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>=</mo><msup><mi>x</mi><mn>2</mn></msup> </math>";
+  test("en", "SimpleSpeak", expr, "cap p of x, is equal to x squared");
+}
+
+#[test]
+fn complement() {
+  let expr = "<math> <mi>P</mi><mo>(</mo><mover><mi>A</mi><mo>¯</mo></mover><mo>)</mo><mo>=</mo><mn>1</mn><mo>-</mo><mi>P</mi><mo>(</mo><mi>A</mi><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr,
+      "the probability of the complement of cap eigh; is equal to, 1 minus the probability of cap eigh");
+}
+
+#[test]
+fn expected_value() {
+  let expr = "<math> <mi>E</mi><mo>[</mo><mi>X</mi><mo>|</mo><mi>Y</mi><mo>]</mo> </math>";
+  test("en", "SimpleSpeak", expr, "the expected value of cap x given cap y");
+}
+
+#[test]
+fn variance() {
+  let expr = "<math> <mi>Var</mi><mo>(</mo><mi>X</mi><mo>)</mo><mo>=</mo>
+        <mi>E</mi><mo>[</mo><msup><mi>X</mi><mn>2</mn></msup><mo>]</mo><mo>-</mo>
+        <msup><mrow><mo>(</mo><mi>E</mi><mo>[</mo><mi>X</mi><mo>]</mo><mo>)</mo></mrow><mn>2</mn></msup> </math>";
+  test("en", "SimpleSpeak", expr,
+      "the variance of cap x, is equal to; the expected value of cap x squared, minus, open paren, the expected value of cap x, close paren squared");
+}
+
+#[test]
+fn covariance() {
+  let expr = "<math> <mi>Cov</mi><mo>(</mo><mi>X</mi><mo>,</mo><mi>Y</mi><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "the covariance of cap x and cap y");
+}
+
+#[test]
+fn normal_distribution() {
+  let expr = "<math> <mi>X</mi><mo>∼</mo><mi mathvariant='script'>N</mi><mo>(</mo><mi>μ</mi><mo>,</mo><msup><mi>σ</mi><mn>2</mn></msup><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "cap x is distributed as the normal distribution with mean mu and variance sigma squared");
+}
+
+#[test]
+fn poisson_distribution() {
+  let expr = "<math> <mi>X</mi><mo>~</mo><mi>Pois</mi><mo>(</mo><mi>λ</mi><mo>)</mo> </math>";
+  test("en", "SimpleSpeak", expr, "cap x is distributed as the poisson distribution with rate lambda");
+}
+
+#[test]
+fn sample_mean() {
+  let expr = "<math> <mover><mi>x</mi><mo>¯</mo></mover><mo>=</mo><mfrac><mn>1</mn><mi>n</mi></mfrac>
+        <munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover><msub><mi>x</mi><mi>i</mi></msub> </math>";
+  test("en", "SimpleSpeak", expr, "the mean of x is equal to; 1 over n; the sum from i is equal to 1 to n of; x sub i");
+  // without the data, it is just "x bar"
+  let expr = "<math> <mover><mi>x</mi><mo>¯</mo></mover> </math>";
+  test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "General")], expr, "x bar,");
+}
+
+#[test]
+fn conjugate() {
+  let expr = "<math> <mover><mi>z</mi><mo>¯</mo></mover> </math>";
+  test("en", "SimpleSpeak", expr, "the complex conjugate of z");
+}
+
+#[test]
+fn estimate() {
+  let expr = "<math> <mover><mi>θ</mi><mo>^</mo></mover> </math>";
+  test("en", "SimpleSpeak", expr, "the estimate of theta");
+  // a hat on a Latin letter is only an estimate in statistics
+  let p_hat = "<math> <mover><mi>p</mi><mo>^</mo></mover> </math>";
+  test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "General")], p_hat, "p hat,");
+  test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "Statistics")], p_hat, "the estimate of p");
+  test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "General"), ("Verbosity", "Terse")], expr, "theta hat");
+}
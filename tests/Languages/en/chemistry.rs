@@ -655,3 +655,34 @@ fn mhchem_roman_in_superscript() {
 }
 
 
+
+#[test]
+fn as_compound_water() {
+  let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+  test_prefs("en", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "water,");
+}
+
+#[test]
+fn as_compound_with_state() {
+  let expr = "<math><mrow><mi>Na</mi><mi>Cl</mi><mo stretchy='false'>(</mo><mi>aq</mi><mo stretchy='false'>)</mo></mrow></math>";
+  test_prefs("en", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "sodium chloride, aqueous,");
+}
+
+#[test]
+fn as_compound_equation() {
+  let expr = "<math><mrow>
+      <msub><mi>H</mi><mn>2</mn></msub><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub>
+      <mo>+</mo><mn>2</mn><mi>Na</mi><mi>O</mi><mi>H</mi>
+      <mo>&#x2192;</mo>
+      <mi>Na</mi><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub>
+    </mrow></math>";
+  // NaSO4 isn't in the compound list, so it is spelled out
+  test_prefs("en", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr,
+      "sulfuric acid, plus 2 sodium hydroxide; reacts to form; cap n eigh, cap s, cap o, sub 4");
+}
+
+#[test]
+fn as_compound_unknown() {
+  let expr = "<math><mrow><msub><mi>Xe</mi><mn>3</mn></msub><mi>Na</mi></mrow></math>";
+  test_prefs("en", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "cap x e, sub 3 cap n eigh,");
+}
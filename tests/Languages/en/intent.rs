@@ -39,5 +39,30 @@ fn intent_prob_x() {
         <mi arg='arg'>x</mi>
         <mi arg='op' intent='probability' mathvariant='normal'>P</mi>
     </msup></math>";
-    test("en", "ClearSpeak", expr, "probability of, x");
+    test("en", "ClearSpeak", expr, "the probability of x");
+}
+
+#[test]
+fn concept_dictionary_infix() {
+    let expr = "<math><mrow intent='less-than($a,$b)'><mi arg='a'>x</mi><mo>&lt;</mo><mi arg='b'>y</mi></mrow></math>";
+    test("en", "SimpleSpeak", expr, "x is less than y");
+}
+
+#[test]
+fn concept_dictionary_postfix() {
+    let expr = "<math><mrow intent='factorial($n)'><mi arg='n'>n</mi><mo>!</mo></mrow></math>";
+    test("en", "SimpleSpeak", expr, "n factorial");
+}
+
+#[test]
+fn concept_dictionary_function() {
+    let expr = "<math><mrow intent='gradient($f)'><mo>∇</mo><mi arg='f'>f</mi></mrow></math>";
+    test("en", "SimpleSpeak", expr, "gradient of, f");
+    test_prefs("en", "SimpleSpeak", vec![("Verbosity", "Verbose")], expr, "the gradient of, f");
+}
+
+#[test]
+fn concept_dictionary_fixity_override() {
+    let expr = "<math><mrow intent='factorial:prefix($n)'><mo>!</mo><mi arg='n'>n</mi></mrow></math>";
+    test("en", "SimpleSpeak", expr, "factorial n");
 }
@@ -0,0 +1,154 @@
+/// Conformance tests for the MathML 4 `intent` attribute, modeled on the examples in the MathML 4 spec.
+/// These cover the grammar (concepts, literals, numbers, references, applications, and properties)
+/// and how open concepts are spoken based on their fixity property.
+use crate::common::*;
+
+#[test]
+fn concept_core() {
+    let expr = "<math><mrow intent='binomial($n,$k)'>
+            <mo>(</mo><mfrac linethickness='0'><mi arg='n'>n</mi><mi arg='k'>k</mi></mfrac><mo>)</mo>
+        </mrow></math>";
+    test("en", "SimpleSpeak", expr, "n choose k");
+}
+
+#[test]
+fn concept_with_postfix_speech() {
+    let expr = "<math><msup intent='transpose($M)'><mi arg='M'>M</mi><mi>T</mi></msup></math>";
+    test("en", "SimpleSpeak", expr, "cap m transpose");
+}
+
+#[test]
+fn open_concept_default_function() {
+    let expr = "<math><mrow intent='free-algebra($r,$x)'>
+            <mi arg='r'>R</mi><mo>⟨</mo><mi arg='x'>X</mi><mo>⟩</mo>
+        </mrow></math>";
+    test("en", "SimpleSpeak", expr, "free algebra of, cap r comma cap x");
+}
+
+#[test]
+fn open_concept_function() {
+    let expr = "<math><mrow intent='foo:function($a,$b)'><mi arg='a'>x</mi><mo>⊕</mo><mi arg='b'>y</mi></mrow></math>";
+    test("en", "SimpleSpeak", expr, "foo of, x comma y");
+}
+
+#[test]
+fn open_concept_prefix() {
+    let expr = "<math><mrow intent='card:prefix($s)'><mo>#</mo><mi arg='s'>S</mi></mrow></math>";
+    test("en", "SimpleSpeak", expr, "card cap s");
+}
+
+#[test]
+fn open_concept_postfix() {
+    let expr = "<math><mrow intent='bang:postfix($n)'><mi arg='n'>n</mi><mo>‼</mo></mrow></math>";
+    test("en", "SimpleSpeak", expr, "n bang");
+}
+
+#[test]
+fn open_concept_infix() {
+    let expr = "<math><mrow intent='foo-bar:infix($a,$b,$c)'>
+            <mi arg='a'>x</mi><mo>⊕</mo><mi arg='b'>y</mi><mo>⊕</mo><mi arg='c'>z</mi>
+        </mrow></math>";
+    test("en", "SimpleSpeak", expr, "x foo bar y foo bar z");
+}
+
+#[test]
+fn open_concept_in_expression() {
+    // the pauses around an open concept are only used when something is spoken before/after it
+    let expr = "<math><mn>2</mn><mo>+</mo><mrow intent='foo-bar:infix($a,$b)'><mi arg='a'>x</mi><mo>⊕</mo><mi arg='b'>y</mi></mrow>
+            <mo>+</mo><mn>1</mn></math>";
+    test("en", "SimpleSpeak", expr, "2 plus, x foo bar y; plus 1");
+    let expr = "<math><mrow intent='bang:postfix($n)'><mi arg='n'>n</mi><mo>‼</mo></mrow><mo>+</mo><mn>1</mn></math>";
+    test("en", "SimpleSpeak", expr, "n bang plus 1");
+}
+
+#[test]
+fn open_concept_infix_one_arg() {
+    let expr = "<math><mrow intent='foo:infix($a)'><mo>⊕</mo><mi arg='a'>x</mi></mrow></math>";
+    test("en", "SimpleSpeak", expr, "foo x");
+}
+
+#[test]
+fn open_concept_silent() {
+    let expr = "<math><mrow intent='foo:silent($a,$b)'><mi arg='a'>x</mi><mo>⊕</mo><mi arg='b'>y</mi></mrow></math>";
+    test("en", "SimpleSpeak", expr, "x y");
+}
+
+#[test]
+fn open_concept_no_args() {
+    let expr = "<math><mi intent='foo()'>x</mi></math>";
+    test("en", "SimpleSpeak", expr, "foo");
+}
+
+#[test]
+fn open_concept_silent_no_args() {
+    let expr = "<math><mn>2</mn><mo>+</mo><mi intent='foo:silent()'>x</mi></math>";
+    test("en", "SimpleSpeak", expr, "2 plus");
+}
+
+#[test]
+fn known_concept_with_fixity() {
+    // a fixity property overrides the speech rule for a known concept
+    let expr = "<math><mrow intent='plus:infix($a,$b)'><mi arg='a'>x</mi><mo>+</mo><mi arg='b'>y</mi></mrow></math>";
+    test("en", "SimpleSpeak", expr, "x plus y");
+}
+
+#[test]
+fn concept_name_separators() {
+    let expr = "<math><mi intent='my.concept_name'>x</mi></math>";
+    test("en", "SimpleSpeak", expr, "my concept name");
+}
+
+#[test]
+fn literal() {
+    let expr = "<math><mi intent='_my-literal'>x</mi></math>";
+    test("en", "SimpleSpeak", expr, "my literal");
+}
+
+#[test]
+fn literal_silent() {
+    let expr = "<math><mtext intent='_'>x</mtext><mo>+</mo><mn>1</mn></math>";
+    test("en", "SimpleSpeak", expr, "plus 1");
+}
+
+#[test]
+fn number() {
+    let expr = "<math><mi intent='-3.5'>x</mi></math>";
+    test("en", "SimpleSpeak", expr, "minus 3.5");
+}
+
+#[test]
+fn reference_with_intent() {
+    let expr = "<math><mrow intent='$f($x)'>
+            <mi arg='f' intent='bessel-j'>J</mi><mo>(</mo><mi arg='x'>x</mi><mo>)</mo>
+        </mrow></math>";
+    test("en", "SimpleSpeak", expr, "bessel j of, x");
+}
+
+#[test]
+fn reference_with_fixity() {
+    let expr = "<math><mrow intent='$op($a,$b)'>
+            <mi arg='a'>x</mi><mo arg='op' intent='foo:infix'>⊕</mo><mi arg='b'>y</mi>
+        </mrow></math>";
+    test("en", "SimpleSpeak", expr, "x foo y");
+}
+
+#[test]
+fn nested_application() {
+    let expr = "<math><mrow intent='$op($a)($b)'>
+            <mi arg='op' intent='shift'>S</mi><mi arg='a'>a</mi><mi arg='b'>b</mi>
+        </mrow></math>";
+    test("en", "SimpleSpeak", expr, "shift of, eigh applied to b");
+}
+
+#[test]
+fn self_property_list() {
+    let expr = "<math><mn>2</mn><mi intent=':silent'>x</mi></math>";
+    test("en", "SimpleSpeak", expr, "2");
+}
+
+#[test]
+fn structure_property_is_inherited() {
+    // ':structure' turns off inference (e.g., chemistry) for all the descendants
+    let expr = "<math><mrow intent=':structure'><mrow><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></mrow></mrow></math>";
+    test("en", "SimpleSpeak", expr, "cap h sub 2 cap o");
+}
@@ -0,0 +1,112 @@
+use crate::common::*;
+
+#[test]
+fn short_expr_is_read() {
+    let expr = "<math><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>1</mn></math>";
+    test_overview("en", vec![], expr, "x squared plus 1");
+}
+
+#[test]
+fn equation() {
+    let expr = "<math>
+        <mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mi>c</mi></mfrac>
+        <mo>=</mo>
+        <msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>3</mn><mi>x</mi><mo>-</mo><mn>1</mn>
+    </math>";
+    // a sum with only 3 terms isn't named
+    test_overview("en", vec![], expr, "fraction, eigh plus b, over c, end fraction; is equal to, x squared plus 3 x minus 1");
+    test_overview("en", vec![("OverviewDepth", "2")], expr,
+        "fraction, eigh plus b, over c, end fraction; is equal to, x squared plus 3 x minus 1");
+}
+
+#[test]
+fn long_sum() {
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <msup><mi>x</mi><mn>3</mn></msup><mo>+</mo><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>3</mn><mi>x</mi><mo>-</mo><mn>1</mn>
+    </math>";
+    test_overview("en", vec![], expr, "y is equal to, an expression with 4 terms");
+}
+
+#[test]
+fn fraction_plus_1() {
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <mfrac>
+            <mrow><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mi>c</mi></mrow>
+            <mrow><mi>x</mi><mo>−</mo><mn>1</mn></mrow>
+        </mfrac>
+        <mo>+</mo><mn>1</mn>
+    </math>";
+    test_overview("en", vec![], expr, "y is equal to, a fraction plus 1");
+}
+
+#[test]
+fn depth_zero() {
+    let expr = "<math>
+        <mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mi>c</mi></mfrac>
+        <mo>=</mo>
+        <msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><mn>3</mn><mi>x</mi><mo>-</mo><mn>1</mn>
+    </math>";
+    test_overview("en", vec![("OverviewDepth", "0")], expr, "an equation");
+}
+
+#[test]
+fn sum_of_large_terms() {
+    let expr = "<math>
+        <mfrac>
+            <mrow><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mi>c</mi></mrow>
+            <mrow><mi>x</mi><mo>-</mo><mn>1</mn></mrow>
+        </mfrac>
+        <mo>+</mo>
+        <msqrt><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><msup><mi>y</mi><mn>2</mn></msup></msqrt>
+    </math>";
+    test_overview("en", vec![], expr, "a fraction plus, the square root of x squared plus y squared end root,");
+}
+
+#[test]
+fn definite_integral() {
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup><msup><mi>x</mi><mn>2</mn></msup><mi>d</mi><mi>x</mi>
+        <mo>+</mo><mn>1</mn>
+    </math>";
+    test_overview("en", vec![("OverviewDepth", "2")], expr, "y is equal to, a definite integral plus 1");
+}
+
+#[test]
+fn summation() {
+    let expr = "<math>
+        <mi>S</mi><mo>=</mo>
+        <munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover>
+        <msup><mi>i</mi><mn>2</mn></msup>
+    </math>";
+    test_overview("en", vec![], expr, "cap s is equal to a summation");
+}
+
+#[test]
+fn matrix() {
+    let expr = "<math>
+        <mi>A</mi><mo>=</mo>
+        <mrow><mo>[</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd><mtd><mn>3</mn></mtd></mtr>
+            <mtr><mtd><mn>4</mn></mtd><mtd><mn>5</mn></mtd><mtd><mn>6</mn></mtd></mtr>
+            <mtr><mtd><mn>7</mn></mtd><mtd><mn>8</mn></mtd><mtd><mn>9</mn></mtd></mtr>
+        </mtable><mo>]</mo></mrow>
+    </math>";
+    test_overview("en", vec![], expr, "cap eigh is equal to a 3 by 3 matrix");
+}
+
+#[test]
+fn intent_name() {
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <mrow intent='greatest-common-divisor($a, $b)'>
+            <mo>gcd</mo><mo>(</mo>
+            <mrow arg='a'><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mi>c</mi></mrow><mo>,</mo>
+            <mrow arg='b'><mi>x</mi><mo>+</mo><mi>y</mi></mrow>
+            <mo>)</mo>
+        </mrow>
+    </math>";
+    test_overview("en", vec![], expr, "y is equal to a greatest common divisor");
+}
@@ -110,8 +110,8 @@ fn prime() {
 #[test]
 fn given() {
     let expr = "<math><mi>P</mi><mo>(</mo><mi>A</mi><mo>|</mo><mi>B</mi><mo>)</mo></math>";
-    test("en", "SimpleSpeak", expr, "cap p, open paren, cap eigh vertical line cap b; close paren");
-    test("en", "ClearSpeak", expr,  "cap p, open paren, cap eigh divides cap b, close paren");  // not good, but follows the spec
+    test("en", "SimpleSpeak", expr, "the probability of cap eigh given cap b");
+    test("en", "ClearSpeak", expr,  "the probability of cap eigh given cap b");
 }
 
 #[test]
@@ -201,7 +201,7 @@ fn ignore_period() {
       </annotation-xml>
     </semantics>  
   </math>";
-    test("en", "SimpleSpeak", expr, "cap p; open paren, cap eigh and cap b; close paren; is equal to; cap p, open paren, cap eigh intersection cap b; close paren; is equal to, cap p of cap eigh, cap p of cap b");
+    test("en", "SimpleSpeak", expr, "the probability of cap eigh and cap b; is equal to, the probability of cap eigh intersection cap b; is equal to, the probability of cap eigh, the probability of cap b");
 }
 
 #[test]
@@ -293,3 +293,217 @@ fn mn_with_space() {
     let expr = "<math><mn>1 234 567</mn></math>";
     test("en", "SimpleSpeak", expr, "1234567");
 }
+
+#[test]
+fn speech_sound_earcons() {
+    let expr = "<math><mi>x</mi><mo>+</mo><mn>1</mn></math>";
+    test_prefs("en", "SimpleSpeak", vec![("SpeechSound", "Beep")], expr, "[sound:math-start.mp4] x plus 1 [sound:math-end.mp4]");
+    test_prefs("en", "SimpleSpeak", vec![("SpeechSound", "Beep"), ("TTS", "SSML")], expr,
+        "<audio src='math-start.mp4'>math</audio> <say-as interpret-as='characters'>x</say-as> plus 1 <audio src='math-end.mp4'>end math</audio>");
+    // SAPI5 can't play sounds, so the text fallback is spoken
+    test_prefs("en", "SimpleSpeak", vec![("SpeechSound", "Beep"), ("TTS", "SAPI5")], expr,
+        "math <spell>x</spell> plus 1 end math");
+}
+
+#[test]
+fn eloquence_markup() {
+    let expr = "<math><mfrac><mrow><mi>a</mi><mo>+</mo><mn>1</mn></mrow><mi>b</mi></mfrac><mo>=</mo><msup><mi>x</mi><mi>n</mi></msup></math>";
+    test_prefs("en", "SimpleSpeak", vec![("TTS", "Eloquence")], expr,
+        "fraction `p150 `ts1 a`ts0 plus 1 `p150 over `ts1 b`ts0 `p150 end fraction `p300 is equal to `ts1 x`ts0 to the `ts1 n`ts0 `[T]");
+    test_prefs("en", "SimpleSpeak", vec![("TTS", "Eloquence"), ("CapitalLetters_Pitch", "50")], "<math><mi>A</mi></math>",
+        "cap `vb44 `ts1 a`ts0 `vb26");
+}
+
+#[test]
+fn mac_markup() {
+    let expr = "<math><mfrac><mrow><mi>a</mi><mo>+</mo><mn>1</mn></mrow><mi>b</mi></mfrac><mo>=</mo><msup><mi>x</mi><mi>n</mi></msup></math>";
+    test_prefs("en", "SimpleSpeak", vec![("TTS", "Mac")], expr,
+        "fraction [[slnc 150]] [[char LTRL]]a[[char NORM]] plus 1 [[slnc 150]] over [[char LTRL]]b[[char NORM]] [[slnc 150]] end fraction [[slnc 300]]is equal to [[char LTRL]]x[[char NORM]] to the [[char LTRL]]n[[char NORM]] [[inpt PHON]]T[[inpt TEXT]]");
+    test_prefs("en", "SimpleSpeak", vec![("TTS", "Mac"), ("CapitalLetters_Pitch", "50")], "<math><mi>A</mi></math>",
+        "cap [[pbas +7]][[char LTRL]]a[[char NORM]][[pbas -7]]");
+}
+
+#[test]
+fn ssml_document() {
+    let expr = "<math><mfrac><mi>A</mi><mi>b</mi></mfrac><mo>=</mo><mtext>a&lt;b &amp; c</mtext></math>";
+    test_prefs("en", "SimpleSpeak", vec![("TTS", "SSMLDocument"), ("CapitalLetters_Pitch", "50")], expr,
+        "<speak version='1.1' xmlns='http://www.w3.org/2001/10/synthesis' xml:lang='en'>\
+         cap <prosody pitch='+50%'><say-as interpret-as='characters'>a</say-as></prosody> over <say-as interpret-as='characters'>b</say-as> \
+         <break time='150ms'/> is equal to a&lt;b &amp;c</speak>");
+}
+
+#[test]
+fn ssml_document_validator() {
+    use crate::common::ssml::validate_ssml;
+    assert!(validate_ssml("<speak version='1.1' xmlns='http://www.w3.org/2001/10/synthesis' xml:lang='en-US'>x <break time='300ms'/>\
+                <prosody pitch='+50%' volume='-6dB'><say-as interpret-as='characters'>a</say-as></prosody></speak>").is_ok());
+    assert!(validate_ssml("<speak version='1.1' xml:lang='en'>x</speak>").is_err());                           // no namespace
+    assert!(validate_ssml("<speak version='1.1' xmlns='http://www.w3.org/2001/10/synthesis'>x</speak>").is_err());   // no xml:lang
+    assert!(validate_ssml("<speak version='1.1' xmlns='http://www.w3.org/2001/10/synthesis' xml:lang='en'>a < b</speak>").is_err());
+    assert!(validate_ssml("<speak version='1.1' xmlns='http://www.w3.org/2001/10/synthesis' xml:lang='en'>\
+                <voice required='gender=\"female\"'>x</voice></speak>").is_err());
+    assert!(validate_ssml("<speak version='1.1' xmlns='http://www.w3.org/2001/10/synthesis' xml:lang='en'>\
+                <prosody pitch='50%'>x</prosody></speak>").is_err());
+    assert!(validate_ssml("<speak version='1.1' xmlns='http://www.w3.org/2001/10/synthesis' xml:lang='en'>\
+                <say-as interpret-as='characters'><break time='10ms'/>x</say-as></speak>").is_err());
+}
+
+#[test]
+fn speech_overrides() {
+    use libmathcat::interface::{set_rules_dir, set_preference, set_speech_override, remove_speech_override};
+    set_rules_dir(abs_rules_dir_path()).unwrap();
+    set_preference("Language".to_string(), "en".to_string()).unwrap();
+    set_speech_override("≤".to_string(), "at most".to_string()).unwrap();
+    set_speech_override("mod".to_string(), "modulo".to_string()).unwrap();
+    set_speech_override("θ".to_string(), "[pronounce: [{text: theta}, {ipa: 'θiːtə'}]]".to_string()).unwrap();
+    set_speech_override("greatest-common-divisor".to_string(), "g c d".to_string()).unwrap();
+    let expr = "<math><mi>x</mi><mo>≤</mo><mn>7</mn><mo>mod</mo><mn>2</mn><mo>+</mo><mi>θ</mi></math>";
+    test("en", "SimpleSpeak", expr, "x at most, 7 modulo 2 plus theta");
+    let expr = "<math><mrow intent='greatest-common-divisor($a,$b)'>
+            <mi>gcd</mi><mo>&#x2061;</mo><mo>(</mo><mi arg='a'>a</mi><mo>,</mo><mi arg='b'>b</mi><mo>)</mo>
+        </mrow></math>";
+    test("en", "SimpleSpeak", expr, "g c d of, eigh comma b");
+    remove_speech_override("≤".to_string()).unwrap();
+    test("en", "SimpleSpeak", "<math><mi>x</mi><mo>≤</mo><mn>7</mn></math>", "x is less than or equal to 7");
+    test_prefs("en", "SimpleSpeak", vec![("TTS", "SSML")], "<math><mi>θ</mi></math>", "<phoneme alphabet='ipa' ph='θiːtə'>theta</phoneme>");
+}
+
+#[test]
+fn speech_overrides_parens() {
+    let expr = "<math><mn>2</mn><mo>(</mo><mi>x</mi><mo>+</mo><mn>1</mn><mo>)</mo></math>";
+    test_prefs("en", "SimpleSpeak", vec![("SpeechOverrides_LeftParen", "open"), ("SpeechOverrides_RightParen", "close")], expr,
+        "2 times, open x plus 1 close");
+}
+
+#[test]
+fn subject_area_calculus() {
+    let expr = "<math><msup><mi>f</mi><mo>′</mo></msup><mo>(</mo><mi>x</mi><mo>)</mo></math>";
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "General")], expr, "f prime, of x");
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "Calculus")], expr, "the derivative of f, of x");
+    let expr = "<math><msup><mi>y</mi><mo>″</mo></msup><mo>=</mo><mn>0</mn></math>";
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "Calculus")], expr, "the second derivative of y, is equal to 0");
+    let expr = "<math><mover><mi>x</mi><mo>˙</mo></mover><mo>+</mo><mover><mi>x</mi><mo>¨</mo></mover></math>";
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "General")], expr, "x dot, plus x double dot,");
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "Calculus")], expr, "the derivative of x plus the second derivative of x");
+}
+
+#[test]
+fn subject_area_statistics() {
+    let expr = "<math><mover><mi>x</mi><mo>¯</mo></mover></math>";
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "Statistics")], expr, "the mean of x");
+    // subject area without any special rules
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "Chemistry")], expr, "x bar,");
+}
+
+#[test]
+fn subject_area_attr() {
+    // the expression's subject area overrides the preference
+    let expr = "<math data-subject-area='Statistics'><mover><mi>x</mi><mo>¯</mo></mover></math>";
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "Calculus")], expr, "the mean of x");
+    let expr = "<math><mover><mi>x</mi><mo>¯</mo></mover></math>";
+    test_prefs("en", "SimpleSpeak", vec![("SubjectArea", "Calculus")], expr, "x bar,");
+}
+
+#[test]
+fn impairment_blindness() {
+    let expr = "<math><mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mi>c</mi></mfrac><mo>+</mo><msqrt><mi>x</mi><mo>+</mo><mn>1</mn></msqrt><mo>+</mo><mi>X</mi></math>";
+    test_prefs("en", "SimpleSpeak", vec![("Impairment", "Blindness")], expr,
+        "fraction, eigh plus b, over c, end fraction; plus, the square root of x plus 1 end root; plus cap x");
+}
+
+#[test]
+fn impairment_low_vision() {
+    let expr = "<math><mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mi>c</mi></mfrac><mo>+</mo><msqrt><mi>x</mi><mo>+</mo><mn>1</mn></msqrt><mo>+</mo><mi>X</mi></math>";
+    test_prefs("en", "SimpleSpeak", vec![("Impairment", "LowVision")], expr,
+        "fraction, eigh plus b, over c; plus, the square root of x plus 1; plus x");
+    // bookmarks are generated so that the AT can highlight what is being spoken
+    let expr = "<math><mi id='id-1'>x</mi><mo id='id-2'>+</mo><mn id='id-3'>1</mn></math>";
+    test_prefs("en", "SimpleSpeak", vec![("Impairment", "LowVision"), ("TTS", "SSML")], expr,
+        "<mark name='id-1'/> <say-as interpret-as='characters'>x</say-as> <mark name='id-2'/> plus <mark name='id-3'/> 1");
+}
+
+#[test]
+fn impairment_learning_disability() {
+    let expr = "<math><mfrac><mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow><mi>c</mi></mfrac><mo>+</mo><msup><mi>x</mi><mrow><mi>n</mi><mo>+</mo><mn>1</mn></mrow></msup></math>";
+    // fractions are spoken more naturally than with the usual "fraction ... over ..." phrasing
+    test_prefs("en", "ClearSpeak", vec![("Impairment", "LearningDisability")], expr,
+        "eigh plus b, divided by c; plus, x raised to the n plus 1 power");
+    test_prefs("en", "SimpleSpeak", vec![("Impairment", "LearningDisability")], expr,
+        "eigh plus b, divided by c; plus, x raised to the n plus 1 power");
+    // ...unless a fraction style was chosen
+    test_prefs("en", "ClearSpeak", vec![("Impairment", "LearningDisability"), ("ClearSpeak_Fractions", "General")], expr,
+        "the fraction with numerator; eigh plus b; and denominator c; plus, x raised to the n plus 1 power");
+    // pauses are 50% longer than usual
+    test_prefs("en", "SimpleSpeak", vec![("Impairment", "LearningDisability"), ("TTS", "SSML")], expr,
+        "<say-as interpret-as='characters'>a</say-as> plus <say-as interpret-as='characters'>b</say-as> <break time='225ms'/> divided by <say-as interpret-as='characters'>c</say-as> <break time='450ms'/>plus <say-as interpret-as='characters'>x</say-as> raised to the <say-as interpret-as='characters'>n</say-as> plus 1 power");
+}
+
+#[test]
+fn sync_map() {
+    let expr = "<math id='id-0'><mrow id='id-1'>
+            <mfrac id='id-2'><mi id='id-3'>a</mi><mi id='id-4'>b</mi></mfrac>
+            <mo id='id-5'>+</mo>
+            <msup id='id-6'><mi id='id-7'>x</mi><mn id='id-8'>2</mn></msup>
+        </mrow></math>";
+    let words = vec![
+        ("eigh", "id-3"), ("over", "id-2"), ("b", "id-4"), ("plus", "id-5"), ("x", "id-7"), ("squared", "id-6")
+    ];
+    test_sync_map("SimpleSpeak", vec![], expr, words.clone());
+    test_sync_map("SimpleSpeak", vec![("Impairment", "LowVision")], expr, words);
+    // the ranges skip the markup (including the bookmarks generated for LowVision)
+    test_sync_map("SimpleSpeak", vec![("TTS", "SSML")], expr,
+        vec![("a", "id-3"), ("over", "id-2"), ("b", "id-4"), ("plus", "id-5"), ("x", "id-7"), ("squared", "id-6")]);
+}
+
+#[test]
+fn sync_map_concat() {
+    let expr = "<math id='id-0'><msup id='id-1'><mi id='id-2'>x</mi><mi id='id-3'>n</mi></msup></math>";
+    test_sync_map("ClearSpeak", vec![], expr,
+        vec![("x", "id-2"), ("to", "id-1"), ("the", "id-1"), ("n-th", "id-3"), ("power", "id-1")]);
+}
+
+#[test]
+fn mstack_addition() {
+    let expr = "<math><mstack>
+            <mscarries><none/><mn>1</mn><none/></mscarries>
+            <mn>178</mn>
+            <msrow><mo>+</mo><mn>46</mn></msrow>
+            <msline/>
+            <mn>224</mn>
+        </mstack></math>";
+    test("en", "SimpleSpeak", expr,
+        "column addition with 3 rows; carry 1 in column 2; row 1, 178; row 2, plus, 46; line; row 3, 224");
+    test_prefs("en", "SimpleSpeak", vec![("ElementaryMath", "ByDigit")], expr,
+        "column addition with 3 rows; carry 1 in column 2; row 1, 1, 7, 8; row 2, plus, 4, 6; line; row 3, 2, 2, 4");
+}
+
+#[test]
+fn mstack_subtraction_borrow() {
+    let expr = "<math><mstack>
+            <mscarries crossout='updiagonalstrike'><mn>4</mn><mn>12</mn></mscarries>
+            <mn>52</mn>
+            <msrow><mo>-</mo><mn>17</mn></msrow>
+            <msline/>
+            <mn>35</mn>
+        </mstack></math>";
+    test("en", "SimpleSpeak", expr,
+        "column subtraction with 3 rows; in column 1 cross out 5 and write 4, in column 2 cross out 2 and write 12; \
+         row 1, 52; row 2, minus, 17; line; row 3, 35");
+}
+
+#[test]
+fn mlongdiv() {
+    let expr = "<math><mlongdiv longdivstyle='lefttop'>
+            <mn>3</mn>
+            <mn>435</mn>
+            <mn>1306</mn>
+            <msgroup position='2' shift='-1'>
+                <msgroup><mn>12</mn><msline length='2'/></msgroup>
+                <msgroup><mn>10</mn><mn>9</mn><msline length='2'/></msgroup>
+                <msgroup><mn>16</mn><mn>15</mn><msline length='2'/><mn>1</mn></msgroup>
+            </msgroup>
+        </mlongdiv></math>";
+    test("en", "SimpleSpeak", expr,
+        "long division, 1306 divided by 3; result 435; steps; row 1, 12; line; row 2, 10; row 3, 9; line; \
+         row 4, 16; row 5, 15; line; row 6, 1");
+}
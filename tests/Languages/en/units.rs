@@ -0,0 +1,99 @@
+/// Tests for quantities with units of measure (e.g., "9.8 m/s²")
+use crate::common::*;
+
+#[test]
+fn meters_per_second_squared() {
+    let expr = "<math><mn>9.8</mn><mi mathvariant='normal'>m</mi><mo>/</mo><msup><mi mathvariant='normal'>s</mi><mn>2</mn></msup></math>";
+    test("en", "SimpleSpeak", expr, "9.8 meters per second squared");
+}
+
+#[test]
+fn prefixed_unit() {
+    let expr = "<math><mn>3</mn><mi>mL</mi></math>";
+    test("en", "SimpleSpeak", expr, "3 milliliters");
+}
+
+#[test]
+fn singular_unit() {
+    let expr = "<math><mn>1</mn><mi>kg</mi></math>";
+    test("en", "SimpleSpeak", expr, "1 kilogram");
+}
+
+#[test]
+fn product_of_units() {
+    let expr = "<math><mn>5</mn><mi>kg</mi><mo>·</mo><msup><mi mathvariant='normal'>m</mi><mn>2</mn></msup></math>";
+    test("en", "SimpleSpeak", expr, "5 kilogram meters squared");
+}
+
+#[test]
+fn negative_power_unit() {
+    let expr = "<math><mn>6</mn><mi mathvariant='normal'>m</mi><mo>⋅</mo><msup><mi mathvariant='normal'>s</mi><mrow><mo>-</mo><mn>1</mn></mrow></msup></math>";
+    test("en", "SimpleSpeak", expr, "6 meters per second");
+}
+
+#[test]
+fn mtext_unit() {
+    let expr = "<math><mn>12</mn><mtext>ft</mtext></math>";
+    test("en", "SimpleSpeak", expr, "12 feet");
+}
+
+#[test]
+fn degrees_celsius() {
+    let expr = "<math><msup><mn>25</mn><mo>°</mo></msup><mi mathvariant='normal'>C</mi></math>";
+    test("en", "SimpleSpeak", expr, "25 degrees Celsius");
+}
+
+#[test]
+fn negative_degrees_fahrenheit() {
+    let expr = "<math><mo>-</mo><msup><mn>40</mn><mo>°</mo></msup><mi mathvariant='normal'>F</mi></math>";
+    test("en", "SimpleSpeak", expr, "negative 40 degrees Fahrenheit");
+}
+
+#[test]
+fn units_in_sum() {
+    let expr = "<math><mn>2</mn><mi>km</mi><mo>+</mo><mn>300</mn><mi mathvariant='normal'>m</mi></math>";
+    test("en", "SimpleSpeak", expr, "2 kilometers plus 300 meters");
+}
+
+#[test]
+fn italic_var_not_unit() {
+    let expr = "<math><mn>3</mn><mi>m</mi></math>";
+    test("en", "SimpleSpeak", expr, "3 m");
+}
+
+#[test]
+fn explicit_unit() {
+    let expr = "<math><mn>7</mn><mi intent=':unit'>furlong</mi></math>";
+    test("en", "SimpleSpeak", expr, "7 furlong");
+}
+
+#[test]
+fn units_clearspeak() {
+    let expr = "<math><mn>20</mn><mi>km</mi><mo>/</mo><mi mathvariant='normal'>h</mi></math>";
+    test("en", "ClearSpeak", expr, "20 kilometers per hour");
+}
+
+#[test]
+fn chemistry_not_units() {
+    let expr = "<math><mn>2</mn><msub><mi mathvariant='normal'>N</mi><mn>2</mn></msub><mo>+</mo>
+                      <mn>3</mn><msub><mi mathvariant='normal'>H</mi><mn>2</mn></msub><mo>→</mo>
+                      <mn>2</mn><mi mathvariant='normal'>N</mi><msub><mi mathvariant='normal'>H</mi><mn>3</mn></msub></math>";
+    test("en", "SimpleSpeak", expr, "2 cap n, sub 2, plus 3 cap h, sub 2; reacts to form, 2, cap n, cap h, sub 3");
+    let expr = "<math><mn>2</mn><mi mathvariant='normal'>K</mi><mo>+</mo><msub><mi>Cl</mi><mn>2</mn></msub></math>";
+    test("en", "SimpleSpeak", expr, "2 cap k; plus cap c l, sub 2");
+}
+
+#[test]
+fn element_symbol_not_unit() {
+    // "C" could be coulombs, but a lone element symbol needs more evidence to be a unit
+    let expr = "<math><mn>6</mn><mi mathvariant='normal'>C</mi></math>";
+    test("en", "SimpleSpeak", expr, "6 cap c");
+    let expr = "<math><mn>5</mn><mi mathvariant='normal'>N</mi><mo>⋅</mo><mi mathvariant='normal'>m</mi></math>";
+    test("en", "SimpleSpeak", expr, "5 newton meters");
+}
+
+#[test]
+fn function_with_braces_not_unit() {
+    let expr = "<math><mn>2</mn><mi>min</mi><mo>{</mo><mi>a</mi><mo>,</mo><mi>b</mi><mo>}</mo></math>";
+    test("en", "SimpleSpeak", expr, "2, min of the set eigh comma b");
+}
@@ -0,0 +1,64 @@
+//! Checks that speech for the newer intents and features is in the language (not English).
+use crate::common::*;
+
+#[test]
+fn subject_area_calculus() {
+    // there are no calculus speech rules for this language, so the calculus intents aren't used
+    let expr = "<math><msup><mi>f</mi><mo>′</mo></msup><mo>(</mo><mi>x</mi><mo>)</mo></math>";
+    test_prefs("es", "SimpleSpeak", vec![("SubjectArea", "Calculus")], expr, "f prima, de x");
+    test_prefs("es", "SimpleSpeak", vec![("SubjectArea", "General")], expr, "f prima, de x");
+}
+
+#[test]
+fn overview() {
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <mfrac>
+            <mrow><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mi>c</mi></mrow>
+            <mrow><mi>x</mi><mo>−</mo><mn>1</mn></mrow>
+        </mfrac>
+        <mo>+</mo><mn>1</mn>
+    </math>";
+    test_overview("es", vec![], expr, "y es igual a, una fracción más 1");
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <mrow><mo>[</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+            <mtr><mtd><mn>3</mn></mtd><mtd><mn>4</mn></mtd></mtr>
+        </mtable><mo>]</mo></mrow>
+    </math>";
+    test_overview("es", vec![], expr, "y es igual a una matriz de 2 por 2");
+}
+
+#[test]
+fn integral_article() {
+    // "integral" and "suma" are feminine
+    let expr = "<math><msubsup><mo>∫</mo><mn>0</mn><mn>1</mn></msubsup><msup><mi>x</mi><mn>2</mn></msup><mi>d</mi><mi>x</mi></math>";
+    test("es", "SimpleSpeak", expr, "la integral de 0 a 1 de x al cuadrado, con respecto a x");
+    let expr = "<math><mo>∫</mo><msup><mi>x</mi><mn>2</mn></msup><mi>d</mi><mi>x</mi></math>";
+    test("es", "SimpleSpeak", expr, "la integral de x al cuadrado, con respecto a x");
+    let expr = "<math><munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover><mi>i</mi></math>";
+    test("es", "SimpleSpeak", expr, "la suma de i es igual a 1 a n de i");
+}
+
+#[test]
+fn logic() {
+    // quantifiers are spoken by the logic rules; the logical connectives are spoken with the concept names
+    let expr = "<math> <mo>∀</mo><mi>x</mi><mo>∈</mo><mi>S</mi><mo>,</mo><mo>∃</mo><mi>y</mi><mo>:</mo><mi>P</mi><mo>(</mo><mi>x</mi><mo>,</mo><mi>y</mi><mo>)</mo> </math>";
+    test("es", "SimpleSpeak", expr, "para todo x en mayúscula s; existe y tal que mayúscula p de, se abren paréntesis, x coma y, se cierran paréntesis");
+    let expr = "<math> <mo>∀</mo><mi>ε</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mo>∃</mo><mi>δ</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mi>x</mi><mo>&lt;</mo><mi>δ</mi> </math>";
+    test("es", "SimpleSpeak", expr, "para todo épsilon mayor que 0; existe delta mayor que 0 tal que x es menor que delta");
+    let expr = "<math> <mi>p</mi><mo>⇒</mo><mi>q</mi> </math>";
+    test("es", "SimpleSpeak", expr, "p implica q");
+    let expr = "<math> <mo>¬</mo><mo>(</mo><mi>p</mi><mo>∧</mo><mi>q</mi><mo>)</mo><mo>⇔</mo><mo>¬</mo><mi>p</mi><mo>∨</mo><mo>¬</mo><mi>q</mi> </math>";
+    test("es", "SimpleSpeak", expr, "no, se abren paréntesis, p y q, se cierran paréntesis si y solo si, no p o no q");
+}
+
+#[test]
+fn chemical_compounds() {
+    // the names come from the language's chemical-compounds.yaml
+    let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+    test_prefs("es", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "agua,");
+    let expr = "<math><mrow><mi>Na</mi><mi>Cl</mi><mo stretchy='false'>(</mo><mi>aq</mi><mo stretchy='false'>)</mo></mrow></math>";
+    test_prefs("es", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "cloruro de sodio, acuoso,");
+}
@@ -286,3 +286,15 @@ fn mn_with_space() {
     let expr = "<math><mn>1 234 567</mn></math>";
     test("en", "SimpleSpeak", expr, "1234567");
 }
+
+#[test]
+fn units_per_second_squared() {
+    let expr = "<math><mn>9.8</mn><mi mathvariant='normal'>m</mi><mo>/</mo><msup><mi mathvariant='normal'>s</mi><mn>2</mn></msup></math>";
+    test("es", "SimpleSpeak", expr, "9.8 metros por segundo cuadrado");
+}
+
+#[test]
+fn units_singular() {
+    let expr = "<math><mn>1</mn><mi>km</mi></math>";
+    test("es", "SimpleSpeak", expr, "1 kilómetro");
+}
@@ -0,0 +1,73 @@
+//! Checks that speech for the newer intents and features is in the language (not English).
+use crate::common::*;
+
+#[test]
+fn subject_area_calculus() {
+    // there are no calculus speech rules for this language, so the calculus intents aren't used
+    let expr = "<math><msup><mi>f</mi><mo>′</mo></msup><mo>(</mo><mi>x</mi><mo>)</mo></math>";
+    test_prefs("id", "SimpleSpeak", vec![("SubjectArea", "Calculus")], expr, "f aksen, x");
+    test_prefs("id", "SimpleSpeak", vec![("SubjectArea", "General")], expr, "f aksen, x");
+}
+
+#[test]
+fn overview() {
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <mfrac>
+            <mrow><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mi>c</mi></mrow>
+            <mrow><mi>x</mi><mo>−</mo><mn>1</mn></mrow>
+        </mfrac>
+        <mo>+</mo><mn>1</mn>
+    </math>";
+    test_overview("id", vec![], expr, "y sama dengan, pecahan tambah 1");
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <mrow><mo>[</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+            <mtr><mtd><mn>3</mn></mtd><mtd><mn>4</mn></mtd></mtr>
+        </mtable><mo>]</mo></mrow>
+    </math>";
+    test_overview("id", vec![], expr, "y sama dengan matriks 2 kali 2");
+}
+
+#[test]
+fn concept_names() {
+    // the concept names come from the language's intent-concepts.yaml
+    let expr = "<math><mrow intent='maximum($a, $b)'><mi arg='a'>a</mi><mi arg='b'>b</mi></mrow></math>";
+    test("id", "SimpleSpeak", expr, "maksimum , eigh koma b");
+    let expr = "<math><mrow intent='proportional-to($a, $b)'><mi arg='a'>a</mi><mi arg='b'>b</mi></mrow></math>";
+    test("id", "SimpleSpeak", expr, "eigh sebanding dengan b");
+}
+
+#[test]
+fn probability() {
+    // probability notation is spoken with the concept names, so these need to be translated
+    let expr = "<math><mi>P</mi><mo>(</mo><mi>A</mi><mo>|</mo><mi>B</mi><mo>)</mo></math>";
+    test("id", "SimpleSpeak", expr, "peluang , eigh diberikan b");
+    let expr = "<math><mover><mi>z</mi><mo>¯</mo></mover></math>";
+    test("id", "SimpleSpeak", expr, "konjugat kompleks , z");
+    let expr = "<math><mi>X</mi><mo>~</mo><mi>N</mi><mo>(</mo><mi>μ</mi><mo>,</mo><msup><mi>σ</mi><mn>2</mn></msup><mo>)</mo></math>";
+    test("id", "SimpleSpeak", expr, "x berdistribusi, distribusi normal , mu koma sigma kuadrat");
+}
+
+#[test]
+fn logic() {
+    // quantifiers are spoken by the logic rules; the logical connectives are spoken with the concept names
+    let expr = "<math> <mo>∀</mo><mi>x</mi><mo>∈</mo><mi>S</mi><mo>,</mo><mo>∃</mo><mi>y</mi><mo>:</mo><mi>P</mi><mo>(</mo><mi>x</mi><mo>,</mo><mi>y</mi><mo>)</mo> </math>";
+    test("id", "SimpleSpeak", expr, "untuk semua x di s; ada y sehingga p, buka kurung x koma y, tutup kurung");
+    let expr = "<math> <mo>∀</mo><mi>ε</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mo>∃</mo><mi>δ</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mi>x</mi><mo>&lt;</mo><mi>δ</mi> </math>";
+    test("id", "SimpleSpeak", expr, "untuk semua epsilon lebih dari 0; ada delta lebih dari 0 sehingga x kurang dari delta");
+    let expr = "<math> <mi>p</mi><mo>⇒</mo><mi>q</mi> </math>";
+    test("id", "SimpleSpeak", expr, "p mengakibatkan q");
+    let expr = "<math> <mo>¬</mo><mo>(</mo><mi>p</mi><mo>∧</mo><mi>q</mi><mo>)</mo><mo>⇔</mo><mo>¬</mo><mi>p</mi><mo>∨</mo><mo>¬</mo><mi>q</mi> </math>";
+    test("id", "SimpleSpeak", expr, "bukan, buka kurung p dan q, tutup kurung jika dan hanya jika, bukan p atau bukan q");
+}
+
+#[test]
+fn chemical_compounds() {
+    // the names come from the language's chemical-compounds.yaml
+    let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+    test_prefs("id", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "air,");
+    let expr = "<math><mrow><mi>Na</mi><mi>Cl</mi><mo stretchy='false'>(</mo><mi>aq</mi><mo stretchy='false'>)</mo></mrow></math>";
+    test_prefs("id", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "natrium klorida, encer,");
+}
@@ -0,0 +1,73 @@
+//! Checks that speech for the newer intents and features is in the language (not English).
+use crate::common::*;
+
+#[test]
+fn subject_area_calculus() {
+    // there are no calculus speech rules for this language, so the calculus intents aren't used
+    let expr = "<math><msup><mi>f</mi><mo>′</mo></msup><mo>(</mo><mi>x</mi><mo>)</mo></math>";
+    test_prefs("vi", "SimpleSpeak", vec![("SubjectArea", "Calculus")], expr, "f phẩy, của x");
+    test_prefs("vi", "SimpleSpeak", vec![("SubjectArea", "General")], expr, "f phẩy, của x");
+}
+
+#[test]
+fn overview() {
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <mfrac>
+            <mrow><mi>a</mi><mo>+</mo><mi>b</mi><mo>+</mo><mi>c</mi></mrow>
+            <mrow><mi>x</mi><mo>−</mo><mn>1</mn></mrow>
+        </mfrac>
+        <mo>+</mo><mn>1</mn>
+    </math>";
+    test_overview("vi", vec![], expr, "y bằng, một phân số cộng 1");
+    let expr = "<math>
+        <mi>y</mi><mo>=</mo>
+        <mrow><mo>[</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+            <mtr><mtd><mn>3</mn></mtd><mtd><mn>4</mn></mtd></mtr>
+        </mtable><mo>]</mo></mrow>
+    </math>";
+    test_overview("vi", vec![], expr, "y bằng ma trận 2 nhân 2");
+}
+
+#[test]
+fn concept_names() {
+    // the concept names come from the language's intent-concepts.yaml
+    let expr = "<math><mrow intent='maximum($a, $b)'><mi arg='a'>a</mi><mi arg='b'>b</mi></mrow></math>";
+    test("vi", "SimpleSpeak", expr, "giá trị lớn nhất của, a phẩy b");
+    let expr = "<math><mrow intent='proportional-to($a, $b)'><mi arg='a'>a</mi><mi arg='b'>b</mi></mrow></math>";
+    test("vi", "SimpleSpeak", expr, "a tỉ lệ với b");
+}
+
+#[test]
+fn probability() {
+    // probability notation is spoken with the concept names, so these need to be translated
+    let expr = "<math><mi>P</mi><mo>(</mo><mi>A</mi><mo>|</mo><mi>B</mi><mo>)</mo></math>";
+    test("vi", "SimpleSpeak", expr, "xác suất của, a với điều kiện b");
+    let expr = "<math><mover><mi>θ</mi><mo>^</mo></mover></math>";
+    test("vi", "SimpleSpeak", expr, "ước lượng của, tê ta");
+    let expr = "<math><mi>X</mi><mo>~</mo><mi>N</mi><mo>(</mo><mi>μ</mi><mo>,</mo><msup><mi>σ</mi><mn>2</mn></msup><mo>)</mo></math>";
+    test("vi", "SimpleSpeak", expr, "x có phân phối; phân phối chuẩn của, mu phẩy xích ma bình phương");
+}
+
+#[test]
+fn logic() {
+    // quantifiers are spoken by the logic rules; the logical connectives are spoken with the concept names
+    let expr = "<math> <mo>∀</mo><mi>x</mi><mo>∈</mo><mi>S</mi><mo>,</mo><mo>∃</mo><mi>y</mi><mo>:</mo><mi>P</mi><mo>(</mo><mi>x</mi><mo>,</mo><mi>y</mi><mo>)</mo> </math>";
+    test("vi", "SimpleSpeak", expr, "với mọi x thuộc s; tồn tại ít nhất y sao cho p của, mở ngoặc đơn, x phẩy y, đóng ngoặc đơn");
+    let expr = "<math> <mo>∀</mo><mi>ε</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mo>∃</mo><mi>δ</mi><mo>&gt;</mo><mn>0</mn><mo>,</mo><mi>x</mi><mo>&lt;</mo><mi>δ</mi> </math>";
+    test("vi", "SimpleSpeak", expr, "với mọi ép si lon lớn hơn 0; tồn tại ít nhất đen ta lớn hơn 0 sao cho x nhỏ hơn đen ta");
+    let expr = "<math> <mi>p</mi><mo>⇒</mo><mi>q</mi> </math>";
+    test("vi", "SimpleSpeak", expr, "p suy ra q");
+    let expr = "<math> <mo>¬</mo><mo>(</mo><mi>p</mi><mo>∧</mo><mi>q</mi><mo>)</mo><mo>⇔</mo><mo>¬</mo><mi>p</mi><mo>∨</mo><mo>¬</mo><mi>q</mi> </math>";
+    test("vi", "SimpleSpeak", expr, "không, mở ngoặc đơn, p và q, đóng ngoặc đơn tương đương, không p hoặc không q");
+}
+
+#[test]
+fn chemical_compounds() {
+    // the names come from the language's chemical-compounds.yaml
+    let expr = "<math><msub><mi>H</mi><mn>2</mn></msub><mi>O</mi></math>";
+    test_prefs("vi", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "nước,");
+    let expr = "<math><mrow><mi>Na</mi><mi>Cl</mi><mo stretchy='false'>(</mo><mi>aq</mi><mo stretchy='false'>)</mo></mrow></math>";
+    test_prefs("vi", "SimpleSpeak", vec![("Chemistry", "AsCompound")], expr, "natri clorua, thể lỏng,");
+}
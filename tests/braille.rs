@@ -18,6 +18,10 @@ mod braille {
         mod once;
     }
 
+    mod LaTeX {
+        mod latex;
+    }
+
     // mod Vietnam {
     //     mod vi;
     // }
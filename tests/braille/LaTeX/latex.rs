@@ -0,0 +1,111 @@
+// LaTeX-style braille tests
+// The braille is shown using the default (8-dot) NorthAmerican table; the comment shows the LaTeX text
+use crate::common::*;
+
+#[test]
+fn frac() {
+    let expr = "<math><mfrac><mrow><mi>x</mi><mo>+</mo><mn>1</mn></mrow><mn>2</mn></mfrac></math>";
+    // \frac{x+1}{2}
+    test_braille("LaTeX", expr, "⡳⠋⠗⠁⠉⠪⠭⠬⠂⠻⠪⠆⠻");
+}
+
+#[test]
+fn scripts() {
+    let expr = "<math><msup><mi>x</mi><mn>2</mn></msup><mo>+</mo><msup><mi>y</mi><mrow><mn>2</mn><mi>n</mi></mrow></msup></math>";
+    // x^2+y^{2n}
+    test_braille("LaTeX", expr, "⠭⡘⠆⠬⠽⡘⠪⠆⠝⠻");
+}
+
+#[test]
+fn function_names_and_greek() {
+    let expr = "<math><mi>sin</mi><mo>&#x2061;</mo><mi>x</mi><mo>+</mo><mi>α</mi><mo>≤</mo><mi>β</mi></math>";
+    // \sin x+\alpha\le\beta
+    test_braille("LaTeX", expr, "⡳⠎⠊⠝⠀⠭⠬⡳⠁⠇⠏⠓⠁⡳⠇⠑⡳⠃⠑⠞⠁");
+}
+
+#[test]
+fn sum() {
+    let expr = "<math><munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow><mi>n</mi></munderover><msub><mi>a</mi><mi>i</mi></msub></math>";
+    // \sum_{i=1}^na_i
+    test_braille("LaTeX", expr, "⡳⠎⠥⠍⡸⠪⠊⠿⠂⠻⡘⠝⠁⡸⠊");
+}
+
+#[test]
+fn limit() {
+    let expr = "<math><munder><mi>lim</mi><mrow><mi>x</mi><mo>→</mo><mn>0</mn></mrow></munder>
+                    <mfrac><mrow><mi>sin</mi><mo>&#x2061;</mo><mi>x</mi></mrow><mi>x</mi></mfrac></math>";
+    // \lim_{x\to0}\frac{\sin x}{x}
+    test_braille("LaTeX", expr, "⡳⠇⠊⠍⡸⠪⠭⡳⠞⠕⠴⠻⡳⠋⠗⠁⠉⠪⡳⠎⠊⠝⠀⠭⠻⠪⠭⠻");
+}
+
+#[test]
+fn roots() {
+    let expr = "<math><msqrt><mi>x</mi></msqrt><mo>+</mo><mroot><mi>y</mi><mn>3</mn></mroot></math>";
+    // \sqrt{x}+\sqrt[3]{y}
+    test_braille("LaTeX", expr, "⡳⠎⠟⠗⠞⠪⠭⠻⠬⡳⠎⠟⠗⠞⡪⠒⡻⠪⠽⠻");
+}
+
+#[test]
+fn matrix() {
+    let expr = "<math><mrow><mo>[</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mn>2</mn></mtd></mtr>
+            <mtr><mtd><mn>3</mn></mtd><mtd><mn>4</mn></mtd></mtr>
+        </mtable><mo>]</mo></mrow></math>";
+    // \begin{bmatrix}1&2\\3&4\end{bmatrix}
+    test_braille("LaTeX", expr, "⡳⠃⠑⠛⠊⠝⠪⠃⠍⠁⠞⠗⠊⠭⠻⠂⠯⠆⡳⡳⠒⠯⠲⡳⠑⠝⠙⠪⠃⠍⠁⠞⠗⠊⠭⠻");
+}
+
+#[test]
+fn cases() {
+    let expr = "<math><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>=</mo><mrow><mo>{</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mi>x</mi><mo>&gt;</mo><mn>0</mn></mtd></mtr>
+            <mtr><mtd><mn>0</mn></mtd><mtd><mtext>otherwise</mtext></mtd></mtr>
+        </mtable></mrow></math>";
+    // f(x)=\begin{cases}1&x>0\\0&\text{otherwise}\end{cases}
+    test_braille("LaTeX", expr, "⠋⠷⠭⠾⠿⡳⠃⠑⠛⠊⠝⠪⠉⠁⠎⠑⠎⠻⠂⠯⠭⠜⠴⡳⡳⠴⠯⡳⠞⠑⠭⠞⠪⠕⠞⠓⠑⠗⠺⠊⠎⠑⠻⡳⠑⠝⠙⠪⠉⠁⠎⠑⠎⠻");
+}
+
+#[test]
+fn set_builder() {
+    let expr = "<math><mi>A</mi><mo>∪</mo><mi>B</mi><mo>=</mo>
+            <mo>{</mo><mi>x</mi><mo>∣</mo><mi>x</mi><mo>∈</mo><mi mathvariant='double-struck'>R</mi><mo>}</mo></math>";
+    // A\cup B=\{x\mid x\in\mathbb{R}\}
+    test_braille("LaTeX", expr, "⡁⡳⠉⠥⠏⠀⡃⠿⡳⠪⠭⡳⠍⠊⠙⠀⠭⡳⠊⠝⡳⠍⠁⠞⠓⠃⠃⠪⡗⠻⡳⠻");
+}
+
+#[test]
+fn text() {
+    let expr = "<math><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>=</mo><mn>0</mn><mtext>&#xA0;for all&#xA0;</mtext><mi>x</mi></math>";
+    // f(x)=0\text{ for all }x
+    test_braille("LaTeX", expr, "⠋⠷⠭⠾⠿⠴⡳⠞⠑⠭⠞⠪⠀⠋⠕⠗⠀⠁⠇⠇⠀⠻⠭");
+}
+
+#[test]
+fn accent_and_prime() {
+    let expr = "<math><mover><mi>x</mi><mo>¯</mo></mover><mo>+</mo><mi>α</mi><mi>x</mi><mo>+</mo>
+                    <msup><mi>f</mi><mo>′</mo></msup><mo>(</mo><mi>x</mi><mo>)</mo></math>";
+    // \overline{x}+\alpha x+f'(x)
+    test_braille("LaTeX", expr, "⡳⠕⠧⠑⠗⠇⠊⠝⠑⠪⠭⠻⠬⡳⠁⠇⠏⠓⠁⠀⠭⠬⠋⠄⠷⠭⠾");
+}
+
+#[test]
+fn binomial() {
+    let expr = "<math><mrow><mo>(</mo><mfrac linethickness='0'><mi>n</mi><mi>k</mi></mfrac><mo>)</mo></mrow></math>";
+    // \binom{n}{k}
+    test_braille("LaTeX", expr, "⡳⠃⠊⠝⠕⠍⠪⠝⠻⠪⠅⠻");
+}
+
+#[test]
+fn bold_and_names() {
+    let expr = "<math><mi mathvariant='bold'>v</mi><mo>=</mo><mn>3</mn><mi>𝐢</mi><mo>,</mo><mi>speed</mi><mo>=</mo><mn>3.5</mn><mo>%</mo></math>";
+    // \mathbf{v}=3\mathbf{i},\mathrm{speed}=3.5\%
+    test_braille("LaTeX", expr, "⡳⠍⠁⠞⠓⠃⠋⠪⠧⠻⠿⠒⡳⠍⠁⠞⠓⠃⠋⠪⠊⠻⠠⡳⠍⠁⠞⠓⠗⠍⠪⠎⠏⠑⠑⠙⠻⠿⠒⠨⠢⡳⠩");
+}
+
+#[test]
+fn braille_ascii_table() {
+    let expr = "<math><mi>Γ</mi><mo>(</mo><mi>n</mi><mo>)</mo><mo>=</mo><mo>(</mo><mi>n</mi><mo>−</mo><mn>1</mn><mo>)</mo><mo>!</mo></math>";
+    // \Gamma(n)=(n-1)!
+    test_braille("LaTeX", expr, "⡳⡛⠁⠍⠍⠁⠷⠝⠾⠿⠷⠝⠤⠂⠾⠮");
+    test_braille_prefs("LaTeX", vec![("LaTeX_BrailleTable", "BrailleASCII")], expr, "⠳⠛⠁⠍⠍⠁⠷⠝⠾⠿⠷⠝⠤⠂⠾⠮");
+}
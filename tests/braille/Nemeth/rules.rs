@@ -1913,3 +1913,67 @@ fn chem_2_5_1_mchem() {
     // The example uses a short right arrow but chemistry normally uses a long one -- this test has a long right arrow so that char differs from the reference
     test_braille("Nemeth", expr, "⠠⠉⠁⠠⠉⠆⠬⠆⠠⠓⠠⠕⠠⠓⠀⠫⠒⠒⠒⠕⠀⠠⠓⠸⠒⠻⠠⠉⠸⠿⠻⠠⠉⠸⠒⠻⠠⠓⠬⠠⠉⠁⠷⠠⠕⠠⠓⠾⠰⠆");
 }
+
+#[test]
+fn chem_two_letter_element_numeric_sub() {
+    // chemical element symbols are treated as a unit, so the numeric subscript doesn't need a subscript indicator
+    let expr = "<math><msub><mi>Na</mi><mn>2</mn></msub><mi>S</mi><msub><mi>O</mi><mn>4</mn></msub></math>";
+    test_braille("Nemeth", expr, "⠠⠝⠁⠆⠠⠎⠠⠕⠲");
+}
+
+#[test]
+fn chem_arrow_with_condition_over() {
+    let expr = "<math><mi>CaC</mi><msub><mi>O</mi><mn>3</mn></msub><mover><mo>⟶</mo><mi>Δ</mi></mover>
+                <mi>CaO</mi><mo>+</mo><mi>C</mi><msub><mi>O</mi><mn>2</mn></msub></math>";
+    test_braille("Nemeth", expr, "⠠⠉⠁⠠⠉⠠⠕⠒⠀⠐⠫⠒⠒⠒⠕⠣⠨⠠⠙⠻⠀⠠⠉⠁⠠⠕⠬⠠⠉⠠⠕⠆");
+}
+
+#[test]
+fn chem_arrow_with_conditions_over_under() {
+    let expr = "<math><msub><mi mathvariant='normal'>N</mi><mn>2</mn></msub><mo>+</mo><mn>3</mn><msub><mi mathvariant='normal'>H</mi><mn>2</mn></msub>
+                <munderover><mo>⇌</mo><mi>Fe</mi><mi>Δ</mi></munderover>
+                <mn>2</mn><mi mathvariant='normal'>N</mi><msub><mi mathvariant='normal'>H</mi><mn>3</mn></msub></math>";
+    test_braille("Nemeth", expr, "⠠⠝⠆⠬⠒⠠⠓⠆⠀⠐⠫⠒⠒⠈⠕⠫⠈⠪⠒⠒⠩⠠⠋⠑⠣⠨⠠⠙⠻⠀⠼⠆⠠⠝⠠⠓⠒");
+}
+
+// spatial layouts (BrailleSpatialLayout) -- rows are separated by '\n'
+#[test]
+fn spatial_addition() {
+    let expr = "<math><mstack><mn>424</mn><msrow><mo>+</mo><mn>33</mn></msrow><msline/><mn>457</mn></mstack></math>";
+    test_braille_prefs("Nemeth", vec![("BrailleSpatialLayout", "true")], expr, "⠀⠲⠆⠲\n⠬⠀⠒⠒\n⠒⠒⠒⠒\n⠀⠲⠢⠶");
+}
+
+#[test]
+fn spatial_addition_carries() {
+    let expr = "<math><mstack><mscarries><none/><mn>1</mn><none/></mscarries><mn>178</mn>
+                <msrow><mo>+</mo><mn>46</mn></msrow><msline/><mn>224</mn></mstack></math>";
+    test_braille_prefs("Nemeth", vec![("BrailleSpatialLayout", "true")], expr, "⠀⠀⠂⠀\n⠀⠂⠶⠦\n⠬⠀⠲⠖\n⠒⠒⠒⠒\n⠀⠆⠆⠲");
+}
+
+#[test]
+fn spatial_long_division() {
+    let expr = "<math><mlongdiv longdivstyle='lefttop'><mn>3</mn><mn>435</mn><mn>1306</mn>
+            <msgroup position='2' shift='-1'>
+                <msgroup><mn>12</mn><msline length='2'/></msgroup>
+                <msgroup><mn>10</mn><mn>9</mn><msline length='2'/></msgroup>
+                <msgroup><mn>16</mn><mn>15</mn><msline length='2'/><mn>1</mn></msgroup>
+            </msgroup>
+        </mlongdiv></math>";
+    test_braille_prefs("Nemeth", vec![("BrailleSpatialLayout", "true")], expr,
+        "⠀⠀⠀⠲⠒⠢\n⠀⠀⠒⠒⠒⠒\n⠒⠾⠂⠒⠴⠖\n⠀⠀⠂⠆\n⠀⠀⠒⠒\n⠀⠀⠀⠂⠴\n⠀⠀⠀⠀⠔\n⠀⠀⠀⠒⠒\n⠀⠀⠀⠀⠂⠖\n⠀⠀⠀⠀⠂⠢\n⠀⠀⠀⠀⠒⠒\n⠀⠀⠀⠀⠀⠂");
+}
+
+#[test]
+fn spatial_matrix() {
+    let expr = "<math><mrow><mo>[</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mn>20</mn></mtd></mtr>
+            <mtr><mtd><mi>x</mi></mtd><mtd><mn>3</mn></mtd></mtr>
+        </mtable><mo>]</mo></mrow></math>";
+    test_braille_prefs("Nemeth", vec![("BrailleSpatialLayout", "true")], expr, "⠠⠈⠷⠼⠂⠀⠼⠆⠴⠠⠈⠾\n⠠⠈⠷⠰⠭⠀⠼⠒⠀⠠⠈⠾");
+}
+
+#[test]
+fn spatial_embedded_matrix_is_linear() {
+    let expr = "<math><mi>x</mi><mo>+</mo><mrow><mo>[</mo><mtable><mtr><mtd><mn>1</mn></mtd></mtr></mtable><mo>]</mo></mrow></math>";
+    test_braille_prefs("Nemeth", vec![("BrailleSpatialLayout", "true")], expr, "⠭⠬⠈⠷⠂⠈⠾");
+}
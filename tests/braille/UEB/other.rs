@@ -103,3 +103,125 @@ fn contractions_5() {
     let expr = "<math><mi>Real</mi><mo>(</mo><mi>z</mi><mo>)</mo></math>";
     test_braille("UEB", expr, "⠠⠗⠂⠇⠐⠣⠵⠐⠜");
 }
+
+// grade 2 contractions in text (mtext)
+#[test]
+fn contractions_text_for_all() {
+    let expr = "<math><mi>f</mi><mo>(</mo><mi>x</mi><mo>)</mo><mo>=</mo><mn>0</mn><mtext>&#xA0;for all&#xA0;</mtext><mi>x</mi></math>";
+    test_braille("UEB", expr, "⠋⠐⠣⠭⠐⠜⠀⠐⠶⠀⠼⠚⠀⠿⠀⠁⠇⠇⠀⠰⠭");
+}
+
+#[test]
+fn contractions_text_where() {
+    let expr = "<math><mi>x</mi><mo>=</mo><mn>2</mn><mtext>&#xA0;where&#xA0;</mtext><mi>x</mi><mo>&gt;</mo><mn>0</mn></math>";
+    test_braille("UEB", expr, "⠰⠭⠀⠐⠶⠀⠼⠃⠀⠐⠱⠀⠰⠭⠀⠈⠜⠀⠼⠚");
+}
+
+#[test]
+fn contractions_text_short_form() {
+    let expr = "<math><mi>y</mi><mo>=</mo><mn>2</mn><mi>x</mi><mtext>&#xA0;such that&#xA0;</mtext><mi>x</mi><mo>&gt;</mo><mn>0</mn></math>";
+    test_braille("UEB", expr, "⠰⠽⠀⠐⠶⠀⠼⠃⠭⠀⠎⠡⠀⠞⠀⠰⠭⠀⠈⠜⠀⠼⠚");
+}
+
+#[test]
+fn contractions_text_groupsigns() {
+    // RUEB 10.3, 10.4, 10.6: strong and lower groupsigns inside words
+    let expr = "<math><mtext>the distance between them</mtext></math>";
+    test_braille("UEB", expr, "⠮⠀⠲⠞⠨⠑⠀⠆⠞⠀⠮⠍");
+    let expr = "<math><mtext>continuous&#xA0;function</mtext></math>";
+    test_braille("UEB", expr, "⠒⠞⠔⠥⠳⠎⠀⠋⠥⠝⠉⠰⠝");
+}
+
+#[test]
+fn contractions_text_final_letter() {
+    // RUEB 10.8: final-letter groupsigns, including "ation" and "ally"
+    let expr = "<math><mtext>equation&#xA0;totally&#xA0;compound</mtext></math>";
+    test_braille("UEB", expr, "⠑⠟⠥⠠⠝⠀⠞⠕⠞⠠⠽⠀⠉⠕⠍⠏⠨⠙");
+}
+
+#[test]
+fn contractions_text_not_ueb() {
+    // "ble" and "com" are not contracted in UEB
+    let expr = "<math><mtext>variable&#xA0;combine</mtext></math>";
+    test_braille("UEB", expr, "⠧⠜⠊⠁⠃⠇⠑⠀⠉⠕⠍⠃⠔⠑");
+}
+
+#[test]
+fn contractions_text_beginning_groupsign() {
+    // RUEB 10.6.5: "be", "con", "dis" are used when they are the first syllable of a word
+    let expr = "<math><mtext>being&#xA0;beta&#xA0;convex&#xA0;discrete</mtext></math>";
+    test_braille("UEB", expr, "⠆⠬⠀⠆⠞⠁⠀⠒⠧⠑⠭⠀⠲⠉⠗⠑⠞⠑");
+}
+
+#[test]
+fn contractions_text_beginning_groupsign_not_syllable() {
+    // RUEB 10.6.5: the letters "be", "con", "dis" aren't the first syllable of these words ("cone" still uses "one")
+    let expr = "<math><mtext>best&#xA0;bean&#xA0;cone&#xA0;conic&#xA0;disc</mtext></math>";
+    test_braille("UEB", expr, "⠃⠑⠌⠀⠃⠂⠝⠀⠉⠐⠕⠀⠉⠕⠝⠊⠉⠀⠙⠊⠎⠉");
+}
+
+#[test]
+fn contractions_text_initial_letter() {
+    let expr = "<math><mtext>Therefore&#xA0;</mtext><mi>x</mi><mo>=</mo><mn>3</mn></math>";
+    test_braille("UEB", expr, "⠠⠐⠮⠿⠑⠀⠰⠭⠀⠐⠶⠀⠼⠉");
+}
+
+#[test]
+fn contractions_text_grade1_passage() {
+    // no contractions in grade 1 passage mode
+    let expr = "<math><mtext>time</mtext><mo>=</mo><mfrac><mtext>distance</mtext><mtext>speed</mtext></mfrac></math>";
+    test_braille("UEB", expr, "⠰⠰⠰⠞⠊⠍⠑⠀⠐⠶⠀⠷⠙⠊⠎⠞⠁⠝⠉⠑⠨⠌⠎⠏⠑⠑⠙⠾⠰⠄");
+}
+
+#[test]
+fn contractions_standing_alone_groupsign() {
+    // "st" and "ch" standing alone would be read as the wordsigns "still" and "child"
+    let expr = "<math><mtext>st&#xA0;and&#xA0;ch</mtext></math>";
+    test_braille("UEB", expr, "⠎⠞⠀⠯⠀⠉⠓");
+}
+
+#[test]
+fn contractions_short_form_letters() {
+    // "sch" would be contracted to the short form for "such", so it needs a grade 1 indicator
+    let expr = "<math><mi>s</mi><mi>c</mi><mi>h</mi></math>";
+    test_braille("UEB", expr, "⠰⠎⠉⠓");
+}
+
+// spatial layouts (BrailleSpatialLayout) -- rows are separated by '\n'
+#[test]
+fn spatial_addition() {
+    let expr = "<math><mstack><mn>424</mn><msrow><mo>+</mo><mn>33</mn></msrow><msline/><mn>457</mn></mstack></math>";
+    test_braille_prefs("UEB", vec![("BrailleSpatialLayout", "true")], expr, "⠀⠀⠼⠙⠃⠙\n⠐⠖⠀⠼⠉⠉\n⠒⠒⠒⠒⠒⠒\n⠀⠀⠼⠙⠑⠛");
+}
+
+#[test]
+fn spatial_multiplication() {
+    let expr = "<math><mstack><mn>123</mn><msrow><mo>×</mo><mn>12</mn></msrow><msline/>
+                <mn>246</mn><msrow position='1'><mn>123</mn></msrow><msline/><mn>1476</mn></mstack></math>";
+    test_braille_prefs("UEB", vec![("BrailleSpatialLayout", "true")], expr,
+        "⠀⠀⠼⠁⠃⠉\n⠐⠦⠀⠼⠁⠃\n⠒⠒⠒⠒⠒⠒\n⠀⠀⠼⠃⠙⠋\n⠀⠼⠁⠃⠉\n⠒⠒⠒⠒⠒⠒\n⠀⠼⠁⠙⠛⠋");
+}
+
+#[test]
+fn spatial_matrix() {
+    let expr = "<math><mrow><mo>[</mo><mtable>
+            <mtr><mtd><mn>1</mn></mtd><mtd><mn>20</mn></mtd></mtr>
+            <mtr><mtd><mi>x</mi></mtd><mtd><mn>3</mn></mtd></mtr>
+        </mtable><mo>]</mo></mrow></math>";
+    test_braille_prefs("UEB", vec![("BrailleSpatialLayout", "true")], expr, "⠨⠣⠼⠁⠀⠼⠃⠚⠨⠜\n⠨⠣⠰⠭⠀⠼⠉⠀⠨⠜");
+}
+
+#[test]
+fn chem_arrow_with_condition_over() {
+    let expr = "<math><mi>CaC</mi><msub><mi>O</mi><mn>3</mn></msub><mover><mo>⟶</mo><mi>Δ</mi></mover>
+                <mi>CaO</mi><mo>+</mo><mi>C</mi><msub><mi>O</mi><mn>2</mn></msub></math>";
+    test_braille("UEB", expr, "⠰⠰⠰⠠⠉⠁⠠⠉⠠⠕⠢⠼⠉⠀⠳⠒⠒⠒⠕⠨⠔⠠⠨⠙⠀⠠⠉⠁⠠⠕⠐⠖⠠⠉⠠⠕⠢⠼⠃⠰⠄");
+}
+
+#[test]
+fn chem_arrow_with_conditions_over_under() {
+    let expr = "<math><msub><mi mathvariant='normal'>N</mi><mn>2</mn></msub><mo>+</mo><mn>3</mn><msub><mi mathvariant='normal'>H</mi><mn>2</mn></msub>
+                <munderover><mo>⇌</mo><mi>Fe</mi><mi>Δ</mi></munderover>
+                <mn>2</mn><mi mathvariant='normal'>N</mi><msub><mi mathvariant='normal'>H</mi><mn>3</mn></msub></math>";
+    test_braille("UEB", expr, "⠰⠰⠰⠠⠝⠢⠼⠃⠐⠖⠼⠉⠠⠓⠢⠼⠃⠀⠘⠸⠶⠨⠢⠣⠠⠋⠑⠜⠨⠔⠠⠨⠙⠀⠼⠃⠠⠝⠠⠓⠢⠼⠉⠰⠄");
+}
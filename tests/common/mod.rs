@@ -6,6 +6,8 @@ extern crate lazy_static;
 use lazy_static::lazy_static;
 use libmathcat::interface::*;
 
+pub mod ssml;
+
 #[allow(dead_code)] 
 pub fn init_logger() {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("debug"))
@@ -43,6 +45,17 @@ fn check_answer(test: &str, target: &str, failure_message: &str) {
         Ok(speech) => assert_eq!(target, strip_spaces(speech), "\ntest with {} failed", failure_message),
         Err(e) => panic!("{}", errors_to_string(&e)),
     };    
+
+    // all speech should also be a valid SSML document
+    let tts = get_preference("TTS".to_string()).unwrap();
+    set_preference("TTS".to_string(), "SSMLDocument".to_string()).unwrap();
+    match get_spoken_text() {
+        Ok(speech) => if let Err(e) = ssml::validate_ssml(&speech) {
+            panic!("\ntest with {} failed: invalid SSML -- {}\n{}", failure_message, e, speech);
+        },
+        Err(e) => panic!("{}", errors_to_string(&e)),
+    };
+    set_preference("TTS".to_string(), tts).unwrap();
 }
 
 // Compare the result of speaking the mathml input to the output 'speech'
@@ -85,6 +98,59 @@ pub fn test_prefs(language: &str, speech_style: &str, test_prefs: Vec<(&str, &st
     check_answer(mathml, speech, &format!("{}/{} with prefs {:#?}", language, speech_style, test_prefs));
 }
 
+// Compare the overview of the mathml input to the output 'overview'
+// This takes a vector of (pref_name, pref_value) -- OverviewDepth is reset to its default value after the test
+#[allow(dead_code)]     // used in testing
+pub fn test_overview(language: &str, test_prefs: Vec<(&str, &str)>, mathml: &str, overview: &str) {
+    set_rules_dir(abs_rules_dir_path()).unwrap();
+    libmathcat::speech::SPEECH_RULES.with(|rules| {
+        let rules = rules.borrow_mut();
+        let mut prefs = rules.pref_manager.borrow_mut();
+        prefs.set_user_prefs("SpeechOverrides_CapitalLetters", "");         // makes testing simpler
+        prefs.set_user_prefs("PauseFactor", "100");                         // makes testing simpler
+        prefs.set_user_prefs("Verbosity", "Medium");
+    });
+
+    set_preference("Language".to_string(), language.to_string()).unwrap();
+    for (pref_name, pref_value) in test_prefs.clone() {
+        set_preference(pref_name.to_string(), pref_value.to_string()).unwrap();
+    };
+    if let Err(e) = set_mathml(mathml.to_string()) {
+        panic!("{}", errors_to_string(&e));
+    };
+    match get_overview_text() {
+        Ok(speech) => assert_eq!(overview, strip_spaces(speech), "\noverview test with {:#?} failed", test_prefs),
+        Err(e) => panic!("{}", errors_to_string(&e)),
+    };
+    set_preference("OverviewDepth".to_string(), "1".to_string()).unwrap();
+}
+
+// Compare the (word, id) pairs from the sync map of the mathml input to 'words'
+// This takes the speech style along with a vector of (pref_name, pref_value)
+#[allow(dead_code)]     // used in testing
+pub fn test_sync_map(speech_style: &str, test_prefs: Vec<(&str, &str)>, mathml: &str, words: Vec<(&str, &str)>) {
+    set_rules_dir(abs_rules_dir_path()).unwrap();
+    set_preference("Language".to_string(), "en".to_string()).unwrap();
+    set_preference("SpeechStyle".to_string(), speech_style.to_string()).unwrap();
+    for (pref_name, pref_value) in test_prefs.clone() {
+        set_preference(pref_name.to_string(), pref_value.to_string()).unwrap();
+    };
+    if let Err(e) = set_mathml(mathml.to_string()) {
+        panic!("{}", errors_to_string(&e));
+    };
+    match get_spoken_text_with_sync_map() {
+        Ok( (speech, sync_map) ) => {
+            let chars: Vec<char> = speech.chars().collect();
+            let map_words: Vec<(String, &str)> = sync_map.iter()
+                    .map(|(start, end, id)| (chars[*start..*end].iter().collect(), id.as_str()))
+                    .collect();
+            let words: Vec<(String, &str)> = words.iter().map(|(word, id)| (word.to_string(), *id)).collect();
+            assert_eq!(words, map_words, "\nsync map test with {:#?} failed for speech '{}'", test_prefs, speech);
+        },
+        Err(e) => panic!("{}", errors_to_string(&e)),
+    };
+}
+
 // Compare the result of speaking the mathml input to the output 'speech'
 // This forces the use of ClearSpeak and sets a single ClearSpeak preference
 #[allow(dead_code)]     // used in testing
@@ -0,0 +1,117 @@
+//! Checks that speech generated with `TTS=SSMLDocument` is a well-formed SSML 1.1 document.
+//! This is not a full schema validator, but it checks the elements/attributes/values that are defined by the SSML 1.1 spec
+//! (https://www.w3.org/TR/speech-synthesis11/) and the content model restrictions for the elements MathCAT might generate.
+#![allow(dead_code)]     // not all test files use this
+#![allow(clippy::needless_return)]
+
+use regex::Regex;
+use lazy_static::lazy_static;
+use sxd_document::{parser, dom::*};
+
+const SSML_NAMESPACE: &str = "http://www.w3.org/2001/10/synthesis";
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+lazy_static! {
+    static ref LANGUAGE_TAG: Regex = Regex::new(r"^[a-zA-Z]{2,3}(-[a-zA-Z0-9]{1,8})*$").unwrap();
+    static ref TIME: Regex = Regex::new(r"^\d+(\.\d+)?(ms|s)$").unwrap();
+    static ref PITCH: Regex = Regex::new(r"^([+-]\d+(\.\d+)?(Hz|st|%)|\d+(\.\d+)?Hz|x-low|low|medium|high|x-high|default)$").unwrap();
+    static ref RATE: Regex = Regex::new(r"^(\d+(\.\d+)?%|x-slow|slow|medium|fast|x-fast|default)$").unwrap();
+    static ref VOLUME: Regex = Regex::new(r"^([+-]\d+(\.\d+)?dB|silent|x-soft|soft|medium|loud|x-loud|default)$").unwrap();
+}
+
+/// Returns an error message if 'ssml' is not a valid SSML 1.1 document
+pub fn validate_ssml(ssml: &str) -> Result<(), String> {
+    let package = parser::parse(ssml).map_err(|e| format!("SSML is not well-formed XML ({:?})", e))?;
+    let doc = package.as_document();
+    let root = match doc.root().children().as_slice() {
+        [ChildOfRoot::Element(root)] => *root,
+        _ => return Err("SSML document must have exactly one root element".to_string()),
+    };
+    if root.name().local_part() != "speak" {
+        return Err(format!("root element must be 'speak', not '{}'", root.name().local_part()));
+    }
+    if root.attribute_value("version") != Some("1.1") {
+        return Err("'speak' must have version='1.1'".to_string());
+    }
+    match root.attribute_value((XML_NAMESPACE, "lang")) {
+        None => return Err("'speak' must have an 'xml:lang' attribute".to_string()),
+        Some(lang) => check_value("speak", "xml:lang", lang, &LANGUAGE_TAG)?,
+    }
+    return validate_element(root, true);
+}
+
+fn validate_element(element: Element, is_root: bool) -> Result<(), String> {
+    let name = element.name().local_part();
+    if element.name().namespace_uri() != Some(SSML_NAMESPACE) {
+        return Err(format!("'{}' is not in the SSML namespace", name));
+    }
+
+    // allowed attributes, required attributes, and whether child elements are allowed
+    let (allowed, required, elements_allowed): (&[&str], &[&str], bool) = match name {
+        "speak" if is_root => (&["version", "lang", "base", "onlangfailure"], &["version", "lang"], true),
+        "speak" => return Err("'speak' can only be the root element".to_string()),
+        "break" => (&["time", "strength"], &[], false),
+        "mark" => (&["name"], &["name"], false),
+        "prosody" => (&["pitch", "contour", "range", "rate", "duration", "volume"], &[], true),
+        "say-as" => (&["interpret-as", "format", "detail"], &["interpret-as"], false),
+        "phoneme" => (&["ph", "alphabet"], &["ph"], false),
+        "sub" => (&["alias"], &["alias"], false),
+        "audio" => (&["src", "fetchtimeout", "fetchhint", "maxage", "maxstale", "clipBegin", "clipEnd",
+                      "repeatCount", "repeatDur", "soundLevel", "speed"], &["src"], true),
+        "voice" => (&["gender", "age", "variant", "name", "languages", "required", "ordering", "onvoicefailure"], &[], true),
+        "emphasis" => (&["level"], &[], true),
+        "p" | "s" => (&["lang", "onlangfailure"], &[], true),
+        "lang" => (&["lang", "onlangfailure"], &["lang"], true),
+        _ => return Err(format!("'{}' is not an SSML 1.1 element", name)),
+    };
+
+    for attr in element.attributes() {
+        let attr_name = attr.name().local_part();
+        if !allowed.contains(&attr_name) {
+            return Err(format!("'{}' is not a legal attribute for '{}'", attr_name, name));
+        }
+        let value = attr.value();
+        match (name, attr_name) {
+            ("break", "time") => check_value(name, attr_name, value, &TIME)?,
+            ("prosody", "pitch") => check_value(name, attr_name, value, &PITCH)?,
+            ("prosody", "rate") => check_value(name, attr_name, value, &RATE)?,
+            ("prosody", "volume") => check_value(name, attr_name, value, &VOLUME)?,
+            ("voice", "gender") if !["male", "female", "neutral"].contains(&value) =>
+                return Err(format!("'{}' is not a legal value for 'gender' in 'voice'", value)),
+            (_, _) if value.is_empty() => return Err(format!("'{}' in '{}' is empty", attr_name, name)),
+            _ => (),
+        }
+    }
+    for required_attr in required {
+        if !element.attributes().iter().any(|attr| attr.name().local_part() == *required_attr) {
+            return Err(format!("'{}' is missing the required attribute '{}'", name, required_attr));
+        }
+    }
+    if name == "prosody" && element.attributes().is_empty() {
+        return Err("'prosody' must have at least one attribute".to_string());
+    }
+    if name == "voice" && !element.attributes().iter().any(|attr|
+            ["gender", "age", "variant", "name", "languages"].contains(&attr.name().local_part())) {
+        return Err("'voice' must have at least one of 'gender', 'age', 'variant', 'name', or 'languages'".to_string());
+    }
+    if (name == "break" || name == "mark") && !element.children().is_empty() {
+        return Err(format!("'{}' must be empty", name));
+    }
+
+    for child in element.children() {
+        if let ChildOfElement::Element(child) = child {
+            if !elements_allowed {
+                return Err(format!("'{}' can only contain text, but it contains '{}'", name, child.name().local_part()));
+            }
+            validate_element(child, false)?;
+        }
+    }
+    return Ok( () );
+}
+
+fn check_value(element_name: &str, attr_name: &str, value: &str, legal_values: &Regex) -> Result<(), String> {
+    if legal_values.is_match(value) {
+        return Ok( () );
+    }
+    return Err(format!("'{}' is not a legal value for '{}' in '{}'", value, attr_name, element_name));
+}
@@ -4,7 +4,15 @@ mod common;
 
 mod Languages {
     mod en;
-    // mod vi {
-    //     mod vi;
-    // }
+    // many of the older tests for the other languages are out of date -- only the 'localized' tests are run for them
+    mod es {
+        mod localized;
+    }
+    mod id {
+        mod localized;
+    }
+    mod vi {
+        // mod vi;
+        mod localized;
+    }
 }